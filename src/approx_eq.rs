@@ -0,0 +1,74 @@
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use hexf::hexf64;
+
+use crate::TwoFloat;
+
+// Spacing of `TwoFloat` values just above 1, used as the default absolute
+// and relative tolerance
+const DEFAULT_EPSILON: TwoFloat = TwoFloat {
+    hi: hexf64!("0x1.0p-105"),
+    lo: 0.0,
+};
+
+impl AbsDiffEq for TwoFloat {
+    type Epsilon = TwoFloat;
+
+    fn default_epsilon() -> TwoFloat {
+        DEFAULT_EPSILON
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: TwoFloat) -> bool {
+        (*self - *other).abs() <= epsilon
+    }
+}
+
+impl RelativeEq for TwoFloat {
+    fn default_max_relative() -> TwoFloat {
+        DEFAULT_EPSILON
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: TwoFloat, max_relative: TwoFloat) -> bool {
+        // Infinities are only equal to infinities with the same sign
+        if self.hi.is_infinite() || other.hi.is_infinite() {
+            return self.hi == other.hi;
+        }
+
+        if self == other {
+            return true;
+        }
+
+        let abs_diff = (*self - *other).abs();
+        if abs_diff <= epsilon {
+            return true;
+        }
+
+        let largest = self.abs().max(other.abs());
+        abs_diff <= largest * max_relative
+    }
+}
+
+impl UlpsEq for TwoFloat {
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: TwoFloat, max_ulps: u32) -> bool {
+        if self.abs_diff_eq(other, epsilon) {
+            return true;
+        }
+
+        if self.hi.is_infinite() || other.hi.is_infinite() {
+            return self.hi == other.hi;
+        }
+
+        if self.is_sign_negative() != other.is_sign_negative() {
+            return false;
+        }
+
+        // Units in the last place of a 106-bit significand, measured at the
+        // smaller value so that steps across a power of two are not
+        // undercounted
+        let smallest = self.abs().min(other.abs());
+        (*self - *other).abs() <= smallest.ulp() * max_ulps as f64
+    }
+}
@@ -4,7 +4,12 @@ use core::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 
-use crate::{math_util::mathfn, TwoFloat};
+use hexf::hexf64;
+
+use crate::{
+    math_util::{exponent, mathfn, mul_pow2},
+    TwoFloat,
+};
 
 pub(crate) fn fast_two_sum(a: f64, b: f64) -> TwoFloat {
     // Joldes et al. (2017) Algorithm 1
@@ -13,6 +18,348 @@ pub(crate) fn fast_two_sum(a: f64, b: f64) -> TwoFloat {
     TwoFloat { hi: s, lo: b - z }
 }
 
+// Adds `value` to the non-overlapping expansion stored in the first `len`
+// elements of `expansion` using the Grow-Expansion algorithm from Shewchuk
+// (1997), discarding zero components. Returns the new length.
+pub(crate) fn grow_expansion(expansion: &mut [f64], len: usize, value: f64) -> usize {
+    let mut q = value;
+    let mut new_len = 0;
+    for i in 0..len {
+        let (sum, err) = TwoFloat::new_add(q, expansion[i]).into();
+        q = sum;
+        if err != 0.0 {
+            expansion[new_len] = err;
+            new_len += 1;
+        }
+    }
+    if q != 0.0 {
+        expansion[new_len] = q;
+        new_len += 1;
+    }
+    new_len
+}
+
+// Rounds a non-overlapping expansion with components in increasing order of
+// magnitude to a `TwoFloat`.
+fn round_expansion(expansion: &[f64]) -> TwoFloat {
+    match expansion.len() {
+        0 => TwoFloat::from(0.0),
+        1 => TwoFloat::from(expansion[0]),
+        n => {
+            let tail = expansion[..n - 2].iter().fold(0.0, |acc, &x| acc + x);
+            let (mh, ml) = TwoFloat::new_add(expansion[n - 2], tail).into();
+            let (sh, sl) = TwoFloat::new_add(expansion[n - 1], mh).into();
+            fast_two_sum(sh, sl + ml)
+        }
+    }
+}
+
+// Compresses a non-overlapping expansion in place using the Compress
+// algorithm from Shewchuk (1997), so that no two components are adjacent.
+// Returns the new length.
+fn compress_expansion(expansion: &mut [f64], len: usize) -> usize {
+    if len < 2 {
+        return len;
+    }
+
+    let mut bottom = len - 1;
+    let mut q = expansion[bottom];
+    for i in (0..len - 1).rev() {
+        let (sum, err) = fast_two_sum(q, expansion[i]).into();
+        if err != 0.0 {
+            expansion[bottom] = sum;
+            bottom -= 1;
+            q = err;
+        } else {
+            q = sum;
+        }
+    }
+    expansion[bottom] = q;
+
+    let mut top = 0;
+    for i in bottom + 1..len {
+        let (sum, err) = fast_two_sum(expansion[i], q).into();
+        q = sum;
+        if err != 0.0 {
+            expansion[top] = err;
+            top += 1;
+        }
+    }
+    expansion[top] = q;
+    top + 1
+}
+
+// Subtracts `m * 2^scale * y` from the expansion exactly, where `m` is an
+// integer and `scale` is nonnegative. Returns the new length.
+fn sub_multiple(expansion: &mut [f64], mut len: usize, y: TwoFloat, m: f64, scale: i32) -> usize {
+    let (p0, e0) = TwoFloat::new_mul(m, y.hi).into();
+    let (p1, e1) = TwoFloat::new_mul(m, y.lo).into();
+    for &x in &[e1, p1, e0, p0] {
+        len = grow_expansion(expansion, len, -mul_pow2(x, scale));
+    }
+    len
+}
+
+// Returns the sign of the value of a zero-eliminated expansion.
+pub(crate) fn expansion_sign(expansion: &[f64]) -> f64 {
+    match expansion.last() {
+        Some(&x) => mathfn::signum(x),
+        None => 0.0,
+    }
+}
+
+// Products with a smaller magnitude than this may have inexact error terms.
+const PRODUCT_UNDERFLOW_LIMIT: f64 = hexf64!("0x1.0p-968");
+
+// Bound on the rounding error of the error term of an underflowing product.
+const PRODUCT_UNDERFLOW_SLACK: f64 = hexf64!("0x1.0p-1074");
+
+// Adds the product `a * b` to the expansion, returning the new length. If
+// the product underflows, a bound on its error is added to `slack`.
+pub(crate) fn grow_product(
+    expansion: &mut [f64],
+    len: usize,
+    a: f64,
+    b: f64,
+    slack: &mut f64,
+) -> usize {
+    let (p, e) = TwoFloat::new_mul(a, b).into();
+    if a != 0.0 && b != 0.0 && mathfn::abs(p) < PRODUCT_UNDERFLOW_LIMIT {
+        *slack += PRODUCT_UNDERFLOW_SLACK;
+    }
+    let len = grow_expansion(expansion, len, e);
+    grow_expansion(expansion, len, p)
+}
+
+// Returns the value that a result overflowing with the given sign is
+// rounded to in the specified direction.
+pub(crate) fn directed_overflow(positive: bool, up: bool) -> TwoFloat {
+    match (positive, up) {
+        (true, true) => TwoFloat::INFINITY,
+        (true, false) => TwoFloat::MAX,
+        (false, true) => TwoFloat::MIN,
+        (false, false) => TwoFloat::NEG_INFINITY,
+    }
+}
+
+// Steps `value` upwards or downwards until `needs_step` returns `false`,
+// then steps back while the neighbouring value also satisfies the bound.
+pub(crate) fn refine_directed(
+    mut value: TwoFloat,
+    up: bool,
+    mut needs_step: impl FnMut(TwoFloat) -> bool,
+) -> TwoFloat {
+    while value.is_valid() && needs_step(value) {
+        value = if up {
+            value.next_up()
+        } else {
+            value.next_down()
+        };
+    }
+
+    while value.is_valid() {
+        let back = if up {
+            value.next_down()
+        } else {
+            value.next_up()
+        };
+        if !back.is_valid() || needs_step(back) {
+            break;
+        }
+        value = back;
+    }
+    value
+}
+
+// Rounds the value of an expansion, known to within `slack`, to a
+// `TwoFloat` that is not less than (if `up`) or not greater than the value.
+fn round_expansion_directed(expansion: &[f64], slack: f64, up: bool) -> TwoFloat {
+    let mut bound = [0.0; 20];
+    bound[..expansion.len()].copy_from_slice(expansion);
+    let len = grow_expansion(&mut bound, expansion.len(), if up { slack } else { -slack });
+
+    let mut rounded = round_expansion(&bound[..len]);
+    if !rounded.is_valid() {
+        rounded = if expansion_sign(&bound[..len]) > 0.0 {
+            TwoFloat::MAX
+        } else {
+            TwoFloat::MIN
+        };
+    }
+
+    let direction = if up { 1.0 } else { -1.0 };
+    refine_directed(rounded, up, |value| {
+        let mut residual = bound;
+        let mut residual_len = grow_expansion(&mut residual, len, -value.lo);
+        residual_len = grow_expansion(&mut residual, residual_len, -value.hi);
+        expansion_sign(&residual[..residual_len]) == direction
+    })
+}
+
+// The long division in `remainder_impl` takes at most this many steps. Each
+// step with a positive shift lowers the exponent of the remainder by at least
+// 48, which can happen at most 44 times between 2^1024 and 2^-1021, and is
+// followed by at most four unshifted steps and one final adjustment.
+const REMAINDER_MAX_STEPS: usize = 50;
+
+// Each step adds at most four components to the two of `x`, and the tie
+// check for `RemainderMode::Nearest` adds the remainder to a copy of itself
+// after one more step.
+const REMAINDER_EXPANSION_LEN: usize = 2 * (2 + 4 * REMAINDER_MAX_STEPS) + 4;
+
+// Quotient of the long division, accumulated modulo 2^64 and exactly as an
+// expansion with at most one component per step.
+struct Quotient {
+    low_bits: u64,
+    expansion: [f64; REMAINDER_MAX_STEPS],
+    len: usize,
+    overflow: f64,
+}
+
+impl Quotient {
+    fn new() -> Self {
+        Self {
+            low_bits: 0,
+            expansion: [0.0; REMAINDER_MAX_STEPS],
+            len: 0,
+            overflow: 0.0,
+        }
+    }
+
+    fn add(&mut self, m: f64, scale: i32) {
+        if scale < 64 {
+            self.low_bits = self
+                .low_bits
+                .wrapping_add((m as i64 as u64).wrapping_shl(scale as u32));
+        }
+        let term = mul_pow2(m, scale);
+        if term.is_infinite() {
+            self.overflow = term;
+        } else {
+            self.len = grow_expansion(&mut self.expansion, self.len, term);
+        }
+    }
+
+    // Returns the quotient rounded to a `TwoFloat`.
+    fn value(&self) -> TwoFloat {
+        if self.overflow != 0.0 {
+            return TwoFloat::from(self.overflow);
+        }
+        let value = round_expansion(&self.expansion[..self.len]);
+        if value.hi.is_finite() {
+            value
+        } else {
+            TwoFloat::from(value.hi)
+        }
+    }
+}
+
+// Copies the expansion into `scratch` and subtracts `m * y` from the copy,
+// leaving the expansion unchanged. Returns the length of the difference.
+fn sub_multiple_copy(scratch: &mut [f64], expansion: &[f64], y: TwoFloat, m: f64) -> usize {
+    scratch[..expansion.len()].copy_from_slice(expansion);
+    sub_multiple(scratch, expansion.len(), y, m, 0)
+}
+
+// Subtracts `m * 2^scale * y` from the remainder expansion and adds
+// `m * 2^scale` to the quotient. Returns the new length.
+fn remainder_step(
+    expansion: &mut [f64],
+    len: usize,
+    y: TwoFloat,
+    m: f64,
+    scale: i32,
+    quotient: &mut Quotient,
+) -> usize {
+    quotient.add(m, scale);
+    let len = sub_multiple(expansion, len, y, m, scale);
+    compress_expansion(expansion, len)
+}
+
+// 1 - 2^-49, which covers the rounding errors in each quotient estimate.
+const QUOTIENT_BIAS: f64 = hexf64!("0x1.ffffffffffff0p-1");
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RemainderMode {
+    Truncate,
+    Nearest,
+    Euclid,
+}
+
+// Computes the remainder of `x - n * y` for finite operands and nonzero
+// finite `y`, where the integer `n` is chosen according to `mode`. The
+// remainder is evaluated exactly and rounded once. Returns the remainder
+// together with the quotient `n`.
+fn remainder_impl(x: TwoFloat, y: TwoFloat, mode: RemainderMode) -> (TwoFloat, Quotient) {
+    let mut r = [0.0; REMAINDER_EXPANSION_LEN];
+    // Trial differences are formed here, copying only the live components
+    let mut scratch = [0.0; REMAINDER_EXPANSION_LEN];
+    let mut len = grow_expansion(&mut r, 0, x.lo);
+    len = grow_expansion(&mut r, len, x.hi);
+
+    let mut quotient = Quotient::new();
+    let y_exponent = exponent(y.hi);
+    let y_sign = mathfn::signum(y.hi);
+    // Long division, retiring up to 53 quotient bits per iteration
+    loop {
+        let r_sign = expansion_sign(&r[..len]);
+        let diff_len = sub_multiple_copy(&mut scratch, &r[..len], y, r_sign * y_sign);
+        if len == 0 || (diff_len > 0 && expansion_sign(&scratch[..diff_len]) != r_sign) {
+            break;
+        }
+
+        // The quotient estimate is biased downwards so that `m * y` can
+        // never overflow or exceed the remainder in magnitude
+        let r_approx = round_expansion(&r[..len]).hi;
+        let shift = exponent(r_approx) - y_exponent - 53;
+        let (mut m, scale) = if shift > 0 {
+            let t = mul_pow2(r_approx, -shift) / y.hi;
+            (mathfn::trunc(t * QUOTIENT_BIAS), shift)
+        } else {
+            (mathfn::trunc(r_approx / y.hi * QUOTIENT_BIAS), 0)
+        };
+        if m == 0.0 {
+            m = r_sign * y_sign;
+        }
+        len = remainder_step(&mut r, len, y, m, scale, &mut quotient);
+    }
+
+    let r_sign = expansion_sign(&r[..len]);
+    let x_sign = mathfn::signum(x.hi);
+    match mode {
+        RemainderMode::Truncate => {
+            if r_sign == -x_sign {
+                len = remainder_step(&mut r, len, y, -x_sign * y_sign, 0, &mut quotient);
+            }
+        }
+        RemainderMode::Euclid => {
+            if r_sign < 0.0 {
+                len = remainder_step(&mut r, len, y, -y_sign, 0, &mut quotient);
+            }
+        }
+        RemainderMode::Nearest => {
+            if r_sign != 0.0 {
+                // Sign of 2|r| - |y|, evaluated as |r| - |y| + |r|
+                let mut diff_len = sub_multiple_copy(&mut scratch, &r[..len], y, r_sign * y_sign);
+                for &component in &r[..len] {
+                    diff_len = grow_expansion(&mut scratch, diff_len, component);
+                }
+                let excess = expansion_sign(&scratch[..diff_len]) * r_sign;
+                if excess > 0.0 || (excess == 0.0 && quotient.low_bits & 1 == 1) {
+                    len = remainder_step(&mut r, len, y, r_sign * y_sign, 0, &mut quotient);
+                }
+            }
+        }
+    }
+
+    if len == 0 {
+        let zero = if x.hi < 0.0 { -0.0 } else { 0.0 };
+        (TwoFloat::from(zero), quotient)
+    } else {
+        (round_expansion(&r[..len]), quotient)
+    }
+}
+
 impl TwoFloat {
     /// Creates a new `TwoFloat` by adding two `f64` values using Algorithm 2
     /// from Joldes et al. (2017).
@@ -168,7 +515,7 @@ binary_ops! {
     /// the low word.
     fn Div::div<'a, 'b>(self: &'a f64, rhs: &'b TwoFloat) -> TwoFloat {
         let th = rhs.hi.recip();
-        let rh = 1.0 - rhs.hi * th;
+        let rh = mathfn::fma(-rhs.hi, th, 1.0);
         let rl = -(rhs.lo * th);
         let (eh, el) = fast_two_sum(rh, rl).into();
         let e = TwoFloat { hi: eh, lo: el };
@@ -183,7 +530,7 @@ binary_ops! {
     /// (2017) Algorithm 18.
     fn Div::div<'a, 'b>(self: &'a TwoFloat, rhs: &'b TwoFloat) -> TwoFloat {
         let th = rhs.hi.recip();
-        let rh = 1.0 - rhs.hi * th;
+        let rh = mathfn::fma(-rhs.hi, th, 1.0);
         let rl = -(rhs.lo * th);
         let (eh, el) = fast_two_sum(rh, rl).into();
         let e = TwoFloat { hi: eh, lo: el };
@@ -193,18 +540,15 @@ binary_ops! {
     }
 
     fn Rem::rem<'a, 'b>(self: &'a TwoFloat, rhs: &'b f64) -> TwoFloat {
-        let quotient = (self / rhs).trunc();
-        self - quotient * rhs
+        self.fmod(TwoFloat::from(*rhs))
     }
 
     fn Rem::rem<'a, 'b>(self: &'a f64, rhs: &'b TwoFloat) -> TwoFloat {
-        let quotient = (self / rhs).trunc();
-        self - quotient * rhs
+        TwoFloat::from(*self).fmod(*rhs)
     }
 
     fn Rem::rem<'a, 'b>(self: &'a TwoFloat, rhs: &'b TwoFloat) -> TwoFloat {
-        let quotient = (self / rhs).trunc();
-        self - quotient * rhs
+        self.fmod(*rhs)
     }
 }
 
@@ -284,7 +628,7 @@ assign_ops! {
     /// (2017) Algorithm 18.
     fn DivAssign::div_assign<'a>(self: &mut TwoFloat, rhs: &'a TwoFloat) {
         let th = rhs.hi.recip();
-        let rh = 1.0 - rhs.hi * th;
+        let rh = mathfn::fma(-rhs.hi, th, 1.0);
         let rl = -(rhs.lo * th);
         let (eh, el) = fast_two_sum(rh, rl).into();
         let e = TwoFloat { hi: eh, lo: el };
@@ -294,19 +638,385 @@ assign_ops! {
     }
 
     fn RemAssign::rem_assign<'b>(self: &mut TwoFloat, rhs: &'b f64) {
-        let quotient = (*self / rhs).trunc();
-        *self -= quotient * rhs;
+        *self = self.fmod(TwoFloat::from(*rhs));
     }
 
     fn RemAssign::rem_assign<'a>(self: &mut TwoFloat, rhs: &'a TwoFloat) {
-        let quotient = (*self / rhs).trunc();
-        *self -= quotient * rhs;
+        *self = self.fmod(*rhs);
     }
 }
 
 impl TwoFloat {
+    /// Computes `(self * a) + b` without rounding the product. The product
+    /// is evaluated exactly as a sum of eight `f64` values before `b` is
+    /// added, and the exact sum is then rounded to a `TwoFloat`. The final
+    /// rounding folds the smallest components together, so the result is
+    /// nearly, but not always, correctly rounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let e = 2f64.powi(-60);
+    /// let a = TwoFloat::new_add(1.0, e);
+    /// let b = TwoFloat::new_add(1.0, -e);
+    /// let c = a.mul_add(b, TwoFloat::from(-1.0));
+    ///
+    /// assert_eq!(c, TwoFloat::from(-e * e));
+    /// ```
+    pub fn mul_add(self, a: Self, b: Self) -> Self {
+        if !self.is_valid() || !a.is_valid() || !b.is_valid() {
+            return self * a + b;
+        }
+
+        let (p0, e0) = Self::new_mul(self.hi, a.hi).into();
+        let (p1, e1) = Self::new_mul(self.hi, a.lo).into();
+        let (p2, e2) = Self::new_mul(self.lo, a.hi).into();
+        let (p3, e3) = Self::new_mul(self.lo, a.lo).into();
+        if !p0.is_finite() {
+            return self * a + b;
+        }
+
+        let mut expansion = [0.0; 10];
+        let mut len = 0;
+        for &x in &[e3, p3, e2, e1, p2, p1, b.lo, e0, b.hi, p0] {
+            len = grow_expansion(&mut expansion, len, x);
+        }
+
+        round_expansion(&expansion[..len])
+    }
+
+    /// Computes `(self * a) + b` without rounding the product, where the
+    /// multiplier `a` is an `f64` value. As for `mul_add`, the result is
+    /// nearly correctly rounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(1.0, 3.0);
+    /// let b = a.mul_add_f64(3.0, TwoFloat::from(-1.0));
+    ///
+    /// assert!(b.abs() < 1e-32);
+    /// ```
+    pub fn mul_add_f64(self, a: f64, b: Self) -> Self {
+        if !self.is_valid() || !a.is_finite() || !b.is_valid() {
+            return self * a + b;
+        }
+
+        let (p0, e0) = Self::new_mul(self.hi, a).into();
+        let (p1, e1) = Self::new_mul(self.lo, a).into();
+        if !p0.is_finite() {
+            return self * a + b;
+        }
+
+        let mut expansion = [0.0; 6];
+        let mut len = 0;
+        for &x in &[e1, p1, b.lo, e0, b.hi, p0] {
+            len = grow_expansion(&mut expansion, len, x);
+        }
+
+        round_expansion(&expansion[..len])
+    }
+
+    fn add_directed(self, rhs: Self, up: bool) -> Self {
+        if !self.is_valid() || !rhs.is_valid() {
+            return self + rhs;
+        }
+
+        let mut expansion = [0.0; 4];
+        let mut len = 0;
+        for &x in &[self.lo, rhs.lo, self.hi, rhs.hi] {
+            len = grow_expansion(&mut expansion, len, x);
+        }
+        if expansion[..len].iter().any(|x| !x.is_finite()) {
+            return directed_overflow(self.hi + rhs.hi > 0.0, up);
+        }
+
+        round_expansion_directed(&expansion[..len], 0.0, up)
+    }
+
+    /// Adds `rhs` to `self`, rounding the result upwards so that it is
+    /// guaranteed to be greater than or equal to the exact sum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(1.0, 3.0);
+    /// let b = TwoFloat::from(1e-200);
+    ///
+    /// assert!(a.add_up(b) > a);
+    /// assert_eq!(a.add_down(b), a);
+    /// ```
+    pub fn add_up(self, rhs: Self) -> Self {
+        self.add_directed(rhs, true)
+    }
+
+    /// Adds `rhs` to `self`, rounding the result downwards so that it is
+    /// guaranteed to be less than or equal to the exact sum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(1.0, 3.0);
+    /// let b = TwoFloat::from(-1e-200);
+    ///
+    /// assert!(a.add_down(b) < a);
+    /// assert_eq!(a.add_up(b), a);
+    /// ```
+    pub fn add_down(self, rhs: Self) -> Self {
+        self.add_directed(rhs, false)
+    }
+
+    /// Subtracts `rhs` from `self`, rounding the result upwards so that it
+    /// is guaranteed to be greater than or equal to the exact difference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(1.0, 3.0);
+    /// let b = TwoFloat::from(1e-200);
+    ///
+    /// assert_eq!(a.sub_up(b), a);
+    /// assert!(a.sub_down(b) < a);
+    /// ```
+    pub fn sub_up(self, rhs: Self) -> Self {
+        self.add_directed(-rhs, true)
+    }
+
+    /// Subtracts `rhs` from `self`, rounding the result downwards so that it
+    /// is guaranteed to be less than or equal to the exact difference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(3.0);
+    /// let b = TwoFloat::from(2.0);
+    ///
+    /// assert_eq!(a.sub_down(b), TwoFloat::from(1.0));
+    /// ```
+    pub fn sub_down(self, rhs: Self) -> Self {
+        self.add_directed(-rhs, false)
+    }
+
+    fn mul_directed(self, rhs: Self, up: bool) -> Self {
+        if !self.is_valid() || !rhs.is_valid() {
+            return self * rhs;
+        }
+
+        let mut expansion = [0.0; 8];
+        let mut len = 0;
+        let mut slack = 0.0;
+        for &(a, b) in &[
+            (self.lo, rhs.lo),
+            (self.lo, rhs.hi),
+            (self.hi, rhs.lo),
+            (self.hi, rhs.hi),
+        ] {
+            len = grow_product(&mut expansion, len, a, b, &mut slack);
+        }
+        if expansion[..len].iter().any(|x| !x.is_finite()) {
+            return directed_overflow((self.hi > 0.0) == (rhs.hi > 0.0), up);
+        }
+
+        round_expansion_directed(&expansion[..len], slack, up)
+    }
+
+    /// Multiplies `self` by `rhs`, rounding the result upwards so that it is
+    /// guaranteed to be greater than or equal to the exact product.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(1.0, 3.0);
+    /// let b = TwoFloat::new_div(1.0, 7.0);
+    ///
+    /// assert!(a.mul_down(b) < a.mul_up(b));
+    /// assert!(a.mul_up(b) - a.mul_down(b) < 1e-31);
+    /// ```
+    pub fn mul_up(self, rhs: Self) -> Self {
+        self.mul_directed(rhs, true)
+    }
+
+    /// Multiplies `self` by `rhs`, rounding the result downwards so that it
+    /// is guaranteed to be less than or equal to the exact product.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(1.5);
+    /// let b = TwoFloat::from(-4.0);
+    ///
+    /// assert_eq!(a.mul_down(b), TwoFloat::from(-6.0));
+    /// ```
+    pub fn mul_down(self, rhs: Self) -> Self {
+        self.mul_directed(rhs, false)
+    }
+
+    fn div_directed(self, rhs: Self, up: bool) -> Self {
+        let mut nearest = self / rhs;
+        if !self.is_valid() || !rhs.is_valid() || self.hi == 0.0 || rhs.hi == 0.0 {
+            return nearest;
+        } else if !nearest.is_valid() || !(self.hi / rhs.hi).is_finite() {
+            // Start from the largest finite value, which is stepped to
+            // infinity if the quotient overflows
+            nearest = if (self.hi > 0.0) == (rhs.hi > 0.0) {
+                Self::MAX
+            } else {
+                Self::MIN
+            };
+        }
+
+        // Scale small operands upwards so that the residual is exact
+        let self_exponent = exponent(self.hi);
+        let rhs_exponent = exponent(rhs.hi);
+        let scale = (-self_exponent.min(rhs_exponent))
+            .min(1000 - self_exponent.max(rhs_exponent))
+            .max(0);
+        let a = self.ldexp(scale);
+        let b = rhs.ldexp(scale);
+
+        let b_sign = mathfn::signum(b.hi);
+        let direction = if up { -1.0 } else { 1.0 };
+        refine_directed(nearest, up, |q| {
+            // Sign of (a - q * b) * sign(b), which has the sign of a / b - q
+            let mut residual = [0.0; 12];
+            let mut len = grow_expansion(&mut residual, 0, a.lo);
+            len = grow_expansion(&mut residual, len, a.hi);
+            let mut slack = 0.0;
+            for &(x, y) in &[(q.lo, b.lo), (q.lo, b.hi), (q.hi, b.lo), (q.hi, b.hi)] {
+                len = grow_product(&mut residual, len, -x, y, &mut slack);
+            }
+            len = grow_expansion(&mut residual, len, -direction * b_sign * slack);
+            expansion_sign(&residual[..len]) * b_sign == -direction
+        })
+    }
+
+    /// Divides `self` by `rhs`, rounding the result upwards so that it is
+    /// guaranteed to be greater than or equal to the exact quotient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(1.0);
+    /// let b = TwoFloat::from(3.0);
+    ///
+    /// assert!(a.div_up(b) * 3.0 >= 1.0);
+    /// assert!(a.div_down(b) < a.div_up(b));
+    /// ```
+    pub fn div_up(self, rhs: Self) -> Self {
+        self.div_directed(rhs, true)
+    }
+
+    /// Divides `self` by `rhs`, rounding the result downwards so that it is
+    /// guaranteed to be less than or equal to the exact quotient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(1.0);
+    /// let b = TwoFloat::from(4.0);
+    ///
+    /// assert_eq!(a.div_down(b), TwoFloat::from(0.25));
+    /// ```
+    pub fn div_down(self, rhs: Self) -> Self {
+        self.div_directed(rhs, false)
+    }
+
+    // Handles non-finite operands and zero divisors before computing the
+    // exact remainder.
+    fn remainder_with_mode(self, rhs: Self, mode: RemainderMode) -> (Self, Quotient) {
+        if !self.hi.is_finite() || rhs.hi.is_nan() || rhs.hi == 0.0 {
+            (Self::NAN, Quotient::new())
+        } else if rhs.hi.is_infinite() {
+            if mode == RemainderMode::Euclid && self.hi < 0.0 {
+                (Self::INFINITY, Quotient::new())
+            } else {
+                (self, Quotient::new())
+            }
+        } else {
+            remainder_impl(self, rhs, mode)
+        }
+    }
+
+    /// Calculates the remainder of `self` divided by `rhs` with the quotient
+    /// rounded towards zero, matching the `%` operator. The result has the
+    /// same sign as `self` and a magnitude less than `rhs.abs()`.
+    ///
+    /// The remainder is computed exactly and rounded once, so the result is
+    /// exact whenever it is representable as a `TwoFloat`, which is always
+    /// the case when the quotient fits in 53 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(2f64.powi(1000));
+    /// let b = TwoFloat::from(3.0);
+    ///
+    /// assert_eq!(a.fmod(b), TwoFloat::from(1.0));
+    /// assert_eq!((-a).fmod(b), TwoFloat::from(-1.0));
+    /// ```
+    pub fn fmod(self, rhs: Self) -> Self {
+        self.remainder_with_mode(rhs, RemainderMode::Truncate).0
+    }
+
+    /// Calculates the IEEE 754 remainder of `self` divided by `rhs`, where
+    /// the quotient is rounded to the nearest integer with ties to even. The
+    /// result has a magnitude of at most half of `rhs.abs()`.
+    ///
+    /// The remainder is computed exactly and rounded once, as for `fmod`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(5.0);
+    /// let b = TwoFloat::from(2.0);
+    ///
+    /// assert_eq!(a.remainder(b), TwoFloat::from(1.0));
+    /// assert_eq!(TwoFloat::from(7.0).remainder(b), TwoFloat::from(-1.0));
+    /// ```
+    pub fn remainder(self, rhs: Self) -> Self {
+        self.remainder_with_mode(rhs, RemainderMode::Nearest).0
+    }
+
+    /// Calculates the IEEE 754 remainder of `self` divided by `rhs` together
+    /// with the low 31 bits of the rounded quotient. The returned quotient
+    /// bits carry the sign of `self / rhs`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(29.0);
+    /// let b = TwoFloat::from(-3.0);
+    ///
+    /// assert_eq!(a.remquo(b), (TwoFloat::from(-1.0), -10));
+    /// ```
+    pub fn remquo(self, rhs: Self) -> (Self, i32) {
+        let (remainder, quotient) = self.remainder_with_mode(rhs, RemainderMode::Nearest);
+        if (self.hi < 0.0) != (rhs.hi < 0.0) {
+            let bits = quotient.low_bits.wrapping_neg() & 0x7fff_ffff;
+            (remainder, -(bits as i32))
+        } else {
+            (remainder, (quotient.low_bits & 0x7fff_ffff) as i32)
+        }
+    }
+
     /// Calculates Euclidean division, the matching method for `rem_euclid`.
     ///
+    /// The quotient is computed exactly and then rounded, so the result is
+    /// exact whenever the quotient is representable as a `TwoFloat`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -320,23 +1030,25 @@ impl TwoFloat {
     /// assert_eq!((-a).div_euclid(-b), TwoFloat::from(2.0));
     /// ```
     pub fn div_euclid(self, rhs: Self) -> Self {
-        let quotient = (self / rhs).trunc();
-        if (self - quotient * rhs) < 0.0 {
-            if rhs > 0.0 {
-                quotient - 1.0
+        if !self.hi.is_finite() || rhs.hi.is_nan() || rhs.hi == 0.0 {
+            Self::NAN
+        } else if rhs.hi.is_infinite() {
+            if self.hi < 0.0 {
+                Self::from(-mathfn::signum(rhs.hi))
             } else {
-                quotient + 1.0
+                Self::from(0.0)
             }
         } else {
-            quotient
+            remainder_impl(self, rhs, RemainderMode::Euclid).1.value()
         }
     }
 
     /// Calculates the least nonnegative remainder of `self (mod rhs)`.
     ///
-    /// The return value `r` usually satisfies `0.0 <= r < rhs.abs()`,
-    /// although the errors in numerical computation may result in violations
-    /// of this constraint.
+    /// The remainder is computed exactly and rounded once, as for `fmod`, so
+    /// the return value `r` satisfies `0.0 <= r < rhs.abs()` except when
+    /// `self` is negative and much smaller than `rhs.abs()` in magnitude, in
+    /// which case the final rounding may give `r == rhs.abs()`.
     ///
     /// # Examples
     ///
@@ -351,12 +1063,7 @@ impl TwoFloat {
     /// assert_eq!((-a).rem_euclid(-b), TwoFloat::from(1.0));
     /// ```
     pub fn rem_euclid(self, rhs: Self) -> Self {
-        let remainder = self % rhs;
-        if remainder < 0.0 {
-            remainder + rhs.abs()
-        } else {
-            remainder
-        }
+        self.remainder_with_mode(rhs, RemainderMode::Euclid).0
     }
 }
 
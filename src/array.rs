@@ -0,0 +1,102 @@
+//! Support for `TwoFloat` values in `ndarray` arrays.
+//!
+//! `TwoFloat` implements `ScalarOperand`, so arrays can be combined with
+//! scalar values using the arithmetic operators. The elementwise functions
+//! in this module apply the corresponding `TwoFloat` function to every
+//! element of an array, and the `sum` and `dot` functions accumulate `f64`
+//! arrays using double-double arithmetic.
+//!
+//! # Examples
+//!
+//! ```
+//! # use ndarray::array;
+//! # use twofloat::{array, TwoFloat};
+//! let a = array![1e16, 1.0, -1e16];
+//! assert_eq!(a.sum(), 0.0);
+//! assert_eq!(array::sum(&a), 1.0);
+//!
+//! let b = array![TwoFloat::from(1.0), TwoFloat::from(2.0)] * TwoFloat::from(0.5);
+//! assert_eq!(b[1], 1.0);
+//! ```
+
+use ndarray::{ArrayBase, Data, Dimension, Ix1, ScalarOperand};
+
+use crate::TwoFloat;
+
+impl ScalarOperand for TwoFloat {}
+
+/// Computes the sum of the elements of an `f64` array, accumulating the
+/// result as a `TwoFloat`.
+///
+/// # Examples
+///
+/// ```
+/// # use ndarray::Array;
+/// # use twofloat::{array, TwoFloat};
+/// let a = Array::from_elem((10, 10), 0.1);
+/// let sum = array::sum(&a);
+///
+/// assert!((sum - 10.0).abs() < 1e-15);
+/// assert_ne!(a.sum(), sum);
+/// ```
+pub fn sum<S, D>(a: &ArrayBase<S, D>) -> TwoFloat
+where
+    S: Data<Elem = f64>,
+    D: Dimension,
+{
+    a.fold(TwoFloat::from(0.0), |acc, &x| acc + x)
+}
+
+/// Computes the dot product of two `f64` vectors, calculating each product
+/// exactly and accumulating the result as a `TwoFloat`.
+///
+/// # Panics
+///
+/// Panics if the vectors have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// # use ndarray::array;
+/// # use twofloat::{array, TwoFloat};
+/// let a = array![1e16, 1.0, 1e16];
+/// let b = array![1.0, 1.0, -1.0];
+///
+/// assert_eq!(a.dot(&b), 0.0);
+/// assert_eq!(array::dot(&a, &b), 1.0);
+/// ```
+pub fn dot<S1, S2>(a: &ArrayBase<S1, Ix1>, b: &ArrayBase<S2, Ix1>) -> TwoFloat
+where
+    S1: Data<Elem = f64>,
+    S2: Data<Elem = f64>,
+{
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "dot product of vectors of different lengths"
+    );
+    a.iter()
+        .zip(b.iter())
+        .fold(TwoFloat::from(0.0), |acc, (&x, &y)| {
+            acc + TwoFloat::new_mul(x, y)
+        })
+}
+
+#[cfg(feature = "math_funcs")]
+macro_rules! elementwise {
+    ($($name:ident),*) => {
+        $(
+            #[doc = concat!("Applies [`TwoFloat::", stringify!($name), "`] to each element of an array.")]
+            pub fn $name<S, D>(a: &ArrayBase<S, D>) -> ndarray::Array<TwoFloat, D>
+            where
+                S: Data<Elem = TwoFloat>,
+                D: Dimension,
+            {
+                a.mapv(TwoFloat::$name)
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "math_funcs")]
+elementwise!(exp, exp_m1, ln, ln_1p, sqrt, cbrt, sin, cos, tan, asin, acos, atan);
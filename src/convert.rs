@@ -1,6 +1,11 @@
-use core::convert::{From, TryFrom};
+use core::{
+    cmp::Ordering,
+    convert::{From, TryFrom},
+};
 
-use crate::{base::no_overlap, TwoFloat, TwoFloatError};
+use num_traits::float::FloatCore;
+
+use crate::{base::no_overlap, RoundingMode, TwoFloat, TwoFloatError};
 
 macro_rules! from_conversion {
     (|$source_i:ident : TwoFloat| -> $dest:tt $code:block) => {
@@ -156,3 +161,585 @@ bigint_convert!(i128);
 bigint_convert!(i64);
 bigint_convert!(u128);
 bigint_convert!(u64);
+
+// Number of significant bits kept when rounding integers
+const SIGNIFICANT_BITS: i64 = 106;
+
+// Exponent of the smallest positive subnormal `f64` value
+const MIN_EXPONENT: i64 = -1074;
+
+// `MAX` is this 107-bit integer multiplied by 2^917
+const MAX_MANTISSA: u128 = (1 << 107) - (1 << 53) - 1;
+
+// Number of bits in the integer part of `MAX`
+const MAX_BITS: i64 = 1024;
+
+// Number of quotient bits computed when rounding a ratio, which leaves
+// spare bits below the rounding position
+const QUOTIENT_BITS: i64 = 110;
+
+// Checks if a magnitude truncated to an integer mantissa should be rounded
+// away from zero, given the bit after the mantissa and whether any further
+// bits are set.
+pub(crate) fn round_away(
+    mode: RoundingMode,
+    negative: bool,
+    odd: bool,
+    half: bool,
+    sticky: bool,
+) -> bool {
+    match mode {
+        RoundingMode::NearestEven => half && (sticky || odd),
+        RoundingMode::NearestAway => half,
+        RoundingMode::TowardZero => false,
+        RoundingMode::TowardPositive => !negative && (half || sticky),
+        RoundingMode::TowardNegative => negative && (half || sticky),
+    }
+}
+
+// Returns the number of significant bits in a little-endian limb slice.
+pub(crate) fn bit_length(limbs: &[u64]) -> usize {
+    limbs
+        .iter()
+        .rposition(|&x| x != 0)
+        .map_or(0, |i| 64 * (i + 1) - limbs[i].leading_zeros() as usize)
+}
+
+// Reads 128 bits starting at bit `start` of a little-endian limb slice.
+pub(crate) fn read_bits(limbs: &[u64], start: usize) -> u128 {
+    let limb = |i: usize| limbs.get(i).map_or(0, |&x| x as u128);
+    let index = start / 64;
+    let offset = start % 64;
+    let bits = (limb(index) | (limb(index + 1) << 64)) >> offset;
+    if offset == 0 {
+        bits
+    } else {
+        bits | (limb(index + 2) << (128 - offset))
+    }
+}
+
+// Returns limb `index` of the limbs multiplied by 2^shift and truncated to
+// an integer.
+fn shifted_limb(limbs: &[u64], shift: i64, index: usize) -> u64 {
+    let start = 64 * index as i64 - shift;
+    if start >= 0 {
+        read_bits(limbs, start as usize) as u64
+    } else if start > -64 {
+        limbs.first().map_or(0, |&x| x << -start)
+    } else {
+        0
+    }
+}
+
+// Checks if any of the bits below `end` are set in a little-endian limb
+// slice.
+fn any_bits_below(limbs: &[u64], end: usize) -> bool {
+    let index = end / 64;
+    let mask = (1 << (end % 64)) - 1;
+    limbs[..index.min(limbs.len())].iter().any(|&x| x != 0)
+        || limbs.get(index).map_or(0, |&x| x & mask) != 0
+}
+
+// Compares the numerator multiplied by 2^shift and truncated to an integer
+// with the product of the denominator and `x`, streaming both through a
+// single subtraction so that no storage is needed.
+pub(crate) fn compare_quotient(
+    numerator: &[u64],
+    shift: i64,
+    denominator: &[u64],
+    x: u128,
+) -> Ordering {
+    let (x0, x1) = (x as u64 as u128, x >> 64);
+    let numerator_limbs = (bit_length(numerator) as i64 + shift).max(0) as usize / 64 + 1;
+    let len = numerator_limbs.max(denominator.len() + 3);
+
+    let (mut carry0, mut carry1, mut carry_sum) = (0, 0, 0);
+    let mut borrow = false;
+    let mut nonzero = false;
+    for i in 0..len {
+        let d0 = denominator.get(i).map_or(0, |&x| x as u128);
+        let d1 = if i > 0 {
+            denominator.get(i - 1).map_or(0, |&x| x as u128)
+        } else {
+            0
+        };
+
+        let p0 = d0 * x0 + carry0;
+        carry0 = p0 >> 64;
+        let p1 = d1 * x1 + carry1;
+        carry1 = p1 >> 64;
+        let sum = (p0 as u64 as u128) + (p1 as u64 as u128) + carry_sum;
+        carry_sum = sum >> 64;
+
+        let (difference, b1) = shifted_limb(numerator, shift, i).overflowing_sub(sum as u64);
+        let (difference, b2) = difference.overflowing_sub(borrow as u64);
+        borrow = b1 || b2;
+        nonzero |= difference != 0;
+    }
+
+    if borrow {
+        Ordering::Less
+    } else if nonzero {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+// Replaces the number stored in the little-endian limbs with
+// `limbs * factor + addend`, returning `false` if the result does not fit.
+pub(crate) fn mul_add_limbs(limbs: &mut [u64], factor: u64, addend: u64) -> bool {
+    let mut carry = addend as u128;
+    for limb in limbs.iter_mut() {
+        let product = *limb as u128 * factor as u128 + carry;
+        *limb = product as u64;
+        carry = product >> 64;
+    }
+    carry == 0
+}
+
+// Multiplies the number stored in the little-endian limbs by 10^exp,
+// returning `false` if the result does not fit.
+pub(crate) fn mul_pow10_limbs(limbs: &mut [u64], mut exp: u32) -> bool {
+    // Largest power of ten that fits in a u64
+    const CHUNK: u32 = 19;
+    while exp > 0 {
+        let step = exp.min(CHUNK);
+        if !mul_add_limbs(limbs, 10u64.pow(step), 0) {
+            return false;
+        }
+        exp -= step;
+    }
+    true
+}
+
+// Returns the odd integer mantissa and exponent of a nonzero finite value.
+fn decode_reduced(value: f64) -> (u64, i32) {
+    let (mantissa, exponent, _) = FloatCore::integer_decode(value);
+    let zeros = mantissa.trailing_zeros();
+    (mantissa >> zeros, exponent as i32 + zeros as i32)
+}
+
+// Adds or subtracts `mantissa * 2^shift` to the number stored in the
+// little-endian limbs, with `extra` holding the limb above them. Returns
+// `false` if the result does not fit.
+fn accumulate_limbs(
+    limbs: &mut [u64],
+    extra: &mut u64,
+    mantissa: u64,
+    shift: usize,
+    subtract: bool,
+) -> bool {
+    let len = limbs.len();
+    let mut index = shift / 64;
+    let mut remaining = (mantissa as u128) << (shift % 64);
+    let mut carry = false;
+    while remaining != 0 || carry {
+        let limb = match limbs.get_mut(index) {
+            Some(limb) => limb,
+            None if index == len => &mut *extra,
+            None => return false,
+        };
+        let part = remaining as u64;
+        let (result, c1) = if subtract {
+            limb.overflowing_sub(part)
+        } else {
+            limb.overflowing_add(part)
+        };
+        let (result, c2) = if subtract {
+            result.overflowing_sub(carry as u64)
+        } else {
+            result.overflowing_add(carry as u64)
+        };
+        *limb = result;
+        carry = c1 || c2;
+        remaining >>= 64;
+        index += 1;
+    }
+    true
+}
+
+// Converts a magnitude to an `i128` with the given sign.
+fn i128_from_magnitude(magnitude: u128, negative: bool) -> Result<i128, TwoFloatError> {
+    if !negative {
+        i128::try_from(magnitude).map_err(|_| TwoFloatError::ConversionError)
+    } else if magnitude <= i128::MIN.unsigned_abs() {
+        Ok((magnitude as i128).wrapping_neg())
+    } else {
+        Err(TwoFloatError::ConversionError)
+    }
+}
+
+impl TwoFloat {
+    // Rounds the value `limbs * 2^exponent` to 106 significant bits, or to
+    // a multiple of the smallest subnormal value, using the specified
+    // rounding mode. If `sticky` is set, the value is treated as being
+    // slightly greater than the limbs.
+    fn round_limbs(
+        limbs: &[u64],
+        exponent: i64,
+        sticky: bool,
+        negative: bool,
+        mode: RoundingMode,
+    ) -> Self {
+        let bits = bit_length(limbs) as i64;
+        if bits == 0 && !sticky {
+            return Self::from(0.0);
+        }
+
+        let shift = (bits - SIGNIFICANT_BITS)
+            .max(MIN_EXPONENT - exponent)
+            .max(0);
+        let mut mantissa = read_bits(limbs, shift as usize) & ((1 << SIGNIFICANT_BITS) - 1);
+        if shift > 0 {
+            let half = read_bits(limbs, shift as usize - 1) & 1 != 0;
+            let sticky = sticky || any_bits_below(limbs, shift as usize - 1);
+            if round_away(mode, negative, mantissa & 1 != 0, half, sticky) {
+                mantissa += 1;
+            }
+        } else if sticky && round_away(mode, negative, mantissa & 1 != 0, false, true) {
+            mantissa += 1;
+        }
+
+        let scale = (exponent + shift).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        let mut magnitude = Self::from(mantissa).ldexp(scale);
+        if !magnitude.is_valid() {
+            // The rounded mantissa overflowed, which can also happen for
+            // values close to `MAX` since it has 107 significant bits
+            let top = bits + exponent;
+            let exceeds_max = top > MAX_BITS
+                || (top == MAX_BITS && {
+                    let leading = if bits >= 107 {
+                        read_bits(limbs, bits as usize - 107) & ((1 << 107) - 1)
+                    } else {
+                        read_bits(limbs, 0) << (107 - bits)
+                    };
+                    let rest = sticky || (bits > 107 && any_bits_below(limbs, bits as usize - 107));
+                    leading > MAX_MANTISSA || (leading == MAX_MANTISSA && rest)
+                });
+            magnitude = if exceeds_max && round_away(mode, negative, true, true, true) {
+                Self::INFINITY
+            } else {
+                Self::MAX
+            };
+        }
+
+        if negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    // Writes the magnitude of `self * 2^-exponent` to the limbs, which must
+    // be an integer.
+    fn write_limbs(self, limbs: &mut [u64], exponent: i32) -> Result<(), TwoFloatError> {
+        // The high word may not fit when the low word has the opposite sign
+        limbs.iter_mut().for_each(|limb| *limb = 0);
+        let mut extra = 0;
+        for &(value, subtract) in &[
+            (self.hi, false),
+            (self.lo, (self.hi < 0.0) != (self.lo < 0.0)),
+        ] {
+            if value == 0.0 {
+                continue;
+            }
+            let (mantissa, value_exponent) = decode_reduced(value);
+            if value_exponent < exponent
+                || !accumulate_limbs(
+                    limbs,
+                    &mut extra,
+                    mantissa,
+                    (value_exponent - exponent) as usize,
+                    subtract,
+                )
+            {
+                return Err(TwoFloatError::ConversionError);
+            }
+        }
+
+        if extra == 0 {
+            Ok(())
+        } else {
+            Err(TwoFloatError::ConversionError)
+        }
+    }
+
+    /// Converts a big integer, given as its magnitude in little-endian
+    /// 64-bit limbs and its sign, to the nearest `TwoFloat` with 106
+    /// significant bits using the specified rounding mode. Values too large
+    /// to represent are rounded to an infinity or to `MAX` or `MIN`
+    /// according to the rounding mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{RoundingMode, TwoFloat};
+    /// let limbs = [1, 0, 1];
+    /// let a = TwoFloat::from_limbs(&limbs, false, RoundingMode::NearestEven);
+    /// let b = TwoFloat::from_limbs(&limbs, true, RoundingMode::TowardNegative);
+    ///
+    /// assert_eq!(a, TwoFloat::from(2f64.powi(128)));
+    /// assert_eq!(b, -TwoFloat::new_add(2f64.powi(128), 2f64.powi(23)));
+    /// ```
+    pub fn from_limbs(limbs: &[u64], negative: bool, mode: RoundingMode) -> Self {
+        Self::round_limbs(limbs, 0, false, negative, mode)
+    }
+
+    /// Converts a `TwoFloat` holding an integer to its magnitude in
+    /// little-endian 64-bit limbs, setting any unused high limbs to zero.
+    /// The sign can be obtained from `is_sign_negative`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not an integer or if its
+    /// magnitude does not fit in the provided limbs. The contents of the
+    /// limbs are unspecified in this case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(2f64.powi(128), -1.0);
+    /// let mut limbs = [0; 3];
+    ///
+    /// assert!(a.to_limbs(&mut limbs).is_ok());
+    /// assert_eq!(limbs, [u64::MAX, u64::MAX, 0]);
+    /// assert!(a.to_limbs(&mut limbs[..1]).is_err());
+    /// assert!(TwoFloat::from(0.5).to_limbs(&mut limbs).is_err());
+    /// ```
+    pub fn to_limbs(self, limbs: &mut [u64]) -> Result<(), TwoFloatError> {
+        if !self.is_valid() {
+            return Err(TwoFloatError::ConversionError);
+        }
+        self.write_limbs(limbs, 0)
+    }
+
+    /// Converts the ratio of two big integers, given as their magnitudes in
+    /// little-endian 64-bit limbs, to a `TwoFloat` using the specified
+    /// rounding mode. The result is correctly rounded to 106 significant
+    /// bits, or to a multiple of the smallest subnormal `f64` value for
+    /// tiny results. Values too large to represent are handled as in
+    /// `from_limbs`. Division of a nonzero value by zero gives an infinity,
+    /// and division of zero by zero gives `NAN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{RoundingMode, TwoFloat};
+    /// let a = TwoFloat::from_ratio_limbs(&[1], &[3], false, RoundingMode::TowardNegative);
+    /// let b = TwoFloat::from_ratio_limbs(&[1], &[3], false, RoundingMode::TowardPositive);
+    /// let c = TwoFloat::from_ratio_limbs(&[1], &[3], false, RoundingMode::NearestEven);
+    ///
+    /// assert_eq!(b, a.next_up());
+    /// assert!(c == a || c == b);
+    /// assert!((c - TwoFloat::new_div(1.0, 3.0)).abs() < 1e-32);
+    /// ```
+    pub fn from_ratio_limbs(
+        numerator: &[u64],
+        denominator: &[u64],
+        negative: bool,
+        mode: RoundingMode,
+    ) -> Self {
+        let numerator_bits = bit_length(numerator) as i64;
+        let denominator_bits = bit_length(denominator) as i64;
+        if denominator_bits == 0 {
+            return if numerator_bits == 0 {
+                Self::NAN
+            } else if negative {
+                Self::NEG_INFINITY
+            } else {
+                Self::INFINITY
+            };
+        } else if numerator_bits == 0 {
+            return Self::from(0.0);
+        }
+
+        // Chosen so that the quotient has QUOTIENT_BITS - 1 or QUOTIENT_BITS
+        // bits
+        let shift = QUOTIENT_BITS - 1 - numerator_bits + denominator_bits;
+        let mut quotient = 0u128;
+        for bit in (0..QUOTIENT_BITS).rev() {
+            let candidate = quotient | (1 << bit);
+            if compare_quotient(numerator, shift, denominator, candidate) != Ordering::Less {
+                quotient = candidate;
+            }
+        }
+
+        let inexact = compare_quotient(numerator, shift, denominator, quotient) != Ordering::Equal
+            || (shift < 0 && any_bits_below(numerator, -shift as usize));
+        Self::round_limbs(
+            &[quotient as u64, (quotient >> 64) as u64],
+            -shift,
+            inexact,
+            negative,
+            mode,
+        )
+    }
+
+    /// Returns the correctly rounded value of the ratio of two integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from_ratio(-22, 7);
+    ///
+    /// assert_eq!(a, -TwoFloat::from_ratio(22, 7));
+    /// assert!((a - TwoFloat::new_div(-22.0, 7.0)).abs() < 1e-30);
+    /// assert_eq!(TwoFloat::from_ratio(1 << 120, 1 << 20), TwoFloat::from(2f64.powi(100)));
+    /// ```
+    pub fn from_ratio(numerator: i128, denominator: i128) -> Self {
+        let p = numerator.unsigned_abs();
+        let q = denominator.unsigned_abs();
+        Self::from_ratio_limbs(
+            &[p as u64, (p >> 64) as u64],
+            &[q as u64, (q >> 64) as u64],
+            (numerator < 0) != (denominator < 0),
+            RoundingMode::NearestEven,
+        )
+    }
+
+    /// Returns the exact value as a ratio, writing the magnitude of the
+    /// numerator to the little-endian 64-bit limbs and returning the
+    /// base 2 exponent of the denominator, such that
+    /// `self == ±numerator * 2^exponent`. The numerator is odd unless the
+    /// value is zero, in which case the exponent is zero. The sign can be
+    /// obtained from `is_sign_negative`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not finite or if the numerator
+    /// does not fit in the provided limbs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(2f64.powi(100), 0.75);
+    /// let mut limbs = [0; 2];
+    ///
+    /// assert_eq!(a.to_ratio_limbs(&mut limbs).unwrap(), -2);
+    /// assert_eq!(limbs, [3, 1 << 38]);
+    /// ```
+    pub fn to_ratio_limbs(self, limbs: &mut [u64]) -> Result<i32, TwoFloatError> {
+        if !self.is_valid() {
+            return Err(TwoFloatError::ConversionError);
+        }
+
+        let exponent = [self.hi, self.lo]
+            .iter()
+            .filter(|&&x| x != 0.0)
+            .map(|&x| decode_reduced(x).1)
+            .min()
+            .unwrap_or(0);
+        self.write_limbs(limbs, exponent)?;
+        Ok(exponent)
+    }
+
+    /// Returns the exact value as a ratio `(numerator, exponent)` such that
+    /// `self == numerator * 2^exponent`. The numerator is odd unless the
+    /// value is zero, in which case the exponent is zero.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not finite or if the numerator
+    /// does not fit in an `i128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(-0.75, 2f64.powi(-70));
+    ///
+    /// assert_eq!(a.to_ratio().unwrap(), (-(3 << 68) + 1, -70));
+    /// assert!(TwoFloat::new_add(1.0, 2f64.powi(-200)).to_ratio().is_err());
+    /// ```
+    pub fn to_ratio(self) -> Result<(i128, i32), TwoFloatError> {
+        let mut limbs = [0; 2];
+        let exponent = self.to_ratio_limbs(&mut limbs)?;
+        let magnitude = limbs[0] as u128 | ((limbs[1] as u128) << 64);
+        Ok((i128_from_magnitude(magnitude, self.hi < 0.0)?, exponent))
+    }
+
+    /// Converts an `i128` to a `TwoFloat` with 106 significant bits using
+    /// the specified rounding mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{RoundingMode, TwoFloat};
+    /// let value = (1i128 << 110) + 1;
+    /// let a = TwoFloat::from_i128_rounded(value, RoundingMode::NearestEven);
+    /// let b = TwoFloat::from_i128_rounded(value, RoundingMode::TowardPositive);
+    ///
+    /// assert_eq!(a, TwoFloat::from(2f64.powi(110)));
+    /// assert_eq!(b, TwoFloat::new_add(2f64.powi(110), 32.0));
+    /// ```
+    pub fn from_i128_rounded(value: i128, mode: RoundingMode) -> Self {
+        let magnitude = value.unsigned_abs();
+        Self::from_limbs(
+            &[magnitude as u64, (magnitude >> 64) as u64],
+            value < 0,
+            mode,
+        )
+    }
+
+    /// Converts a `u128` to a `TwoFloat` with 106 significant bits using
+    /// the specified rounding mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{RoundingMode, TwoFloat};
+    /// let a = TwoFloat::from_u128_rounded(u128::MAX, RoundingMode::TowardZero);
+    ///
+    /// assert_eq!(a, TwoFloat::new_sub(2f64.powi(128), 2f64.powi(22)));
+    /// ```
+    pub fn from_u128_rounded(value: u128, mode: RoundingMode) -> Self {
+        Self::from_limbs(&[value as u64, (value >> 64) as u64], false, mode)
+    }
+
+    /// Converts the value to an `i128`, returning an error unless it is an
+    /// integer in the range of `i128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(2f64.powi(100), -3.0);
+    ///
+    /// assert_eq!(a.to_i128_exact().unwrap(), (1 << 100) - 3);
+    /// assert!(TwoFloat::new_add(2f64.powi(100), 0.5).to_i128_exact().is_err());
+    /// assert!(TwoFloat::from(2f64.powi(127)).to_i128_exact().is_err());
+    /// ```
+    pub fn to_i128_exact(self) -> Result<i128, TwoFloatError> {
+        let mut limbs = [0; 2];
+        self.to_limbs(&mut limbs)?;
+        let magnitude = limbs[0] as u128 | ((limbs[1] as u128) << 64);
+        i128_from_magnitude(magnitude, self.hi < 0.0)
+    }
+
+    /// Converts the value to a `u128`, returning an error unless it is a
+    /// non-negative integer in the range of `u128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(2f64.powi(127), 1.0);
+    ///
+    /// assert_eq!(a.to_u128_exact().unwrap(), (1 << 127) + 1);
+    /// assert!(TwoFloat::from(-1.0).to_u128_exact().is_err());
+    /// ```
+    pub fn to_u128_exact(self) -> Result<u128, TwoFloatError> {
+        let mut limbs = [0; 2];
+        self.to_limbs(&mut limbs)?;
+        let magnitude = limbs[0] as u128 | ((limbs[1] as u128) << 64);
+        if self.hi < 0.0 && magnitude != 0 {
+            Err(TwoFloatError::ConversionError)
+        } else {
+            Ok(magnitude)
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Discrete Fourier transforms of `TwoFloat` data.
+//!
+//! Complex values are stored as `[re, im]` pairs. The forward transform of
+//! `x` of length `n` is
+//!
+//! `X[k] = sum(x[j] * exp(-2 pi i j k / n), j = 0..n)`,
+//!
+//! and the inverse transform includes the factor `1 / n`, so that it
+//! recovers the original data. Lengths that are powers of two use an
+//! iterative radix-2 algorithm, and other lengths use Bluestein's
+//! algorithm, which computes the transform as a convolution of a
+//! power-of-two length.
+//!
+//! The twiddle factors are computed to full double-double precision, with
+//! the angles reduced exactly to the first octant, so the accuracy of the
+//! transforms is limited only by the rounding in the butterflies.
+//!
+//! # Examples
+//!
+//! ```
+//! # use twofloat::{fft, TwoFloat};
+//! let mut data: Vec<[TwoFloat; 2]> = (0..8)
+//!     .map(|i| [TwoFloat::from(i), TwoFloat::from(0.0)])
+//!     .collect();
+//! fft::fft(&mut data);
+//!
+//! assert_eq!(data[0], [TwoFloat::from(28.0), TwoFloat::from(0.0)]);
+//! assert_eq!(data[4], [TwoFloat::from(-4.0), TwoFloat::from(0.0)]);
+//!
+//! fft::ifft(&mut data);
+//! assert!((data[5][0] - 5.0).abs() < 1e-30);
+//! ```
+
+use crate::{consts::PI, TwoFloat};
+
+type Complex = [TwoFloat; 2];
+
+fn add(a: Complex, b: Complex) -> Complex {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+fn sub(a: Complex, b: Complex) -> Complex {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn mul(a: Complex, b: Complex) -> Complex {
+    [a[0] * b[0] - a[1] * b[1], a[0] * b[1] + a[1] * b[0]]
+}
+
+fn conj(a: Complex) -> Complex {
+    [a[0], -a[1]]
+}
+
+fn scale(a: Complex, factor: TwoFloat) -> Complex {
+    [a[0] * factor, a[1] * factor]
+}
+
+// Returns `exp(-2 pi i p / q)`, or its conjugate if `inverse` is set.
+fn twiddle(p: usize, q: usize, inverse: bool) -> Complex {
+    // The angle is reduced to the first octant in units of 1 / (8q) turns,
+    // in which all of the reflections are exact
+    let full = 8 * q as u128;
+    let mut x = 8 * (p as u128 % q as u128);
+    let (mut sin_sign, mut cos_sign, mut swap) = (1.0, 1.0, false);
+    if x > full / 2 {
+        x = full - x;
+        sin_sign = -sin_sign;
+    }
+    if x > full / 4 {
+        x = full / 2 - x;
+        cos_sign = -cos_sign;
+    }
+    if x > full / 8 {
+        x = full / 4 - x;
+        swap = true;
+    }
+
+    let (sin, cos) = (PI * TwoFloat::from(x as f64) / (4 * q) as f64).sin_cos();
+    let (sin, cos) = if swap { (cos, sin) } else { (sin, cos) };
+    let sign = if inverse { 1.0 } else { -1.0 };
+    [cos * cos_sign, sin * (sin_sign * sign)]
+}
+
+// Computes the unnormalized transform of a power-of-two length in place.
+fn radix2(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let twiddles: Vec<Complex> = (0..n / 2).map(|k| twiddle(k, n, inverse)).collect();
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let stride = n / len;
+        for block in data.chunks_exact_mut(len) {
+            let (lower, upper) = block.split_at_mut(half);
+            for (k, (a, b)) in lower.iter_mut().zip(upper.iter_mut()).enumerate() {
+                let t = mul(*b, twiddles[k * stride]);
+                *b = sub(*a, t);
+                *a = add(*a, t);
+            }
+        }
+        len *= 2;
+    }
+}
+
+// Computes the unnormalized transform of any length in place, using
+// Bluestein's algorithm with chirp factors `exp(-pi i k^2 / n)`.
+fn bluestein(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    let m = (2 * n - 1).next_power_of_two();
+    let chirp: Vec<Complex> = (0..n)
+        .map(|k| {
+            twiddle(
+                (k as u128 * k as u128 % (2 * n as u128)) as usize,
+                2 * n,
+                inverse,
+            )
+        })
+        .collect();
+
+    let zero = [TwoFloat::from(0.0); 2];
+    let mut a = vec![zero; m];
+    for ((a_k, &x_k), &w_k) in a.iter_mut().zip(data.iter()).zip(&chirp) {
+        *a_k = mul(x_k, w_k);
+    }
+    let mut b = vec![zero; m];
+    b[0] = conj(chirp[0]);
+    for k in 1..n {
+        b[k] = conj(chirp[k]);
+        b[m - k] = b[k];
+    }
+
+    radix2(&mut a, false);
+    radix2(&mut b, false);
+    for (a_k, &b_k) in a.iter_mut().zip(&b) {
+        *a_k = mul(*a_k, b_k);
+    }
+    radix2(&mut a, true);
+
+    // The scaling by 1/m is exact
+    let factor = TwoFloat::from(1.0) / m as f64;
+    for ((x_k, &c_k), &w_k) in data.iter_mut().zip(&a).zip(&chirp) {
+        *x_k = mul(scale(c_k, factor), w_k);
+    }
+}
+
+fn transform(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    if n.is_power_of_two() {
+        radix2(data, inverse);
+    } else if n > 1 {
+        bluestein(data, inverse);
+    }
+}
+
+/// Computes the forward discrete Fourier transform of `data` in place.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{fft, TwoFloat};
+/// let one = [TwoFloat::from(1.0), TwoFloat::from(0.0)];
+/// let mut data = [one; 5];
+/// fft::fft(&mut data);
+///
+/// assert!((data[0][0] - 5.0).abs() < 1e-30);
+/// assert!(data[1][0].abs() < 1e-30 && data[1][1].abs() < 1e-30);
+/// ```
+pub fn fft(data: &mut [[TwoFloat; 2]]) {
+    transform(data, false);
+}
+
+/// Computes the inverse discrete Fourier transform of `data` in place,
+/// including the factor `1 / n`.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{fft, TwoFloat};
+/// let mut data = [[TwoFloat::from(0.0); 2]; 6];
+/// data[0] = [TwoFloat::from(6.0), TwoFloat::from(0.0)];
+/// fft::ifft(&mut data);
+///
+/// for value in &data {
+///     assert!((value[0] - 1.0).abs() < 1e-30 && value[1].abs() < 1e-30);
+/// }
+/// ```
+pub fn ifft(data: &mut [[TwoFloat; 2]]) {
+    transform(data, true);
+    let factor = TwoFloat::from(1.0) / data.len() as f64;
+    for value in data.iter_mut() {
+        *value = scale(*value, factor);
+    }
+}
+
+/// Computes the discrete Fourier transform of the real sequence `input`,
+/// returning the `n / 2 + 1` non-negative frequency terms, where `n` is
+/// the length of `input`. The remaining terms are the complex conjugates
+/// of these.
+///
+/// Sequences of even length are transformed as a complex sequence of half
+/// the length.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{fft, TwoFloat};
+/// let input: Vec<TwoFloat> = [1.0, 2.0, 3.0, 4.0].iter().map(|&x| TwoFloat::from(x)).collect();
+/// let spectrum = fft::rfft(&input);
+///
+/// assert_eq!(spectrum.len(), 3);
+/// assert_eq!(spectrum[0], [TwoFloat::from(10.0), TwoFloat::from(0.0)]);
+/// assert_eq!(spectrum[1], [TwoFloat::from(-2.0), TwoFloat::from(2.0)]);
+/// assert_eq!(spectrum[2], [TwoFloat::from(-2.0), TwoFloat::from(0.0)]);
+/// ```
+pub fn rfft(input: &[TwoFloat]) -> Vec<[TwoFloat; 2]> {
+    let n = input.len();
+    let zero = TwoFloat::from(0.0);
+    if n % 2 == 1 {
+        let mut data: Vec<Complex> = input.iter().map(|&x| [x, zero]).collect();
+        fft(&mut data);
+        data.truncate(n / 2 + 1);
+        return data;
+    } else if n == 0 {
+        return vec![[zero; 2]];
+    }
+
+    // Transform the even and odd terms together, then separate them
+    let m = n / 2;
+    let mut z: Vec<Complex> = input.chunks_exact(2).map(|x| [x[0], x[1]]).collect();
+    fft(&mut z);
+    (0..=m)
+        .map(|k| {
+            let a = z[k % m];
+            let b = conj(z[(m - k) % m]);
+            let even = scale(add(a, b), TwoFloat::from(0.5));
+            let odd = scale(sub(a, b), TwoFloat::from(0.5));
+            // odd / i, rotated by the twiddle factor
+            let odd = [odd[1], -odd[0]];
+            add(even, mul(odd, twiddle(k, n, false)))
+        })
+        .collect()
+}
+
+/// Computes the real sequence of length `len` whose discrete Fourier
+/// transform has the non-negative frequency terms `spectrum`, as returned
+/// by [`rfft`], including the factor `1 / len`. The imaginary parts of the
+/// zero frequency term, and of the `len / 2` term if `len` is even, are
+/// ignored.
+///
+/// # Panics
+///
+/// Panics if the length of `spectrum` is not `len / 2 + 1`.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{fft, TwoFloat};
+/// let input: Vec<TwoFloat> = (1..=7).map(|x| TwoFloat::from(x) / 3.0).collect();
+/// let output = fft::irfft(&fft::rfft(&input), 7);
+///
+/// for (x, y) in input.iter().zip(&output) {
+///     assert!((x - y).abs() < 1e-30);
+/// }
+/// ```
+pub fn irfft(spectrum: &[[TwoFloat; 2]], len: usize) -> Vec<TwoFloat> {
+    assert_eq!(
+        spectrum.len(),
+        len / 2 + 1,
+        "spectrum length does not match the output length"
+    );
+
+    let zero = TwoFloat::from(0.0);
+    if len % 2 == 1 {
+        let mut data: Vec<Complex> = (0..len)
+            .map(|k| {
+                if k < spectrum.len() {
+                    spectrum[k]
+                } else {
+                    conj(spectrum[len - k])
+                }
+            })
+            .collect();
+        data[0][1] = zero;
+        ifft(&mut data);
+        return data.iter().map(|x| x[0]).collect();
+    } else if len == 0 {
+        return Vec::new();
+    }
+
+    // Recover the transforms of the even and odd terms, and invert them
+    // together as a complex sequence of half the length
+    let m = len / 2;
+    let real = |k: usize| {
+        if k == 0 || k == m {
+            [spectrum[k][0], zero]
+        } else {
+            spectrum[k]
+        }
+    };
+    let mut z: Vec<Complex> = (0..m)
+        .map(|k| {
+            let a = real(k);
+            let b = conj(real(m - k));
+            let even = scale(add(a, b), TwoFloat::from(0.5));
+            let odd = mul(scale(sub(a, b), TwoFloat::from(0.5)), twiddle(k, len, true));
+            // even + i * odd
+            [even[0] - odd[1], even[1] + odd[0]]
+        })
+        .collect();
+    ifft(&mut z);
+    z.iter().flat_map(|x| x.iter().copied()).collect()
+}
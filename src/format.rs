@@ -1,118 +1,799 @@
-use core::fmt;
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{self, Write},
+    str::FromStr,
+};
 
-use crate::{math_util::mathfn, TwoFloat};
+use hexf::hexf64;
 
-impl fmt::Display for TwoFloat {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let sign_char = if self.lo().is_sign_positive() {
-            '+'
+use crate::{
+    convert::{
+        bit_length, compare_quotient, mul_add_limbs, mul_pow10_limbs, read_bits, round_away,
+    },
+    math_util::mathfn,
+    RoundingMode, TwoFloat, TwoFloatError,
+};
+
+// Number of 64-bit limbs used for the big integers in decimal conversions,
+// enough for the exact value of any finite `TwoFloat` scaled by the powers
+// of ten needed to extract its digits
+const DECIMAL_LIMBS: usize = 72;
+
+// Largest number of significant digits accepted by `from_decimal`
+const MAX_INPUT_DIGITS: usize = 1000;
+
+// Decimal exponents beyond this always overflow or underflow when parsing
+const MAX_PARSE_EXPONENT: i64 = 100_000;
+
+// Decimal exponents outside this range overflow or round to zero
+const MAX_DECIMAL_EXPONENT: i64 = 310;
+const MIN_DECIMAL_EXPONENT: i64 = -324;
+
+// Returns the used part of a little-endian limb slice.
+fn trim_limbs(limbs: &[u64]) -> &[u64] {
+    &limbs[..bit_length(limbs).div_ceil(64)]
+}
+
+// Returns the quotient `numerator * 2^shift / denominator` truncated to an
+// integer, saturating at 2^128 - 1.
+fn quotient_floor(numerator: &[u64], shift: i64, denominator: &[u64]) -> u128 {
+    // Number of low bits of the quotient searched after estimating it from
+    // the leading bits of each operand
+    const WINDOW_BITS: u32 = 32;
+
+    let at_least = |x: u128| compare_quotient(numerator, shift, denominator, x) != Ordering::Less;
+    let search = |mut quotient: u128, bits: u32| {
+        for bit in (0..bits).rev() {
+            let candidate = quotient | (1 << bit);
+            if at_least(candidate) {
+                quotient = candidate;
+            }
+        }
+        quotient
+    };
+
+    let leading = |limbs: &[u64]| {
+        let start = bit_length(limbs).saturating_sub(128);
+        (TwoFloat::from(read_bits(limbs, start)), start as i64)
+    };
+    let (n, n_start) = leading(numerator);
+    let (d, d_start) = leading(denominator);
+    let scale = (n_start + shift - d_start).clamp(i32::MIN as i64, i32::MAX as i64);
+    let estimate = (n / d).ldexp(scale as i32);
+    if estimate >= hexf64!("0x1.0p127") {
+        return u128::MAX;
+    }
+
+    let base = u128::try_from(estimate.trunc())
+        .unwrap_or(0)
+        .saturating_sub(1 << (WINDOW_BITS - 1))
+        & !((1 << WINDOW_BITS) - 1);
+    if at_least(base) && !at_least(base + (1 << WINDOW_BITS)) {
+        search(base, WINDOW_BITS)
+    } else {
+        search(0, 128)
+    }
+}
+
+// Parses a decimal number with an optional sign, decimal point and
+// exponent, returning the nearest `TwoFloat`
+pub(crate) fn parse_decimal(text: &str) -> Option<TwoFloat> {
+    let bytes = text.as_bytes();
+    let (negative, bytes) = match bytes.first() {
+        Some(b'-') => (true, &bytes[1..]),
+        Some(b'+') => (false, &bytes[1..]),
+        _ => (false, bytes),
+    };
+
+    let mut digits = [0; MAX_INPUT_DIGITS];
+    let mut count = 0;
+    let mut exp10 = 0i64;
+    let mut seen_digit = false;
+    let mut seen_point = false;
+    let mut position = 0;
+    while let Some(&byte) = bytes.get(position) {
+        match byte {
+            b'0'..=b'9' => {
+                seen_digit = true;
+                if count > 0 || byte != b'0' {
+                    *digits.get_mut(count)? = byte - b'0';
+                    count += 1;
+                }
+                if seen_point {
+                    exp10 -= 1;
+                }
+            }
+            b'.' if !seen_point => seen_point = true,
+            b'e' | b'E' => break,
+            _ => return None,
+        }
+        position += 1;
+    }
+    if !seen_digit {
+        return None;
+    }
+
+    if position < bytes.len() {
+        let exponent = &bytes[position + 1..];
+        let (exponent_negative, exponent) = match exponent.first() {
+            Some(b'-') => (true, &exponent[1..]),
+            Some(b'+') => (false, &exponent[1..]),
+            _ => (false, exponent),
+        };
+        if exponent.is_empty() || !exponent.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        let magnitude = exponent.iter().fold(0i64, |acc, &byte| {
+            (acc * 10 + (byte - b'0') as i64).min(MAX_PARSE_EXPONENT)
+        });
+        exp10 += if exponent_negative {
+            -magnitude
         } else {
-            '-'
+            magnitude
         };
-        if f.sign_plus() {
-            match f.precision() {
-                Some(p) => write!(
-                    f,
-                    "{:+.*} {} {:.*}",
-                    p,
-                    self.hi,
-                    sign_char,
-                    p,
-                    mathfn::abs(self.lo)
-                ),
-                None => write!(f, "{:+} {} {}", self.hi, sign_char, mathfn::abs(self.lo)),
+    }
+
+    let exp10 = exp10.clamp(-2 * MAX_PARSE_EXPONENT, 2 * MAX_PARSE_EXPONENT) as i32;
+    TwoFloat::from_decimal(negative, &digits[..count], exp10).ok()
+}
+
+impl TwoFloat {
+    /// Maximum number of significant digits produced by `to_decimal`.
+    pub const MAX_DECIMAL_DIGITS: usize = 38;
+
+    /// Rounds the value to the specified number of significant decimal
+    /// digits using the specified rounding mode. Returns the sign, the
+    /// digits and the decimal exponent `exp10` such that the rounded value
+    /// is `±d[0]d[1]...d[digits - 1] * 10^exp10`, where each element of the
+    /// array is a digit from 0 to 9. The first digit is nonzero unless the
+    /// value is zero, and the digits after the requested number are zero.
+    ///
+    /// The digits are correctly rounded from the exact value of `self`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not finite, or if `digits` is
+    /// zero or greater than `MAX_DECIMAL_DIGITS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{RoundingMode, TwoFloat};
+    /// let a = TwoFloat::from(-2.0 / 3.0);
+    /// let (negative, digits, exp10) = a.to_decimal(5, RoundingMode::NearestEven).unwrap();
+    ///
+    /// assert!(negative);
+    /// assert_eq!(digits[..5], [6, 6, 6, 6, 7]);
+    /// assert_eq!(exp10, -5);
+    ///
+    /// let (_, digits, _) = a.to_decimal(5, RoundingMode::TowardZero).unwrap();
+    /// assert_eq!(digits[..5], [6, 6, 6, 6, 6]);
+    /// ```
+    pub fn to_decimal(
+        self,
+        digits: usize,
+        mode: RoundingMode,
+    ) -> Result<(bool, [u8; Self::MAX_DECIMAL_DIGITS], i32), TwoFloatError> {
+        if digits == 0 || digits > Self::MAX_DECIMAL_DIGITS || !self.is_valid() {
+            return Err(TwoFloatError::ConversionError);
+        }
+
+        let negative = self.hi.is_sign_negative();
+        let mut result = [0; Self::MAX_DECIMAL_DIGITS];
+        if self.hi == 0.0 {
+            return Ok((negative, result, 0));
+        }
+
+        // The magnitude is mantissa * 2^exponent
+        let mut mantissa = [0; DECIMAL_LIMBS];
+        let exponent = self.to_ratio_limbs(&mut mantissa)?;
+        let lower = 10u128.pow(digits as u32 - 1);
+        let upper = lower * 10;
+
+        // Estimate of the decimal exponent using log10(2) ~ 78913 / 2^18,
+        // corrected below if the quotient has the wrong number of digits
+        let mut exp10 = ((self.ilogb() as i64 * 78913) >> 18) as i32 - digits as i32 + 1;
+        loop {
+            // The magnitude divided by 10^exp10 is numerator * 2^shift /
+            // denominator
+            let mut numerator = mantissa;
+            let mut denominator = [0; DECIMAL_LIMBS];
+            let shift = if exponent >= 0 {
+                denominator[0] = 1;
+                exponent as i64
+            } else {
+                denominator[-exponent as usize / 64] = 1 << (-exponent % 64);
+                0
+            };
+            let scaled = if exp10 >= 0 {
+                mul_pow10_limbs(&mut denominator, exp10 as u32)
+            } else {
+                mul_pow10_limbs(&mut numerator, exp10.unsigned_abs())
+            };
+            if !scaled {
+                return Err(TwoFloatError::ConversionError);
             }
-        } else {
-            match f.precision() {
-                Some(p) => write!(
-                    f,
-                    "{:.*} {} {:.*}",
-                    p,
-                    self.hi,
-                    sign_char,
-                    p,
-                    mathfn::abs(self.lo)
-                ),
-                None => write!(f, "{} {} {}", self.hi, sign_char, mathfn::abs(self.lo)),
+
+            let numerator = trim_limbs(&numerator);
+            let denominator = trim_limbs(&denominator);
+            let mut quotient = quotient_floor(numerator, shift, denominator);
+
+            if quotient >= upper {
+                exp10 += 1;
+                continue;
+            } else if quotient < lower {
+                exp10 -= 1;
+                continue;
+            }
+
+            // Compare the remainder with half of the denominator
+            let (half, sticky) =
+                match compare_quotient(numerator, shift + 1, denominator, 2 * quotient + 1) {
+                    Ordering::Less => (
+                        false,
+                        compare_quotient(numerator, shift, denominator, quotient)
+                            != Ordering::Equal,
+                    ),
+                    Ordering::Equal => (true, false),
+                    Ordering::Greater => (true, true),
+                };
+            if round_away(mode, negative, quotient & 1 != 0, half, sticky) {
+                quotient += 1;
+                if quotient == upper {
+                    quotient = lower;
+                    exp10 += 1;
+                }
+            }
+
+            for digit in result[..digits].iter_mut().rev() {
+                *digit = (quotient % 10) as u8;
+                quotient /= 10;
             }
+            return Ok((negative, result, exp10));
+        }
+    }
+
+    /// Returns the value `±d[0]d[1]...d[n - 1] * 10^exp10` correctly
+    /// rounded to the nearest `TwoFloat`, where each element of `digits` is
+    /// a digit from 0 to 9. Values too large to represent are converted to
+    /// an infinity.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if any element of `digits` is not a decimal
+    /// digit, or if there are more than 1000 significant digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from_decimal(false, &[1, 2, 5], -3).unwrap();
+    /// let b = TwoFloat::from_decimal(true, &[1], -1).unwrap();
+    ///
+    /// assert_eq!(a, TwoFloat::from(0.125));
+    /// assert_eq!(b, -TwoFloat::from_ratio(1, 10));
+    /// assert!(TwoFloat::from_decimal(false, &[10], 0).is_err());
+    /// ```
+    pub fn from_decimal(negative: bool, digits: &[u8], exp10: i32) -> Result<Self, TwoFloatError> {
+        if digits.iter().any(|&digit| digit > 9) {
+            return Err(TwoFloatError::ConversionError);
+        }
+
+        let signed = |value: Self| if negative { -value } else { value };
+        let digits = match digits.iter().position(|&digit| digit != 0) {
+            Some(start) => &digits[start..],
+            None => return Ok(signed(Self::from(0.0))),
+        };
+        if digits.len() > MAX_INPUT_DIGITS {
+            return Err(TwoFloatError::ConversionError);
+        }
+
+        // The value lies between 10^(magnitude - 1) and 10^magnitude
+        let magnitude = digits.len() as i64 + exp10 as i64;
+        if magnitude > MAX_DECIMAL_EXPONENT {
+            return Ok(signed(Self::INFINITY));
+        } else if magnitude < MIN_DECIMAL_EXPONENT {
+            return Ok(signed(Self::from(0.0)));
+        }
+
+        let mut numerator = [0; DECIMAL_LIMBS];
+        let mut denominator = [0; DECIMAL_LIMBS];
+        denominator[0] = 1;
+        for &digit in digits {
+            mul_add_limbs(&mut numerator, 10, digit as u64);
+        }
+        if exp10 >= 0 {
+            mul_pow10_limbs(&mut numerator, exp10 as u32);
+        } else {
+            mul_pow10_limbs(&mut denominator, exp10.unsigned_abs());
+        }
+
+        Ok(Self::from_ratio_limbs(
+            trim_limbs(&numerator),
+            trim_limbs(&denominator),
+            negative,
+            RoundingMode::NearestEven,
+        ))
+    }
+
+    /// Returns an object that formats the value using the specified
+    /// options, which allow engineering notation, SI prefixes and digit
+    /// grouping. The digits are correctly rounded from the exact value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{FormatOptions, Notation, TwoFloat};
+    /// let a = TwoFloat::from(0.000123456);
+    /// let options = FormatOptions::new().notation(Notation::SiPrefix).precision(2);
+    ///
+    /// assert_eq!(format!("{}", a.display_with(options)), "123µ");
+    /// assert_eq!(format!("{:*^10.3}", a.display_with(options)), "**123.5µ**");
+    /// assert_eq!(format!("{:.3}", a.display_with(FormatOptions::new())), "0.000");
+    /// ```
+    pub fn display_with(self, options: FormatOptions) -> FormattedTwoFloat {
+        FormattedTwoFloat {
+            value: self,
+            options,
         }
     }
 }
 
-impl fmt::LowerExp for TwoFloat {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let sign_char = if self.lo().is_sign_positive() {
-            '+'
+impl FromStr for TwoFloat {
+    type Err = TwoFloatError;
+
+    /// Parses a decimal number with an optional sign, decimal point and
+    /// exponent, such as `-1.25e-3`, correctly rounded to the nearest
+    /// `TwoFloat`. As for `f64`, the strings `inf`, `infinity` and `nan`
+    /// are accepted in any case, and values too large to represent are
+    /// converted to an infinity.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the string is not a decimal number, or if it
+    /// has more than 1000 significant digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a: TwoFloat = "0.1".parse().unwrap();
+    /// let b: TwoFloat = "-1e400".parse().unwrap();
+    ///
+    /// assert_eq!(a, TwoFloat::from_decimal(false, &[1], -1).unwrap());
+    /// assert_eq!(b, TwoFloat::NEG_INFINITY);
+    /// assert!("1.2.3".parse::<TwoFloat>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, TwoFloatError> {
+        let (negative, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if unsigned.eq_ignore_ascii_case("inf") || unsigned.eq_ignore_ascii_case("infinity") {
+            Ok(if negative {
+                Self::NEG_INFINITY
+            } else {
+                Self::INFINITY
+            })
+        } else if unsigned.eq_ignore_ascii_case("nan") {
+            Ok(Self::NAN)
+        } else {
+            parse_decimal(s).ok_or(TwoFloatError::ParseError)
+        }
+    }
+}
+
+/// Notation used to format a value with `TwoFloat::display_with`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// Fixed-point notation. The precision is the number of digits after
+    /// the decimal point.
+    #[default]
+    Fixed,
+    /// Scientific notation with one digit before the decimal point. The
+    /// precision is the number of digits after the decimal point.
+    Scientific,
+    /// Engineering notation, where the exponent is a multiple of three and
+    /// there are one to three digits before the decimal point. The
+    /// precision is one less than the number of significant digits, as in
+    /// scientific notation.
+    Engineering,
+    /// Engineering notation with the exponent written as an SI prefix, such
+    /// as `k` for 10^3 or `µ` for 10^-6. Exponents outside the range of the
+    /// SI prefixes are written as in engineering notation.
+    SiPrefix,
+}
+
+/// Options for formatting a value with `TwoFloat::display_with`.
+///
+/// The width, fill, alignment and sign flags of the format string are
+/// applied to the whole formatted value, and a precision in the format
+/// string takes priority over the precision in the options. If neither
+/// specifies a precision, the value is rounded to 32 significant digits and
+/// trailing zeros are removed.
+///
+/// At most `TwoFloat::MAX_DECIMAL_DIGITS` significant digits are computed,
+/// and any further digits are written as zeros.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{FormatOptions, Notation, TwoFloat};
+/// let options = FormatOptions::new().notation(Notation::Engineering).precision(3);
+/// let a = TwoFloat::from(123456.0);
+///
+/// assert_eq!(format!("{}", a.display_with(options)), "123.5e3");
+/// assert_eq!(format!("{:>10}", a.display_with(options)), "   123.5e3");
+/// assert_eq!(format!("{:.1}", a.display_with(options)), "120e3");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    notation: Notation,
+    precision: Option<usize>,
+    separator: Option<char>,
+    mode: RoundingMode,
+}
+
+impl FormatOptions {
+    /// Creates the default options: fixed-point notation without digit
+    /// grouping, rounding to nearest with ties to even.
+    pub const fn new() -> Self {
+        Self {
+            notation: Notation::Fixed,
+            precision: None,
+            separator: None,
+            mode: RoundingMode::NearestEven,
+        }
+    }
+
+    /// Sets the notation.
+    pub const fn notation(self, notation: Notation) -> Self {
+        Self { notation, ..self }
+    }
+
+    /// Sets the precision used when the format string does not specify
+    /// one. The meaning of the precision depends on the notation.
+    pub const fn precision(self, precision: usize) -> Self {
+        Self {
+            precision: Some(precision),
+            ..self
+        }
+    }
+
+    /// Groups the digits before the decimal point in threes, separated by
+    /// `separator`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{FormatOptions, TwoFloat};
+    /// let options = FormatOptions::new().grouping(',').precision(2);
+    /// let a = TwoFloat::from(-1234567.125);
+    ///
+    /// assert_eq!(format!("{}", a.display_with(options)), "-1,234,567.12");
+    /// ```
+    pub const fn grouping(self, separator: char) -> Self {
+        Self {
+            separator: Some(separator),
+            ..self
+        }
+    }
+
+    /// Sets the rounding mode used to round the value to the displayed
+    /// digits.
+    pub const fn rounding(self, mode: RoundingMode) -> Self {
+        Self { mode, ..self }
+    }
+}
+
+/// Value formatted with the options given to `TwoFloat::display_with`.
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedTwoFloat {
+    value: TwoFloat,
+    options: FormatOptions,
+}
+
+// Digits of a rounded value: `digits[..len]` followed by zeros, where the
+// first digit is multiplied by 10^exp10
+struct RoundedDigits {
+    negative: bool,
+    digits: [u8; TwoFloat::MAX_DECIMAL_DIGITS],
+    len: usize,
+    exp10: i32,
+}
+
+impl RoundedDigits {
+    fn zero(negative: bool) -> Self {
+        Self {
+            negative,
+            digits: [0; TwoFloat::MAX_DECIMAL_DIGITS],
+            len: 0,
+            exp10: 0,
+        }
+    }
+
+    // Rounds to the specified number of significant digits
+    fn significant(value: TwoFloat, digits: usize, mode: RoundingMode) -> Result<Self, fmt::Error> {
+        let digits = digits.clamp(1, TwoFloat::MAX_DECIMAL_DIGITS);
+        let (negative, result, exp10) = value.to_decimal(digits, mode).map_err(|_| fmt::Error)?;
+        if value.hi == 0.0 {
+            return Ok(Self::zero(negative));
+        }
+
+        Ok(Self {
+            negative,
+            digits: result,
+            len: digits,
+            exp10: exp10 + digits as i32 - 1,
+        })
+    }
+
+    // Rounds to the specified number of digits after the decimal point
+    fn fixed(value: TwoFloat, precision: usize, mode: RoundingMode) -> Result<Self, fmt::Error> {
+        let (negative, truncated, exp10) = value
+            .to_decimal(TwoFloat::MAX_DECIMAL_DIGITS, RoundingMode::TowardZero)
+            .map_err(|_| fmt::Error)?;
+        if value.hi == 0.0 {
+            return Ok(Self::zero(negative));
+        }
+
+        let lead = exp10 as i64 + TwoFloat::MAX_DECIMAL_DIGITS as i64 - 1;
+        let digits = (lead + 1).saturating_add(i64::try_from(precision).unwrap_or(i64::MAX));
+        if digits > 0 {
+            return Self::significant(value, usize::try_from(digits).unwrap_or(usize::MAX), mode);
+        }
+
+        // The value is less than one unit in the last place, so it rounds
+        // to either zero or one unit. The only exact tie is 0.5 rounded to
+        // an integer.
+        let half = digits == 0 && truncated[0] >= 5;
+        let sticky = value.abs() != 0.5;
+        if round_away(mode, negative, false, half, sticky) {
+            let mut result = Self::zero(negative);
+            result.digits[0] = 1;
+            result.len = 1;
+            result.exp10 = -(precision as i32);
+            Ok(result)
         } else {
-            '-'
+            Ok(Self::zero(negative))
+        }
+    }
+
+    fn trim(mut self) -> Self {
+        while self.len > 0 && self.digits[self.len - 1] == 0 {
+            self.len -= 1;
+        }
+        self
+    }
+
+    // Writes the digits in positional form with the first digit in the
+    // position of 10^lead and `fraction` digits after the decimal point
+    fn write(
+        &self,
+        w: &mut dyn Write,
+        lead: i32,
+        fraction: usize,
+        separator: Option<char>,
+    ) -> fmt::Result {
+        let digit = |position: i64| {
+            let index = lead as i64 - position;
+            if index >= 0 && (index as usize) < self.len {
+                char::from(b'0' + self.digits[index as usize])
+            } else {
+                '0'
+            }
         };
-        if f.sign_plus() {
-            match f.precision() {
-                Some(p) => write!(
-                    f,
-                    "{:+.*e} {} {:.*e}",
-                    p,
-                    self.hi,
-                    sign_char,
-                    p,
-                    mathfn::abs(self.lo)
-                ),
-                None => write!(f, "{:+e} {} {:e}", self.hi, sign_char, mathfn::abs(self.lo)),
+
+        for position in (0..=lead.max(0) as i64).rev() {
+            w.write_char(digit(position))?;
+            if let Some(separator) = separator {
+                if position > 0 && position % 3 == 0 {
+                    w.write_char(separator)?;
+                }
             }
-        } else {
-            match f.precision() {
-                Some(p) => write!(
-                    f,
-                    "{:.*e} {} {:.*e}",
-                    p,
-                    self.hi,
-                    sign_char,
-                    p,
-                    mathfn::abs(self.lo)
-                ),
-                None => write!(f, "{:e} {} {:e}", self.hi, sign_char, mathfn::abs(self.lo)),
+        }
+        if fraction > 0 {
+            w.write_char('.')?;
+            for position in 1..=fraction as i64 {
+                w.write_char(digit(-position))?;
             }
         }
+        Ok(())
     }
 }
 
-impl fmt::UpperExp for TwoFloat {
+// SI prefixes for the exponents from -30 to 30 in steps of three
+const SI_PREFIXES: [&str; 21] = [
+    "q", "r", "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+    "R", "Q",
+];
+
+// Default number of significant digits when no precision is specified
+const DEFAULT_DIGITS: usize = 32;
+
+// Counts the characters written, to find the padding needed
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+// Writes the sign followed by the output of `body`, padded to the width
+// specified in the formatter. Numbers are right-aligned by default, and
+// sign-aware zero padding inserts the zeros after the sign.
+fn write_padded(
+    f: &mut fmt::Formatter<'_>,
+    sign: &str,
+    body: impl Fn(&mut dyn Write) -> fmt::Result,
+) -> fmt::Result {
+    let width = match f.width() {
+        Some(width) => width,
+        None => {
+            f.write_str(sign)?;
+            return body(f);
+        }
+    };
+
+    let mut counter = CharCounter(sign.chars().count());
+    body(&mut counter)?;
+    let padding = width.saturating_sub(counter.0);
+    if f.sign_aware_zero_pad() {
+        f.write_str(sign)?;
+        for _ in 0..padding {
+            f.write_char('0')?;
+        }
+        return body(f);
+    }
+
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(sign)?;
+    body(f)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for FormattedTwoFloat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let sign_char = if self.lo().is_sign_positive() {
-            '+'
+        let value = self.value;
+        let options = self.options;
+        if !value.is_valid() {
+            let (sign, text) = if value.hi.is_infinite() {
+                match (value.hi < 0.0, f.sign_plus()) {
+                    (true, _) => ("-", "inf"),
+                    (false, true) => ("+", "inf"),
+                    (false, false) => ("", "inf"),
+                }
+            } else {
+                ("", "NaN")
+            };
+            return write_padded(f, sign, |w| w.write_str(text));
+        }
+
+        let precision = f.precision().or(options.precision);
+        let significant = precision.map(|p| p.saturating_add(1));
+        let rounded = match (options.notation, precision) {
+            (Notation::Fixed, Some(p)) => RoundedDigits::fixed(value, p, options.mode)?,
+            (_, Some(p)) => RoundedDigits::significant(value, p.saturating_add(1), options.mode)?,
+            (_, None) => RoundedDigits::significant(value, DEFAULT_DIGITS, options.mode)?.trim(),
+        };
+        let significant = significant.unwrap_or(rounded.len.max(1));
+
+        let sign = if rounded.negative {
+            "-"
+        } else if f.sign_plus() {
+            "+"
         } else {
-            '-'
+            ""
         };
-        if f.sign_plus() {
-            match f.precision() {
-                Some(p) => write!(
-                    f,
-                    "{:+.*E} {} {:.*E}",
-                    p,
-                    self.hi,
-                    sign_char,
-                    p,
-                    mathfn::abs(self.lo)
-                ),
-                None => write!(f, "{:+E} {} {:E}", self.hi, sign_char, mathfn::abs(self.lo)),
+        let separator = options.separator;
+        match options.notation {
+            Notation::Fixed => {
+                let fraction = precision.unwrap_or_else(|| {
+                    (rounded.len as i64 - 1 - rounded.exp10 as i64).max(0) as usize
+                });
+                write_padded(f, sign, |w| {
+                    rounded.write(w, rounded.exp10, fraction, separator)
+                })
             }
-        } else {
-            match f.precision() {
-                Some(p) => write!(
-                    f,
-                    "{:.*E} {} {:.*E}",
-                    p,
-                    self.hi,
-                    sign_char,
-                    p,
-                    mathfn::abs(self.lo)
-                ),
-                None => write!(f, "{:E} {} {:E}", self.hi, sign_char, mathfn::abs(self.lo)),
+            Notation::Scientific => write_padded(f, sign, |w| {
+                rounded.write(w, 0, significant - 1, separator)?;
+                write!(w, "e{}", rounded.exp10)
+            }),
+            Notation::Engineering | Notation::SiPrefix => {
+                let lead = rounded.exp10.rem_euclid(3);
+                let exponent = rounded.exp10 - lead;
+                let fraction = significant.saturating_sub(lead as usize + 1);
+                let prefix = if options.notation == Notation::SiPrefix {
+                    SI_PREFIXES
+                        .get((exponent / 3 + 10) as usize)
+                        .filter(|_| exponent.abs() <= 30)
+                } else {
+                    None
+                };
+                write_padded(f, sign, |w| {
+                    rounded.write(w, lead, fraction, separator)?;
+                    match prefix {
+                        Some(prefix) => w.write_str(prefix),
+                        None => write!(w, "e{}", exponent),
+                    }
+                })
             }
         }
     }
 }
 
+// Formats the value as `hi ± |lo|`, using `word` to format each word with
+// the precision of the formatter
+fn write_words(
+    value: &TwoFloat,
+    f: &mut fmt::Formatter<'_>,
+    word: fn(&mut dyn Write, f64, Option<usize>) -> fmt::Result,
+) -> fmt::Result {
+    let sign = if value.hi.is_nan() {
+        ""
+    } else if value.hi.is_sign_negative() {
+        "-"
+    } else if f.sign_plus() {
+        "+"
+    } else {
+        ""
+    };
+    let sign_char = if value.lo.is_sign_positive() {
+        '+'
+    } else {
+        '-'
+    };
+    let precision = f.precision();
+    write_padded(f, sign, |w| {
+        word(w, mathfn::abs(value.hi), precision)?;
+        write!(w, " {} ", sign_char)?;
+        word(w, mathfn::abs(value.lo), precision)
+    })
+}
+
+impl fmt::Display for TwoFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_words(self, f, |w, x, precision| match precision {
+            Some(p) => write!(w, "{:.*}", p, x),
+            None => write!(w, "{}", x),
+        })
+    }
+}
+
+impl fmt::LowerExp for TwoFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_words(self, f, |w, x, precision| match precision {
+            Some(p) => write!(w, "{:.*e}", p, x),
+            None => write!(w, "{:e}", x),
+        })
+    }
+}
+
+impl fmt::UpperExp for TwoFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_words(self, f, |w, x, precision| match precision {
+            Some(p) => write!(w, "{:.*E}", p, x),
+            None => write!(w, "{:E}", x),
+        })
+    }
+}
+
 #[cfg(all(feature = "std", test))]
 mod test {
     use crate::TwoFloat;
@@ -1,3 +1,5 @@
+pub mod bits;
+pub mod continued_fraction;
 pub mod fraction;
 pub mod sign;
 
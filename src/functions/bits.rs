@@ -0,0 +1,430 @@
+use core::convert::TryFrom;
+
+use crate::{
+    arithmetic::fast_two_sum,
+    math_util::{exponent, mathfn, mul_pow2},
+    TwoFloat, TwoFloatError,
+};
+
+// Scaling by more than this always overflows or underflows
+const MAX_SCALE: i32 = 2200;
+
+// Number of significant bits used for the spacing of `TwoFloat` values
+const SIGNIFICANT_BITS: i32 = 106;
+
+// Exponent of the smallest positive subnormal `f64` value
+const MIN_EXPONENT: i32 = -1074;
+
+// Joins the byte representations of the high and low words
+fn join_words(hi: [u8; 8], lo: [u8; 8]) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    bytes[..8].copy_from_slice(&hi);
+    bytes[8..].copy_from_slice(&lo);
+    bytes
+}
+
+// Splits a byte representation into those of the high and low words
+fn split_words(bytes: [u8; 16]) -> ([u8; 8], [u8; 8]) {
+    let mut hi = [0; 8];
+    let mut lo = [0; 8];
+    hi.copy_from_slice(&bytes[..8]);
+    lo.copy_from_slice(&bytes[8..]);
+    (hi, lo)
+}
+
+impl TwoFloat {
+    /// Returns `self * 2^exp`. The result is exact unless it overflows or
+    /// the low word underflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(3.0, 1e-20);
+    /// let b = a.ldexp(4);
+    ///
+    /// assert_eq!(b, TwoFloat::new_add(48.0, 16e-20));
+    /// assert_eq!(b.ldexp(-4), a);
+    /// ```
+    pub fn ldexp(self, exp: i32) -> Self {
+        let exp = exp.clamp(-MAX_SCALE, MAX_SCALE);
+        let hi = mul_pow2(self.hi, exp);
+        if hi.is_infinite() {
+            if hi > 0.0 {
+                Self::INFINITY
+            } else {
+                Self::NEG_INFINITY
+            }
+        } else if !hi.is_finite() || hi == 0.0 {
+            Self::from(hi)
+        } else {
+            fast_two_sum(hi, mul_pow2(self.lo, exp))
+        }
+    }
+
+    /// Returns `self * 2^exp`. This is equivalent to `ldexp`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(1.0, 3.0);
+    ///
+    /// assert_eq!(a.scalbn(-10), a.ldexp(-10));
+    /// ```
+    pub fn scalbn(self, exp: i32) -> Self {
+        self.ldexp(exp)
+    }
+
+    /// Breaks `self` into a normalized fraction and an integral power of
+    /// two, such that `self == fraction * 2^exp` and the magnitude of the
+    /// fraction is in the range `[0.5, 1)`. Zero and non-finite values are
+    /// returned unchanged with an exponent of zero.
+    ///
+    /// The decomposition is exact unless scaling causes the low word to
+    /// underflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(12.0, 1e-20);
+    /// let (fraction, exp) = a.frexp();
+    ///
+    /// assert_eq!(fraction, TwoFloat::new_add(0.75, 1e-20 / 16.0));
+    /// assert_eq!(exp, 4);
+    /// ```
+    pub fn frexp(self) -> (Self, i32) {
+        if self.hi == 0.0 || !self.hi.is_finite() {
+            (self, 0)
+        } else {
+            let exp = self.ilogb() + 1;
+            (self.ldexp(-exp), exp)
+        }
+    }
+
+    /// Returns the exponent of the leading bit of `self`, that is, the
+    /// floor of `log2(|self|)`. Returns `i32::MIN` for zero or `NAN`, and
+    /// `i32::MAX` for infinite values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(8.0);
+    /// let b = TwoFloat::new_add(8.0, -1e-20);
+    ///
+    /// assert_eq!(a.ilogb(), 3);
+    /// assert_eq!(b.ilogb(), 2);
+    /// ```
+    pub fn ilogb(self) -> i32 {
+        if self.hi == 0.0 || self.hi.is_nan() {
+            i32::MIN
+        } else if self.hi.is_infinite() {
+            i32::MAX
+        } else {
+            let exp = exponent(self.hi);
+            let is_power_of_two = mathfn::abs(self.hi) == mul_pow2(1.0, exp);
+            if is_power_of_two && self.lo != 0.0 && (self.lo < 0.0) != (self.hi < 0.0) {
+                exp - 1
+            } else {
+                exp
+            }
+        }
+    }
+
+    /// Returns the unit in the last place of `self`, treating a `TwoFloat`
+    /// as having 106 significant bits. Returns `NAN` for non-finite values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(1.0);
+    /// let b = TwoFloat::from(0.0);
+    ///
+    /// assert_eq!(a.ulp(), TwoFloat::from(2f64.powi(-105)));
+    /// assert_eq!(b.ulp(), TwoFloat::from(f64::from_bits(1)));
+    /// ```
+    pub fn ulp(self) -> Self {
+        if !self.hi.is_finite() {
+            Self::NAN
+        } else if self.hi == 0.0 {
+            Self::from(mul_pow2(1.0, MIN_EXPONENT))
+        } else {
+            let exp = (self.ilogb() - SIGNIFICANT_BITS + 1).max(MIN_EXPONENT);
+            Self::from(mul_pow2(1.0, exp))
+        }
+    }
+
+    /// Returns `self` plus one unit in the last place, treating a `TwoFloat`
+    /// as having 106 significant bits. The step is `self.ulp()`, halved when
+    /// `self` is a negative power of two outside the subnormal range because
+    /// 106-bit values are spaced more closely below a power of two in
+    /// magnitude.
+    ///
+    /// This is the next 106-bit value, not the least `TwoFloat` greater than
+    /// `self`, since a `TwoFloat` can hold more than 106 significant bits
+    /// when its words are far apart. The result is exact if `self` can be
+    /// represented with 106 significant bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(1.0);
+    ///
+    /// assert_eq!(a.next_up(), TwoFloat::new_add(1.0, 2f64.powi(-105)));
+    /// assert_eq!(a.next_up().next_down(), a);
+    /// ```
+    pub fn next_up(self) -> Self {
+        if self.hi.is_nan() || self.hi == f64::INFINITY {
+            self
+        } else if self.hi == f64::NEG_INFINITY {
+            Self::MIN
+        } else if self.hi == 0.0 {
+            Self::from(mul_pow2(1.0, MIN_EXPONENT))
+        } else {
+            let mut step = self.ulp().hi;
+            // Values are more closely spaced below a power of two
+            let exp = exponent(self.hi);
+            if self.hi < 0.0
+                && self.lo == 0.0
+                && -self.hi == mul_pow2(1.0, exp)
+                && exp - SIGNIFICANT_BITS >= MIN_EXPONENT
+            {
+                step *= 0.5;
+            }
+
+            let result = self + step;
+            if result.is_valid() {
+                result
+            } else {
+                Self::INFINITY
+            }
+        }
+    }
+
+    /// Returns `self` minus one unit in the last place, treating a `TwoFloat`
+    /// as having 106 significant bits. The step is `self.ulp()`, halved when
+    /// `self` is a positive power of two, and mirrors `next_up`.
+    ///
+    /// This is the previous 106-bit value, not the greatest `TwoFloat` less
+    /// than `self`. The result is exact if `self` can be represented with
+    /// 106 significant bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(1.0);
+    ///
+    /// assert_eq!(a.next_down(), TwoFloat::new_sub(1.0, 2f64.powi(-106)));
+    /// assert_eq!(a.next_down().next_up(), a);
+    /// ```
+    pub fn next_down(self) -> Self {
+        -(-self).next_up()
+    }
+
+    /// Returns the mantissa, base 2 exponent and sign of `self` as integers,
+    /// such that `self == sign * mantissa * 2^exponent`. The mantissa holds
+    /// 106 significant bits, so the decomposition is exact whenever `self`
+    /// can be represented with 106 bits. Otherwise the mantissa is rounded
+    /// to nearest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(-1.0, -2f64.powi(-100));
+    /// let (mantissa, exponent, sign) = a.integer_decode();
+    ///
+    /// assert_eq!(mantissa, (1 << 105) + (1 << 5));
+    /// assert_eq!(exponent, -105);
+    /// assert_eq!(sign, -1);
+    /// ```
+    pub fn integer_decode(self) -> (u128, i16, i8) {
+        if self.hi == 0.0 || !self.hi.is_finite() {
+            let (mantissa, exponent, sign) = num_traits::float::FloatCore::integer_decode(self.hi);
+            return (mantissa as u128, exponent, sign);
+        }
+
+        let exp = (self.ilogb() - SIGNIFICANT_BITS + 1).max(MIN_EXPONENT);
+        let hi = mathfn::abs(mul_pow2(self.hi, -exp));
+        let lo = mathfn::signum(self.hi) * mul_pow2(self.lo, -exp);
+
+        // Round the low word to the nearest integer, with ties to even
+        let lo_rounded = mathfn::round(lo);
+        let lo_int = if mathfn::abs(lo_rounded - lo) == 0.5 {
+            2.0 * mathfn::round(0.5 * lo)
+        } else {
+            lo_rounded
+        };
+
+        let mantissa = (hi as i128 + lo_int as i128) as u128;
+        let sign = if self.hi < 0.0 { -1 } else { 1 };
+
+        // A power of two less a tiny low word can round up to the next
+        // power of two, which needs one more bit
+        if mantissa == 1 << SIGNIFICANT_BITS {
+            (mantissa >> 1, (exp + 1) as i16, sign)
+        } else {
+            (mantissa, exp as i16, sign)
+        }
+    }
+
+    /// Returns the raw bit patterns of the high and low words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(1.0, -2f64.powi(-60));
+    ///
+    /// assert_eq!(a.to_bits(), (1f64.to_bits(), (-2f64.powi(-60)).to_bits()));
+    /// ```
+    pub fn to_bits(self) -> (u64, u64) {
+        (self.hi.to_bits(), self.lo.to_bits())
+    }
+
+    /// Creates a value from the raw bit patterns of the high and low words.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the words are not finite or overlap, as in
+    /// the conversion from `(f64, f64)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(1.0, 3.0);
+    ///
+    /// assert_eq!(TwoFloat::from_bits(a.to_bits()).unwrap(), a);
+    /// assert!(TwoFloat::from_bits((1f64.to_bits(), 1f64.to_bits())).is_err());
+    /// ```
+    pub fn from_bits(bits: (u64, u64)) -> Result<Self, TwoFloatError> {
+        Self::try_from((f64::from_bits(bits.0), f64::from_bits(bits.1)))
+    }
+
+    /// Returns the memory representation of the value as a byte array in
+    /// little-endian byte order. The first eight bytes hold the high word
+    /// and the last eight bytes hold the low word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let bytes = TwoFloat::new_add(1.0, 2f64.powi(-60)).to_le_bytes();
+    ///
+    /// assert_eq!(bytes[..8], 1f64.to_le_bytes());
+    /// assert_eq!(bytes[8..], 2f64.powi(-60).to_le_bytes());
+    /// ```
+    pub fn to_le_bytes(self) -> [u8; 16] {
+        join_words(self.hi.to_le_bytes(), self.lo.to_le_bytes())
+    }
+
+    /// Returns the memory representation of the value as a byte array in
+    /// big-endian byte order. The first eight bytes hold the high word and
+    /// the last eight bytes hold the low word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let bytes = TwoFloat::new_add(1.0, 2f64.powi(-60)).to_be_bytes();
+    ///
+    /// assert_eq!(bytes[..8], 1f64.to_be_bytes());
+    /// assert_eq!(bytes[8..], 2f64.powi(-60).to_be_bytes());
+    /// ```
+    pub fn to_be_bytes(self) -> [u8; 16] {
+        join_words(self.hi.to_be_bytes(), self.lo.to_be_bytes())
+    }
+
+    /// Returns the memory representation of the value as a byte array in
+    /// native byte order. The first eight bytes hold the high word and the
+    /// last eight bytes hold the low word, matching the layout of the
+    /// struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_add(1.0, 2f64.powi(-60));
+    /// let expected = if cfg!(target_endian = "big") {
+    ///     a.to_be_bytes()
+    /// } else {
+    ///     a.to_le_bytes()
+    /// };
+    ///
+    /// assert_eq!(a.to_ne_bytes(), expected);
+    /// ```
+    pub fn to_ne_bytes(self) -> [u8; 16] {
+        join_words(self.hi.to_ne_bytes(), self.lo.to_ne_bytes())
+    }
+
+    /// Creates a value from its memory representation as a byte array in
+    /// little-endian byte order, as produced by `to_le_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the words are not finite or overlap, as in
+    /// the conversion from `(f64, f64)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(-2.0, 7.0);
+    ///
+    /// assert_eq!(TwoFloat::from_le_bytes(a.to_le_bytes()).unwrap(), a);
+    /// assert!(TwoFloat::from_le_bytes([0xff; 16]).is_err());
+    /// ```
+    pub fn from_le_bytes(bytes: [u8; 16]) -> Result<Self, TwoFloatError> {
+        let (hi, lo) = split_words(bytes);
+        Self::try_from((f64::from_le_bytes(hi), f64::from_le_bytes(lo)))
+    }
+
+    /// Creates a value from its memory representation as a byte array in
+    /// big-endian byte order, as produced by `to_be_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the words are not finite or overlap, as in
+    /// the conversion from `(f64, f64)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(-2.0, 7.0);
+    ///
+    /// assert_eq!(TwoFloat::from_be_bytes(a.to_be_bytes()).unwrap(), a);
+    /// assert!(TwoFloat::from_be_bytes([0xff; 16]).is_err());
+    /// ```
+    pub fn from_be_bytes(bytes: [u8; 16]) -> Result<Self, TwoFloatError> {
+        let (hi, lo) = split_words(bytes);
+        Self::try_from((f64::from_be_bytes(hi), f64::from_be_bytes(lo)))
+    }
+
+    /// Creates a value from its memory representation as a byte array in
+    /// native byte order, as produced by `to_ne_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the words are not finite or overlap, as in
+    /// the conversion from `(f64, f64)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::new_div(-2.0, 7.0);
+    ///
+    /// assert_eq!(TwoFloat::from_ne_bytes(a.to_ne_bytes()).unwrap(), a);
+    /// ```
+    pub fn from_ne_bytes(bytes: [u8; 16]) -> Result<Self, TwoFloatError> {
+        let (hi, lo) = split_words(bytes);
+        Self::try_from((f64::from_ne_bytes(hi), f64::from_ne_bytes(lo)))
+    }
+}
@@ -0,0 +1,139 @@
+use core::convert::TryFrom;
+
+use crate::{TwoFloat, TwoFloatError};
+
+/// Iterator over the partial quotients of the continued fraction expansion
+/// of a `TwoFloat` value, created by `TwoFloat::continued_fraction`.
+#[derive(Debug, Clone)]
+pub struct ContinuedFraction {
+    numerator: TwoFloat,
+    denominator: TwoFloat,
+    remaining: usize,
+}
+
+impl Iterator for ContinuedFraction {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        if self.remaining == 0 || self.denominator == 0.0 {
+            return None;
+        }
+
+        // Each step is one stage of the Euclidean algorithm, which is exact
+        // while the remainders are representable
+        let remainder = self.numerator.rem_euclid(self.denominator);
+        let quotient = self.numerator.div_euclid(self.denominator);
+        match i64::try_from(quotient) {
+            Ok(term) if quotient.is_valid() && remainder.is_valid() => {
+                self.numerator = self.denominator;
+                self.denominator = remainder;
+                self.remaining -= 1;
+                Some(term)
+            }
+            _ => {
+                self.remaining = 0;
+                None
+            }
+        }
+    }
+}
+
+// Returns the distance between `value` and `p / q` multiplied by `q`.
+fn scaled_distance(value: TwoFloat, p: i128, q: u128) -> TwoFloat {
+    value.mul_add(TwoFloat::from(q), -TwoFloat::from(p)).abs()
+}
+
+impl TwoFloat {
+    /// Returns an iterator over at most `max_terms` partial quotients of
+    /// the continued fraction expansion of `self`. The first term is the
+    /// floor of the value, and all subsequent terms are positive. The
+    /// expansion ends early if the remainder becomes zero or if a term does
+    /// not fit in an `i64`.
+    ///
+    /// Each stage uses `rem_euclid`, which is exact whenever the remainder
+    /// is representable as a `TwoFloat`. The leading terms therefore
+    /// reflect the dyadic rational stored in `self` rather than the value it
+    /// was rounded from, but later terms may differ from those of the stored
+    /// value once a remainder has been rounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(-3.875);
+    /// let terms: Vec<i64> = a.continued_fraction(10).collect();
+    ///
+    /// assert_eq!(terms, [-4, 8]);
+    /// assert_eq!(TwoFloat::new_div(355.0, 113.0).continued_fraction(3).collect::<Vec<_>>(), [3, 7, 16]);
+    /// ```
+    pub fn continued_fraction(self, max_terms: usize) -> ContinuedFraction {
+        ContinuedFraction {
+            numerator: self,
+            denominator: Self::from(1.0),
+            remaining: if self.is_valid() { max_terms } else { 0 },
+        }
+    }
+
+    /// Returns the fraction `(numerator, denominator)` closest to `self`
+    /// with a denominator not greater than `max_denominator`, which is
+    /// treated as 1 if it is zero. If two fractions are equally close, the
+    /// one with the smaller denominator is returned.
+    ///
+    /// The search follows the terms of `continued_fraction`, so the result
+    /// is only guaranteed to be closest while those terms are exact. This
+    /// holds whenever `self` is an integer multiple of 2^-106, as is any
+    /// `f64` value with magnitude at least 2^-53, since every remainder is
+    /// then a multiple of 2^-106 below 1 and is representable.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not finite or if its floor does
+    /// not fit in an `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{consts::PI, TwoFloat};
+    /// assert_eq!(PI.best_rational(10).unwrap(), (22, 7));
+    /// assert_eq!(PI.best_rational(1000).unwrap(), (355, 113));
+    /// assert_eq!(TwoFloat::from(-0.3).best_rational(100).unwrap(), (-3, 10));
+    /// ```
+    pub fn best_rational(self, max_denominator: u64) -> Result<(i64, u64), TwoFloatError> {
+        let max_denominator = max_denominator.max(1) as u128;
+        let mut terms = self.continued_fraction(usize::MAX);
+        let first = terms.next().ok_or(TwoFloatError::ConversionError)?;
+
+        // Convergents h/k of the expansion, starting from 1/0 and a0/1
+        let (mut h_prev, mut k_prev) = (1i128, 0u128);
+        let (mut h, mut k) = (first as i128, 1u128);
+        for term in terms {
+            let term = term as u128;
+            let k_next = term * k + k_prev;
+            let h_next = term as i128 * h + h_prev;
+            if k_next <= max_denominator && i64::try_from(h_next).is_ok() {
+                h_prev = h;
+                k_prev = k;
+                h = h_next;
+                k = k_next;
+                continue;
+            }
+
+            // The best approximation is either the last convergent or the
+            // largest semiconvergent with an allowed denominator
+            let m = ((max_denominator - k_prev) / k).min(term - 1);
+            let h_semi = m as i128 * h + h_prev;
+            let k_semi = m * k + k_prev;
+            if m > 0 && i64::try_from(h_semi).is_ok() {
+                let semi_distance = scaled_distance(self, h_semi, k_semi) * Self::from(k);
+                let distance = scaled_distance(self, h, k) * Self::from(k_semi);
+                if semi_distance < distance {
+                    h = h_semi;
+                    k = k_semi;
+                }
+            }
+            break;
+        }
+
+        Ok((h as i64, k as u64))
+    }
+}
@@ -1,6 +1,6 @@
 use hexf::hexf64;
 
-use crate::{consts::LN_2, TwoFloat};
+use crate::{consts::LN_2, math_util::mul_pow2, TwoFloat};
 
 // 1/ln(2)
 const FRAC_1_LN_2: TwoFloat = TwoFloat {
@@ -24,82 +24,67 @@ const LN_FRAC_3_2: TwoFloat = TwoFloat {
 const EXP_UPPER_LIMIT: f64 = hexf64!("0x1.62e42fefa39efp9"); // ln(0x1.0p1024)
 const EXP_LOWER_LIMIT: f64 = hexf64!("-0x1.74385446d71c3p9"); // ln(0x1.0p-1074)
 
-// Coefficients for polynomial approximation of x*(exp(x)+1)/(exp(x)-1)
-const EXP_COEFFS: [TwoFloat; 6] = [
-    TwoFloat {
-        hi: hexf64!("0x1.5555555555555p-3"),
-        lo: hexf64!("0x1.32460411c87c6p-57"),
-    },
-    TwoFloat {
-        hi: hexf64!("-0x1.6c16c16c16af3p-9"),
-        lo: hexf64!("0x1.136a2c950fda6p-63"),
-    },
-    TwoFloat {
-        hi: hexf64!("0x1.1566abbf9f4f6p-14"),
-        lo: hexf64!("0x1.1110477bd284cp-68"),
-    },
-    TwoFloat {
-        hi: hexf64!("-0x1.bbd7768cb5288p-20"),
-        lo: hexf64!("0x1.fece87c086974p-76"),
-    },
-    TwoFloat {
-        hi: hexf64!("0x1.66a4e5c4c23f6p-25"),
-        lo: hexf64!("-0x1.c8771e6260e4bp-79"),
-    },
-    TwoFloat {
-        hi: hexf64!("-0x1.1f6dc1f8a9983p-30"),
-        lo: hexf64!("-0x1.d03ec3b33eaf5p-84"),
-    },
+// ln(2) split into parts whose products with the reduction multiple `k` of
+// `exp` are exact for |k| < 2^11
+const LN_2_PARTS: [f64; 3] = [
+    hexf64!("0x1.62e42fefa3800p-1"),
+    hexf64!("0x1.ef35793c76800p-45"),
+    hexf64!("-0x1.9ff0342542fc3p-90"),
 ];
 
+// ln(2)/32, the largest argument for which `EXP_M1_COEFFS` is used directly
+const EXP_M1_SERIES_LIMIT: f64 = hexf64!("0x1.62e42fefa39efp-6");
+
+// Taylor series coefficients of (exp(x)-1)/x - 1, truncated where the
+// remaining terms are below double-double precision on [-ln(2)/32, ln(2)/32]
 const EXP_M1_COEFFS: [TwoFloat; 12] = [
     TwoFloat {
         hi: hexf64!("0x1.0p-1"),
-        lo: hexf64!("0x1.bd730351a9755p-56"),
+        lo: 0.0,
     },
     TwoFloat {
-        hi: hexf64!("0x1.5555555555553p-3"),
-        lo: hexf64!("-0x1.7597a71b9af89p-57"),
+        hi: hexf64!("0x1.5555555555555p-3"),
+        lo: hexf64!("0x1.5555555555555p-57"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.55555555553f5p-5"),
-        lo: hexf64!("-0x1.ccd976a7f775cp-59"),
+        hi: hexf64!("0x1.5555555555555p-5"),
+        lo: hexf64!("0x1.5555555555555p-59"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.11111111115c4p-7"),
-        lo: hexf64!("0x1.342b20ac16f97p-61"),
+        hi: hexf64!("0x1.1111111111111p-7"),
+        lo: hexf64!("0x1.1111111111111p-63"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.6c16c16c6709ep-10"),
-        lo: hexf64!("-0x1.3ce71843eff0cp-64"),
+        hi: hexf64!("0x1.6c16c16c16c17p-10"),
+        lo: hexf64!("-0x1.f49f49f49f49fp-65"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.a01a01a0b696cp-13"),
-        lo: hexf64!("0x1.d41bdeddcef57p-71"),
+        hi: hexf64!("0x1.a01a01a01a01ap-13"),
+        lo: hexf64!("0x1.a01a01a01a01ap-73"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.a01a00aeb2858p-16"),
-        lo: hexf64!("0x1.7b3bc0a8a9fafp-70"),
+        hi: hexf64!("0x1.a01a01a01a01ap-16"),
+        lo: hexf64!("0x1.a01a01a01a01ap-76"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.71de32b050a9dp-19"),
-        lo: hexf64!("-0x1.9be0c6cec6271p-77"),
+        hi: hexf64!("0x1.71de3a556c734p-19"),
+        lo: hexf64!("-0x1.c154f8ddc6c00p-73"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.27e62dc06cd67p-22"),
-        lo: hexf64!("-0x1.41f2a2a0cba43p-77"),
+        hi: hexf64!("0x1.27e4fb7789f5cp-22"),
+        lo: hexf64!("0x1.cbbc05b4fa99ap-76"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.ae852d1420eefp-26"),
-        lo: hexf64!("-0x1.669f123719ab2p-81"),
+        hi: hexf64!("0x1.ae64567f544e4p-26"),
+        lo: hexf64!("-0x1.c062e06d1f209p-80"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.1e22aadda1973p-29"),
-        lo: hexf64!("-0x1.83b25ef3d0968p-85"),
+        hi: hexf64!("0x1.1eed8eff8d898p-29"),
+        lo: hexf64!("-0x1.2aec959e14c06p-83"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.36ab6f77c95d8p-33"),
-        lo: hexf64!("0x1.c16dc2dc455f1p-89"),
+        hi: hexf64!("0x1.6124613a86d09p-33"),
+        lo: hexf64!("0x1.f28e0cc748ebep-87"),
     },
 ];
 
@@ -163,20 +148,23 @@ const EXP2_COEFFS: [TwoFloat; 14] = [
     },
 ];
 
-fn mul_pow2(mut x: f64, mut y: i32) -> f64 {
-    loop {
-        if y < -1074 {
-            x *= hexf64!("0x1.0p-1074");
-            y += 1074;
-        } else if y < -1022 {
-            return x * f64::from_bits(1u64 << (y + 1074));
-        } else if y < 1024 {
-            return x * f64::from_bits(((y + 1023) as u64) << 52);
-        } else {
-            x *= hexf64!("0x1.0p1023");
-            y -= 1023;
-        }
+// Evaluates exp(x)-1 for |x| <= ln(2) by halving the argument until the
+// Taylor series converges quickly, then applying the doubling formula
+// exp(2x)-1 = (exp(x)-1)*(exp(x)+1), which preserves the relative accuracy
+// of small results.
+fn exp_m1_kernel(x: TwoFloat) -> TwoFloat {
+    let mut t = x;
+    let mut halvings = 0;
+    while t.hi.abs() > EXP_M1_SERIES_LIMIT {
+        t *= 0.5;
+        halvings += 1;
     }
+
+    let mut result = t * polynomial!(t, 1.0, EXP_M1_COEFFS);
+    for _ in 0..halvings {
+        result = result * (result + 2.0);
+    }
+    result
 }
 
 impl TwoFloat {
@@ -206,24 +194,9 @@ impl TwoFloat {
             // reduce value to range |r| <= ln(2)/2
             // where self = k*ln(2) + r
 
-            let k = ((FRAC_1_LN_2 * self).hi + 0.5).trunc();
-            let r = self - LN_2 * k;
-
-            // Now approximate the function
-            //
-            // R(r^2) = r*(exp(r)+1)/(exp(r)-1) = 2 + P1*r^2 + P2*r^4 + ...
-            //
-            // using a polynomial obtained by the Remez algorithm on the
-            // interval [0, ln(2)/2], then:
-            //
-            // exp(r) = 1 + 2*r/(R-r) = 1 + r + (r*R1) / (2-R1)
-            //
-            // where R1 = r - (P1*r^2 + P2*r^4 + ...)
-
-            let rr = r * r;
-            let r1 = r - rr * polynomial!(rr, EXP_COEFFS);
-
-            let exp_r = 1.0 - ((r * r1) / (r1 - 2.0) - r);
+            let k = (FRAC_1_LN_2 * self).hi.round();
+            let r = LN_2_PARTS.iter().fold(self, |r, &part| r - k * part);
+            let exp_r = exp_m1_kernel(r) + 1.0;
 
             // then scale back
 
@@ -255,8 +228,7 @@ impl TwoFloat {
         if self < -LN_2 || self > LN_FRAC_3_2 {
             self.exp() - 1.0
         } else {
-            let r = polynomial!(self, 1.0, EXP_M1_COEFFS);
-            self * r
+            exp_m1_kernel(self)
         }
     }
 
@@ -296,14 +268,13 @@ impl TwoFloat {
 
     /// Returns the natural logarithm of the value.
     ///
-    /// Uses Newton–Raphson iteration which depends on the `exp` function, so
-    /// may not be fully accurate to the full precision of a `TwoFloat`.
+    /// Uses Newton–Raphson iteration on the `exp` function.
     ///
     /// # Example
     ///
     /// ```
     /// let a = twofloat::consts::E.ln();
-    /// assert!((a - 1.0).abs() < 1e-11);
+    /// assert!((a - 1.0).abs() < 1e-30);
     /// ```
     pub fn ln(self) -> Self {
         if self == 1.0 {
@@ -319,8 +290,7 @@ impl TwoFloat {
 
     /// Returns the natural logarithm of `1 + self`.
     ///
-    /// Uses Newton–Raphson iteration which depends on the `expm1` function,
-    /// so may not be fully accurate to the full precision of a `TwoFloat`.
+    /// Uses Newton–Raphson iteration on the `exp_m1` function.
     ///
     /// # Example
     ///
@@ -1,4 +1,11 @@
-use crate::{arithmetic::fast_two_sum, math_util::mathfn, TwoFloat};
+use core::convert::TryFrom;
+
+use crate::{arithmetic::fast_two_sum, math_util::mathfn, RoundingMode, TwoFloat, TwoFloatError};
+
+// Checks if an integer-valued `TwoFloat` is odd.
+fn is_odd(value: TwoFloat) -> bool {
+    (mathfn::fract(value.hi * 0.5) != 0.0) != (mathfn::fract(value.lo * 0.5) != 0.0)
+}
 
 impl TwoFloat {
     /// Returns the fractional part of the number.
@@ -144,6 +151,105 @@ impl TwoFloat {
             mathfn::round(self.hi).into()
         }
     }
+
+    /// Returns the nearest integer to the value. Round half-way cases to the
+    /// nearest even integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(2.5).round_ties_even();
+    /// let b = TwoFloat::from(-3.5).round_ties_even();
+    /// let c = TwoFloat::new_add(2.5, 1e-200).round_ties_even();
+    ///
+    /// assert_eq!(a, TwoFloat::from(2.0));
+    /// assert_eq!(b, TwoFloat::from(-4.0));
+    /// assert_eq!(c, TwoFloat::from(3.0));
+    /// ```
+    pub fn round_ties_even(self) -> Self {
+        let rounded = self.round();
+        if self.fract().abs() == 0.5 && is_odd(rounded) {
+            if self.is_sign_positive() {
+                rounded - 1.0
+            } else {
+                rounded + 1.0
+            }
+        } else {
+            rounded
+        }
+    }
+
+    /// Rounds the value to an integer using the specified rounding mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{RoundingMode, TwoFloat};
+    /// let a = TwoFloat::new_add(-2.5, 1e-200);
+    ///
+    /// assert_eq!(a.rint(RoundingMode::NearestEven), TwoFloat::from(-2.0));
+    /// assert_eq!(a.rint(RoundingMode::TowardZero), TwoFloat::from(-2.0));
+    /// assert_eq!(a.rint(RoundingMode::TowardNegative), TwoFloat::from(-3.0));
+    /// ```
+    pub fn rint(self, mode: RoundingMode) -> Self {
+        match mode {
+            RoundingMode::NearestEven => self.round_ties_even(),
+            RoundingMode::NearestAway => self.round(),
+            RoundingMode::TowardZero => self.trunc(),
+            RoundingMode::TowardPositive => self.ceil(),
+            RoundingMode::TowardNegative => self.floor(),
+        }
+    }
+
+    /// Returns the integral and fractional parts of the value, both with the
+    /// same sign as `self`. The sum of the two parts is exactly `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let (a, b) = TwoFloat::new_add(-3.0, -1e-200).modf();
+    ///
+    /// assert_eq!(a, TwoFloat::from(-3.0));
+    /// assert_eq!(b, TwoFloat::from(-1e-200));
+    /// ```
+    pub fn modf(self) -> (Self, Self) {
+        (self.trunc(), self.fract())
+    }
+
+    /// Rounds the value to an integer using the specified rounding mode and
+    /// converts it to an `i64`, returning an error if it is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{RoundingMode, TwoFloat};
+    /// let a = TwoFloat::new_add(1e15, 0.5);
+    ///
+    /// assert_eq!(a.to_i64_rounded(RoundingMode::NearestEven).unwrap(), 1_000_000_000_000_000);
+    /// assert_eq!(a.to_i64_rounded(RoundingMode::NearestAway).unwrap(), 1_000_000_000_000_001);
+    /// assert!(TwoFloat::from(1e20).to_i64_rounded(RoundingMode::TowardZero).is_err());
+    /// ```
+    pub fn to_i64_rounded(self, mode: RoundingMode) -> Result<i64, TwoFloatError> {
+        i64::try_from(self.rint(mode))
+    }
+
+    /// Rounds the value to an integer using the specified rounding mode and
+    /// converts it to an `i128`, returning an error if it is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{RoundingMode, TwoFloat};
+    /// let a = TwoFloat::new_add(1e20, -0.25);
+    ///
+    /// assert_eq!(a.to_i128_rounded(RoundingMode::TowardPositive).unwrap(), 100_000_000_000_000_000_000);
+    /// assert_eq!(a.to_i128_rounded(RoundingMode::TowardNegative).unwrap(), 99_999_999_999_999_999_999);
+    /// ```
+    pub fn to_i128_rounded(self, mode: RoundingMode) -> Result<i128, TwoFloatError> {
+        i128::try_from(self.rint(mode))
+    }
 }
 
 #[cfg(test)]
@@ -308,4 +414,35 @@ mod tests {
             TwoFloat::new_add(-EXP2_60, -2.0)
         );
     }
+
+    #[test]
+    fn round_ties_even_test() {
+        assert_eq!(0.0, TwoFloat::from(0.5).round_ties_even());
+        assert_eq!(2.0, TwoFloat::from(1.5).round_ties_even());
+        assert_eq!(2.0, TwoFloat::from(2.5).round_ties_even());
+        assert_eq!(-0.0, TwoFloat::from(-0.5).round_ties_even());
+        assert_eq!(-2.0, TwoFloat::from(-2.5).round_ties_even());
+
+        assert_eq!(TwoFloat::new_add(2.5, 1e-200).round_ties_even(), 3.0);
+        assert_eq!(TwoFloat::new_add(2.5, -1e-200).round_ties_even(), 2.0);
+        assert_eq!(TwoFloat::new_add(-2.5, 1e-200).round_ties_even(), -2.0);
+        assert_eq!(TwoFloat::new_add(-2.5, -1e-200).round_ties_even(), -3.0);
+
+        assert_eq!(
+            TwoFloat::new_add(EXP2_60, 0.5).round_ties_even(),
+            TwoFloat::from(EXP2_60)
+        );
+        assert_eq!(
+            TwoFloat::new_add(EXP2_60, 1.5).round_ties_even(),
+            TwoFloat::new_add(EXP2_60, 2.0)
+        );
+        assert_eq!(
+            TwoFloat::new_add(-EXP2_60, -2.5).round_ties_even(),
+            TwoFloat::new_add(-EXP2_60, -2.0)
+        );
+        assert_eq!(
+            TwoFloat::new_add(-EXP2_60, 3.5).round_ties_even(),
+            TwoFloat::new_add(-EXP2_60, 4.0)
+        );
+    }
 }
@@ -1,10 +1,98 @@
-use crate::TwoFloat;
+use hexf::hexf64;
+
+use crate::{consts::LN_2, TwoFloat};
+
+// Coefficients of the Taylor series of sinh(x)/x - 1 in powers of x^2
+const SINH_COEFFS: [TwoFloat; 12] = [
+    TwoFloat {
+        hi: hexf64!("0x1.5555555555555p-3"),
+        lo: hexf64!("0x1.5555555555555p-57"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.1111111111111p-7"),
+        lo: hexf64!("0x1.1111111111111p-63"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.a01a01a01a01ap-13"),
+        lo: hexf64!("0x1.a01a01a01a01ap-73"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.71de3a556c734p-19"),
+        lo: hexf64!("-0x1.c154f8ddc6c00p-73"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.ae64567f544e4p-26"),
+        lo: hexf64!("-0x1.c062e06d1f209p-80"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.6124613a86d09p-33"),
+        lo: hexf64!("0x1.f28e0cc748ebep-87"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.ae7f3e733b81fp-41"),
+        lo: hexf64!("0x1.1d8656b0ee8cbp-97"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.952c77030ad4ap-49"),
+        lo: hexf64!("0x1.ac981465ddc6cp-103"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.2f49b46814157p-57"),
+        lo: hexf64!("0x1.2650f61dbdcb4p-112"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.71b8ef6dcf572p-66"),
+        lo: hexf64!("-0x1.d043ae40c4647p-120"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.761b41316381ap-75"),
+        lo: hexf64!("-0x1.3423c7d91404fp-130"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.3f3ccdd165fa9p-84"),
+        lo: hexf64!("-0x1.58ddadf344487p-139"),
+    },
+];
+
+// Limit for evaluating sinh(x) using the Taylor series
+const SINH_SERIES_LIMIT: f64 = 0.5;
+
+// Value above which exp(-|x|) is negligible compared to exp(|x|)
+const EXP_NEGLIGIBLE_LIMIT: f64 = 40.0;
+
+// ln(0x1.0p1024), above which exp(x) overflows
+const EXP_UPPER_LIMIT: f64 = hexf64!("0x1.62e42fefa39efp9");
+
+// Values above which the constant 1 is negligible in x^2 + 1 and x^2 - 1
+const SQRT_NEGLIGIBLE_LIMIT: f64 = hexf64!("0x1.0p53");
+
+// Computes sinh(x) using the Taylor series, for |x| < SINH_SERIES_LIMIT.
+fn restricted_sinh(x: TwoFloat) -> TwoFloat {
+    let x2 = x * x;
+    x * polynomial!(x2, 1.0, SINH_COEFFS)
+}
+
+// Computes tanh(x) from the Taylor series of sinh(x), for
+// |x| < SINH_SERIES_LIMIT.
+fn restricted_tanh(x: TwoFloat) -> TwoFloat {
+    let s = restricted_sinh(x);
+    s / (1.0 + s * s).sqrt()
+}
+
+// Returns exp(x)/2 for large positive x, avoiding premature overflow.
+fn half_exp(x: TwoFloat) -> TwoFloat {
+    if x.hi < EXP_UPPER_LIMIT {
+        x.exp() * 0.5
+    } else {
+        (x - LN_2).exp()
+    }
+}
 
 impl TwoFloat {
     /// Hyperbolic cosine function.
     ///
-    /// This is a convenience method that computes the value by calling the
-    /// exponential function.
+    /// Uses a Taylor series for small arguments and a scaled exponential for
+    /// large arguments to avoid premature overflow.
     ///
     /// # Examples
     ///
@@ -17,13 +105,28 @@ impl TwoFloat {
     /// assert!((b - c).abs() < 1e-10);
     /// ```
     pub fn cosh(self) -> Self {
-        self.exp() / 2.0 + (-self).exp() / 2.0
+        if !self.is_valid() {
+            return self.abs();
+        }
+
+        let x = self.abs();
+        if x.hi >= EXP_NEGLIGIBLE_LIMIT {
+            half_exp(x)
+        } else if x.hi < 2.0 * SINH_SERIES_LIMIT {
+            // cosh(x) = 1 + 2 * sinh(x/2)^2
+            let s = restricted_sinh(x * 0.5);
+            1.0 + 2.0 * (s * s)
+        } else {
+            let e = x.exp();
+            0.5 * (e + e.recip())
+        }
     }
 
     /// Hyperbolic sine function.
     ///
-    /// This is a convenience method that computes the value by calling the
-    /// exponential function.
+    /// Uses a Taylor series for small arguments, `exp_m1` for intermediate
+    /// arguments and a scaled exponential for large arguments to avoid
+    /// premature overflow.
     ///
     /// # Examples
     ///
@@ -36,13 +139,33 @@ impl TwoFloat {
     /// assert!((b - c).abs() < 1e-10);
     /// ```
     pub fn sinh(self) -> Self {
-        self.exp() / 2.0 - (-self).exp() / 2.0
+        if !self.is_valid() {
+            return self;
+        }
+
+        let x = self.abs();
+        let result = if x.hi < SINH_SERIES_LIMIT {
+            restricted_sinh(x)
+        } else if x.hi < EXP_NEGLIGIBLE_LIMIT {
+            // sinh(x) = (t + t / (t + 1)) / 2 where t = exp(x) - 1
+            let t = x.exp_m1();
+            0.5 * (t + t / (t + 1.0))
+        } else {
+            half_exp(x)
+        };
+
+        if self.is_sign_positive() {
+            result
+        } else {
+            -result
+        }
     }
 
     /// Hyperbolic tangent function.
     ///
-    /// This is a convenience method that computes the value by calling the
-    /// exponential function.
+    /// Uses a Taylor series for small arguments and `exp_m1` for larger
+    /// arguments to avoid cancellation, and returns `±1` when the difference
+    /// from one is negligible.
     ///
     /// # Examples
     ///
@@ -55,15 +178,32 @@ impl TwoFloat {
     /// assert!((b - c).abs() < 1e-10);
     /// ```
     pub fn tanh(self) -> Self {
-        let e_plus = self.exp();
-        let e_minus = (-self).exp();
-        (e_plus - e_minus) / (e_plus + e_minus)
+        if self.hi.is_nan() || self.lo.is_nan() {
+            return Self::NAN;
+        }
+
+        let x = self.abs();
+        let result = if x.hi >= EXP_NEGLIGIBLE_LIMIT {
+            Self::from(1.0)
+        } else if x.hi >= SINH_SERIES_LIMIT {
+            // tanh(x) = 1 - 2 / (t + 2) where t = exp(2x) - 1
+            let t = (2.0 * x).exp_m1();
+            1.0 - 2.0 / (t + 2.0)
+        } else {
+            restricted_tanh(x)
+        };
+
+        if self.is_sign_positive() {
+            result
+        } else {
+            -result
+        }
     }
 
     /// Inverse hyperbolic cosine function.
     ///
-    /// This is a convenience method that computes the value by calling the
-    /// `sqrt` and `ln` functions.
+    /// Uses `ln_1p` for arguments close to one and avoids overflow of the
+    /// intermediate square for large arguments.
     ///
     /// # Examples
     ///
@@ -76,13 +216,26 @@ impl TwoFloat {
     /// assert!((b - c).abs() < 1e-10);
     /// ```
     pub fn acosh(self) -> Self {
-        (self + (self * self - 1.0).sqrt()).ln()
+        if self.hi.is_nan() || self.lo.is_nan() || self < 1.0 {
+            Self::NAN
+        } else if self == 1.0 {
+            Self::from(0.0)
+        } else if !self.is_valid() {
+            self
+        } else if self.hi >= SQRT_NEGLIGIBLE_LIMIT {
+            self.ln() + LN_2
+        } else if self.hi > 2.0 {
+            (2.0 * self - (self + (self * self - 1.0).sqrt()).recip()).ln()
+        } else {
+            let t = self - 1.0;
+            (t + (2.0 * t + t * t).sqrt()).ln_1p()
+        }
     }
 
     /// Inverse hyperbolic sine function.
     ///
-    /// This is a convenience method that computes the value by calling the
-    /// `sqrt` and `ln` functions.
+    /// Uses `ln_1p` refined with a Newton-Raphson step for small arguments
+    /// and avoids overflow of the intermediate square for large arguments.
     ///
     /// # Examples
     ///
@@ -95,13 +248,38 @@ impl TwoFloat {
     /// assert!((b - c).abs() < 1e-10);
     /// ```
     pub fn asinh(self) -> Self {
-        (self + (self * self + 1.0).sqrt()).ln()
+        if !self.is_valid() || self.hi == 0.0 {
+            return self;
+        }
+
+        let x = self.abs();
+        let result = if x.hi >= SQRT_NEGLIGIBLE_LIMIT {
+            x.ln() + LN_2
+        } else if x.hi > 2.0 {
+            (2.0 * x + ((x * x + 1.0).sqrt() + x).recip()).ln()
+        } else {
+            let x2 = x * x;
+            let y = (x + x2 / (1.0 + (1.0 + x2).sqrt())).ln_1p();
+            if x.hi < SINH_SERIES_LIMIT {
+                // refine using Newton-Raphson iteration on the Taylor series
+                let s = restricted_sinh(y);
+                y - (s - x) / (1.0 + s * s).sqrt()
+            } else {
+                y
+            }
+        };
+
+        if self.is_sign_positive() {
+            result
+        } else {
+            -result
+        }
     }
 
     /// Inverse hyperbolic tangent function.
     ///
-    /// This is a convenience method that computes the value by calling the
-    /// `ln` function.
+    /// Uses `ln_1p` refined with a Newton-Raphson step to avoid cancellation
+    /// for small arguments.
     ///
     /// # Examples
     ///
@@ -114,6 +292,33 @@ impl TwoFloat {
     /// assert!((b - c).abs() < 1e-10);
     /// ```
     pub fn atanh(self) -> Self {
-        ((1.0 + self) / (1.0 - self)).ln() / 2.0
+        let x = self.abs();
+        if !self.is_valid() || x > 1.0 {
+            return Self::NAN;
+        } else if self.hi == 0.0 {
+            return self;
+        }
+
+        let result = if x == 1.0 {
+            Self::INFINITY
+        } else if x < 0.5 {
+            let x2 = 2.0 * x;
+            let y = 0.5 * (x2 + x2 * x / (1.0 - x)).ln_1p();
+            if x.hi < 0.5 * SINH_SERIES_LIMIT {
+                // refine using Newton-Raphson iteration on the Taylor series
+                let t = restricted_tanh(y);
+                y - (t - x) / (1.0 - t * t)
+            } else {
+                y
+            }
+        } else {
+            0.5 * (2.0 * x / (1.0 - x)).ln_1p()
+        };
+
+        if self.is_sign_positive() {
+            result
+        } else {
+            -result
+        }
     }
 }
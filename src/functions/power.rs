@@ -1,4 +1,14 @@
-use crate::TwoFloat;
+use hexf::hexf64;
+
+use crate::{
+    arithmetic::{expansion_sign, grow_expansion, grow_product, refine_directed},
+    TwoFloat,
+};
+
+// Inputs outside this range are rescaled so that the residual in the
+// directed square root does not underflow or overflow
+const SQRT_SCALE_LOWER: f64 = hexf64!("0x1.0p-900");
+const SQRT_SCALE_UPPER: f64 = hexf64!("0x1.0p900");
 
 impl TwoFloat {
     /// Returns the square root of the number, using equation 4 from Karp &
@@ -25,6 +35,74 @@ impl TwoFloat {
         }
     }
 
+    fn sqrt_directed(self, up: bool) -> Self {
+        if !self.is_valid() || self.hi <= 0.0 {
+            return self.sqrt();
+        }
+
+        let half_scale = if self.hi < SQRT_SCALE_LOWER {
+            500
+        } else if self.hi > SQRT_SCALE_UPPER {
+            -256
+        } else {
+            0
+        };
+        let a = self.ldexp(2 * half_scale);
+        let a_slack = if half_scale < 0 {
+            hexf64!("0x1.0p-1074")
+        } else {
+            0.0
+        };
+
+        let direction = if up { -1.0 } else { 1.0 };
+        let result = refine_directed(a.sqrt(), up, |s| {
+            // Sign of a - s^2, which has the sign of sqrt(a) - s
+            let mut residual = [0.0; 12];
+            let mut len = grow_expansion(&mut residual, 0, a.lo);
+            len = grow_expansion(&mut residual, len, a.hi);
+            let mut slack = a_slack;
+            for &(x, y) in &[(s.lo, s.lo), (s.lo, s.hi), (s.lo, s.hi), (s.hi, s.hi)] {
+                len = grow_product(&mut residual, len, -x, y, &mut slack);
+            }
+            len = grow_expansion(&mut residual, len, -direction * slack);
+            expansion_sign(&residual[..len]) == -direction
+        });
+        result.ldexp(-half_scale)
+    }
+
+    /// Returns the square root of the number, rounded upwards so that it is
+    /// guaranteed to be greater than or equal to the exact square root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(2.0);
+    /// let b = a.sqrt_up();
+    ///
+    /// assert!(b * b >= a);
+    /// assert_eq!(TwoFloat::from(9.0).sqrt_up(), TwoFloat::from(3.0));
+    /// ```
+    pub fn sqrt_up(self) -> Self {
+        self.sqrt_directed(true)
+    }
+
+    /// Returns the square root of the number, rounded downwards so that it
+    /// is guaranteed to be less than or equal to the exact square root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(2.0);
+    ///
+    /// assert!(a.sqrt_down() < a.sqrt_up());
+    /// assert_eq!(TwoFloat::from(9.0).sqrt_down(), TwoFloat::from(3.0));
+    /// ```
+    pub fn sqrt_down(self) -> Self {
+        self.sqrt_directed(false)
+    }
+
     /// Returns the cube root of the number, using Newton-Raphson iteration.
     ///
     /// # Examples
@@ -7,127 +7,117 @@ use crate::{
     TwoFloat,
 };
 
-// Polynomial coefficients of sin(x)-x on [0,pi/4]
-const SIN_COEFFS: [TwoFloat; 7] = [
+// Taylor series coefficients of sin(x)-x, truncated where the remaining
+// terms are below double-double precision on [0,pi/4]
+const SIN_COEFFS: [TwoFloat; 13] = [
     TwoFloat {
         hi: hexf64!("-0x1.5555555555555p-3"),
-        lo: hexf64!("-0x1.3a26e9901c14ap-57"),
+        lo: hexf64!("-0x1.5555555555555p-57"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.1111111111105p-7"),
-        lo: hexf64!("-0x1.487cfb2f402fap-63"),
+        hi: hexf64!("0x1.1111111111111p-7"),
+        lo: hexf64!("0x1.1111111111111p-63"),
     },
     TwoFloat {
-        hi: hexf64!("-0x1.a01a01a017e07p-13"),
-        lo: hexf64!("-0x1.22340ff667d3fp-67"),
+        hi: hexf64!("-0x1.a01a01a01a01ap-13"),
+        lo: hexf64!("-0x1.a01a01a01a01ap-73"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.71de3a526314fp-19"),
-        lo: hexf64!("0x1.1ddd0a161cfa7p-75"),
+        hi: hexf64!("0x1.71de3a556c734p-19"),
+        lo: hexf64!("-0x1.c154f8ddc6c00p-73"),
     },
     TwoFloat {
-        hi: hexf64!("-0x1.ae6451ad6a8ebp-26"),
-        lo: hexf64!("0x1.cb014c3ddfd85p-84"),
+        hi: hexf64!("-0x1.ae64567f544e4p-26"),
+        lo: hexf64!("0x1.c062e06d1f209p-80"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.612010f363e7dp-33"),
-        lo: hexf64!("0x1.0dba7b1b83a01p-88"),
+        hi: hexf64!("0x1.6124613a86d09p-33"),
+        lo: hexf64!("0x1.f28e0cc748ebep-87"),
     },
     TwoFloat {
-        hi: hexf64!("-0x1.aa6c431516f76p-41"),
-        lo: hexf64!("0x1.df71e9b9b179bp-95"),
+        hi: hexf64!("-0x1.ae7f3e733b81fp-41"),
+        lo: hexf64!("-0x1.1d8656b0ee8cbp-97"),
     },
-];
-
-// Polynomial coefficients of cos(x)-1+x^2/2 on [0,pi/4]
-const COS_COEFFS: [TwoFloat; 7] = [
     TwoFloat {
-        hi: hexf64!("0x1.5555555555555p-5"),
-        lo: hexf64!("0x1.4b27f9ddea57ap-59"),
+        hi: hexf64!("0x1.952c77030ad4ap-49"),
+        lo: hexf64!("0x1.ac981465ddc6cp-103"),
     },
     TwoFloat {
-        hi: hexf64!("-0x1.6c16c16c16c0fp-10"),
-        lo: hexf64!("0x1.1e7208a68629bp-64"),
+        hi: hexf64!("-0x1.2f49b46814157p-57"),
+        lo: hexf64!("-0x1.2650f61dbdcb4p-112"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.a01a01a018bcdp-16"),
-        lo: hexf64!("0x1.adea7f883a49cp-71"),
+        hi: hexf64!("0x1.71b8ef6dcf572p-66"),
+        lo: hexf64!("-0x1.d043ae40c4647p-120"),
     },
     TwoFloat {
-        hi: hexf64!("-0x1.27e4fb75e002ap-22"),
-        lo: hexf64!("-0x1.a26582a390382p-76"),
+        hi: hexf64!("-0x1.761b41316381ap-75"),
+        lo: hexf64!("0x1.3423c7d91404fp-130"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.1eed8c87a5a51p-29"),
-        lo: hexf64!("-0x1.551d13b8d9c61p-85"),
+        hi: hexf64!("0x1.3f3ccdd165fa9p-84"),
+        lo: hexf64!("-0x1.58ddadf344487p-139"),
     },
     TwoFloat {
-        hi: hexf64!("-0x1.93931ca96bc22p-37"),
-        lo: hexf64!("0x1.25124fcc17b3fp-91"),
-    },
-    TwoFloat {
-        hi: hexf64!("0x1.aabaa8059719cp-45"),
-        lo: hexf64!("0x1.4cf2f15ef56d1p-99"),
+        hi: hexf64!("-0x1.d1ab1c2dccea3p-94"),
+        lo: hexf64!("-0x1.054d0c78aea14p-149"),
     },
 ];
 
-// Polynomial coefficients of tan(x)-x on [0,pi/4]
-const TAN_COEFFS: [TwoFloat; 14] = [
-    TwoFloat {
-        hi: hexf64!("0x1.555555555530fp-2"),
-        lo: hexf64!("-0x1.38ef22c4b8238p-56"),
-    },
+// Taylor series coefficients of cos(x)-1+x^2/2, truncated where the
+// remaining terms are below double-double precision on [0,pi/4]
+const COS_COEFFS: [TwoFloat; 13] = [
     TwoFloat {
-        hi: hexf64!("0x1.111111112c40ap-3"),
-        lo: hexf64!("0x1.db464d0cd9cb4p-57"),
+        hi: hexf64!("0x1.5555555555555p-5"),
+        lo: hexf64!("0x1.5555555555555p-59"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.ba1ba1a984e9fp-5"),
-        lo: hexf64!("0x1.b2454b6b23d17p-61"),
+        hi: hexf64!("-0x1.6c16c16c16c17p-10"),
+        lo: hexf64!("0x1.f49f49f49f49fp-65"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.664f4b43a4fefp-6"),
-        lo: hexf64!("0x1.bb1ac07d3ba2fp-61"),
+        hi: hexf64!("0x1.a01a01a01a01ap-16"),
+        lo: hexf64!("0x1.a01a01a01a01ap-76"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.226ded039b30dp-7"),
-        lo: hexf64!("-0x1.9110570c2853ap-63"),
+        hi: hexf64!("-0x1.27e4fb7789f5cp-22"),
+        lo: hexf64!("-0x1.cbbc05b4fa99ap-76"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.d6ddaf4100a51p-9"),
-        lo: hexf64!("-0x1.654e37a706894p-65"),
+        hi: hexf64!("0x1.1eed8eff8d898p-29"),
+        lo: hexf64!("-0x1.2aec959e14c06p-83"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.7d2be8d9e1761p-10"),
-        lo: hexf64!("-0x1.d33168adc5b21p-64"),
+        hi: hexf64!("-0x1.93974a8c07c9dp-37"),
+        lo: hexf64!("-0x1.05d6f8a2efd1fp-92"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.395c8b79b1e68p-11"),
-        lo: hexf64!("-0x1.5c77d3711fefdp-66"),
+        hi: hexf64!("0x1.ae7f3e733b81fp-45"),
+        lo: hexf64!("0x1.1d8656b0ee8cbp-101"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.c3c79cdabdf3ep-13"),
-        lo: hexf64!("0x1.7af98e21b704bp-69"),
+        hi: hexf64!("-0x1.6827863b97d97p-53"),
+        lo: hexf64!("-0x1.eec01221a8b0bp-107"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.399dadec87c3ap-13"),
-        lo: hexf64!("-0x1.793a97fd365d5p-68"),
+        hi: hexf64!("0x1.e542ba4020225p-62"),
+        lo: hexf64!("0x1.ea72b4afe3c2fp-120"),
     },
     TwoFloat {
-        hi: hexf64!("-0x1.6a82ab57290c9p-15"),
-        lo: hexf64!("0x1.48e1069bffaafp-73"),
+        hi: hexf64!("-0x1.0ce396db7f853p-70"),
+        lo: hexf64!("0x1.aebcdbd20331cp-124"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.b3221d6d8c4b6p-14"),
-        lo: hexf64!("-0x1.049e004213205p-69"),
+        hi: hexf64!("0x1.f2cf01972f578p-80"),
+        lo: hexf64!("-0x1.9ada5fcc1ab14p-135"),
     },
     TwoFloat {
-        hi: hexf64!("-0x1.b4a2a3d0229eap-15"),
-        lo: hexf64!("0x1.cb2115b70d6e3p-69"),
+        hi: hexf64!("-0x1.88e85fc6a4e5ap-89"),
+        lo: hexf64!("0x1.71c37ebd16540p-143"),
     },
     TwoFloat {
-        hi: hexf64!("0x1.7917a05c89f91p-16"),
-        lo: hexf64!("-0x1.9ff84f5cc7024p-70"),
+        hi: hexf64!("0x1.0a18a2635085dp-98"),
+        lo: hexf64!("0x1.b9e2e28e1aa54p-153"),
     },
 ];
 
@@ -249,6 +239,12 @@ const ATAN_FRAC_3_2: TwoFloat = TwoFloat {
     lo: hexf64!("0x1.007887af0cbbdp-56"),
 };
 
+// cos(pi/6) = sqrt(3)/2
+const COS_FRAC_PI_6: TwoFloat = TwoFloat {
+    hi: hexf64!("0x1.bb67ae8584caap-1"),
+    lo: hexf64!("0x1.cec95d0b5c1e3p-55"),
+};
+
 fn quadrant(value: TwoFloat) -> (TwoFloat, i8) {
     if value.abs() < FRAC_PI_4 {
         (value, 0)
@@ -263,6 +259,36 @@ fn quadrant(value: TwoFloat) -> (TwoFloat, i8) {
     }
 }
 
+// Reduces a value measured in half-turns to the range [-1/4, 1/4] together
+// with the number of quarter turns (modulo 4) that were removed. The
+// reduction is exact.
+fn quadrant_pi(value: TwoFloat) -> (TwoFloat, i8) {
+    let y = TwoFloat::new_add(value.hi % 2.0, value.lo % 2.0);
+    let n = (2.0 * y.hi).round();
+    let r = TwoFloat::new_add(y.hi - 0.5 * n, y.lo);
+    (r, (n as i8).rem_euclid(4))
+}
+
+// Reduces a value measured in degrees to the range [-45, 45] together with
+// the number of quarter turns (modulo 4) that were removed. The reduction is
+// exact.
+fn quadrant_deg(value: TwoFloat) -> (TwoFloat, i8) {
+    let y = TwoFloat::new_add(value.hi % 360.0, value.lo % 360.0);
+    let n = (y.hi / 90.0).round();
+    let r = TwoFloat::new_add(y.hi - 90.0 * n, y.lo);
+    (r, (n as i8).rem_euclid(4))
+}
+
+// Gives zero results the sign of the argument, so that the sine of a
+// positive multiple of a half-turn is `+0.0` as for IEEE 754 `sinPi`.
+fn signed_zero(result: TwoFloat, argument: TwoFloat) -> TwoFloat {
+    if result.hi == 0.0 {
+        TwoFloat::from(0.0).copysign(&argument)
+    } else {
+        result
+    }
+}
+
 fn restricted_sin(x: TwoFloat) -> TwoFloat {
     let x2 = x * x;
     x * polynomial!(x2, 1.0, SIN_COEFFS)
@@ -274,8 +300,7 @@ fn restricted_cos(x: TwoFloat) -> TwoFloat {
 }
 
 fn restricted_tan(x: TwoFloat) -> TwoFloat {
-    let x2 = x * x;
-    x * polynomial!(x2, 1.0, TAN_COEFFS)
+    restricted_sin(x) / restricted_cos(x)
 }
 
 fn restricted_asin(x: TwoFloat) -> TwoFloat {
@@ -288,6 +313,45 @@ fn restricted_atan(x: TwoFloat) -> TwoFloat {
     x * polynomial!(x2, 1.0, ATAN_COEFFS)
 }
 
+// Reduces a value in [-45, 45] degrees by the nearest multiple of 30
+// degrees, returning the sine and cosine of the remainder together with the
+// sine and cosine of the multiple. The sines and cosines of the remainder
+// are exact at zero, so the results at multiples of 30 degrees are exact
+// where they are rational.
+fn sin_cos_deg_parts(x: TwoFloat) -> ((TwoFloat, TwoFloat), (TwoFloat, TwoFloat)) {
+    let n = (x.hi / 30.0).round().clamp(-1.0, 1.0);
+    let r = PI * ((x - 30.0 * n) / 180.0);
+    let (s, c) = (restricted_sin(r), restricted_cos(r));
+    let multiple = if n == 0.0 {
+        (TwoFloat::from(0.0), TwoFloat::from(1.0))
+    } else {
+        (TwoFloat::from(0.5 * n), COS_FRAC_PI_6)
+    };
+    ((s, c), multiple)
+}
+
+fn restricted_sin_deg(x: TwoFloat) -> TwoFloat {
+    let ((s, c), (sn, cn)) = sin_cos_deg_parts(x);
+    if sn == 0.0 {
+        s
+    } else {
+        sn * c + cn * s
+    }
+}
+
+fn restricted_cos_deg(x: TwoFloat) -> TwoFloat {
+    let ((s, c), (sn, cn)) = sin_cos_deg_parts(x);
+    if sn == 0.0 {
+        c
+    } else {
+        cn * c - sn * s
+    }
+}
+
+fn restricted_tan_deg(x: TwoFloat) -> TwoFloat {
+    restricted_tan(PI * (x / 180.0))
+}
+
 impl TwoFloat {
     /// Computes the sine of the value (in radians).
     ///
@@ -532,9 +596,339 @@ impl TwoFloat {
     }
 }
 
+impl TwoFloat {
+    /// Computes the sine of `π * self`. The argument reduction is exact, so
+    /// that integer and half-integer arguments give exact results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(0.5).sin_pi();
+    /// let b = TwoFloat::from(1e20).sin_pi();
+    ///
+    /// assert_eq!(a, 1.0);
+    /// assert_eq!(b, 0.0);
+    /// ```
+    pub fn sin_pi(self) -> Self {
+        if !self.is_valid() {
+            return Self::NAN;
+        }
+        let (x, quadrant) = quadrant_pi(self);
+        let result = match quadrant {
+            0 => restricted_sin(PI * x),
+            1 => restricted_cos(PI * x),
+            2 => -restricted_sin(PI * x),
+            _ => -restricted_cos(PI * x),
+        };
+        signed_zero(result, self)
+    }
+
+    /// Computes the cosine of `π * self`. The argument reduction is exact,
+    /// so that integer and half-integer arguments give exact results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(0.5).cos_pi();
+    /// let b = TwoFloat::from(-3.0).cos_pi();
+    ///
+    /// assert_eq!(a, 0.0);
+    /// assert_eq!(b, -1.0);
+    /// ```
+    pub fn cos_pi(self) -> Self {
+        if !self.is_valid() {
+            return Self::NAN;
+        }
+        let (x, quadrant) = quadrant_pi(self);
+        match quadrant {
+            0 => restricted_cos(PI * x),
+            1 => -restricted_sin(PI * x),
+            2 => -restricted_cos(PI * x),
+            _ => restricted_sin(PI * x),
+        }
+    }
+
+    /// Simultaneously computes the sine and cosine of `π * self`. Returns a
+    /// tuple with the sine as the first element and the cosine as the second
+    /// element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let (s, c) = TwoFloat::from(1.5).sin_cos_pi();
+    ///
+    /// assert_eq!(s, -1.0);
+    /// assert_eq!(c, 0.0);
+    /// ```
+    pub fn sin_cos_pi(self) -> (Self, Self) {
+        if !self.is_valid() {
+            return (Self::NAN, Self::NAN);
+        }
+        let (x, quadrant) = quadrant_pi(self);
+        let s = restricted_sin(PI * x);
+        let c = restricted_cos(PI * x);
+        let (s, c) = match quadrant {
+            0 => (s, c),
+            1 => (c, -s),
+            2 => (-s, -c),
+            _ => (-c, s),
+        };
+        (signed_zero(s, self), c)
+    }
+
+    /// Computes the tangent of `π * self`. The argument reduction is exact,
+    /// so that multiples of one quarter give exact results. Odd multiples of
+    /// one half return an infinite value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(0.25).tan_pi();
+    /// let b = TwoFloat::from(-1.0).tan_pi();
+    ///
+    /// assert_eq!(a, 1.0);
+    /// assert_eq!(b, 0.0);
+    /// ```
+    pub fn tan_pi(self) -> Self {
+        if !self.is_valid() {
+            return Self::NAN;
+        }
+        let (x, quadrant) = quadrant_pi(self);
+        let t = restricted_tan(PI * x);
+        match quadrant {
+            0 | 2 => t,
+            1 if t == 0.0 => Self::INFINITY,
+            3 if t == 0.0 => Self::NEG_INFINITY,
+            _ => -1.0 / t,
+        }
+    }
+
+    /// Computes the arcsine of the value divided by `π`. Return value is in
+    /// half-turns in the range [-1/2, 1/2] or an invalid value if the input
+    /// value is outside the range [-1, 1].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(-1.0).asin_pi();
+    /// let b = TwoFloat::from(0.5).asin_pi();
+    ///
+    /// assert_eq!(a, -0.5);
+    /// assert!((b - TwoFloat::from(1.0) / 6.0).abs() < 1e-15);
+    /// ```
+    pub fn asin_pi(self) -> Self {
+        if self == 1.0 {
+            Self::from(0.5)
+        } else if self == -1.0 {
+            Self::from(-0.5)
+        } else if self.hi == 0.0 {
+            self
+        } else {
+            self.asin() / PI
+        }
+    }
+
+    /// Computes the arccosine of the value divided by `π`. Return value is
+    /// in half-turns in the range [0, 1] or an invalid value if the input
+    /// value is outside the range [-1, 1].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(0.0).acos_pi();
+    /// let b = TwoFloat::from(-1.0).acos_pi();
+    ///
+    /// assert_eq!(a, 0.5);
+    /// assert_eq!(b, 1.0);
+    /// ```
+    pub fn acos_pi(self) -> Self {
+        if self == 1.0 {
+            Self::from(0.0)
+        } else if self == -1.0 {
+            Self::from(1.0)
+        } else if self.hi == 0.0 {
+            Self::from(0.5)
+        } else {
+            self.acos() / PI
+        }
+    }
+
+    /// Computes the arctangent of the value divided by `π`. Return value is
+    /// in half-turns in the range [-1/2, 1/2].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(1.0).atan_pi();
+    /// let b = TwoFloat::NEG_INFINITY.atan_pi();
+    ///
+    /// assert_eq!(a, 0.25);
+    /// assert_eq!(b, -0.5);
+    /// ```
+    pub fn atan_pi(self) -> Self {
+        if self.hi.is_infinite() {
+            if self.hi.is_sign_positive() {
+                Self::from(0.5)
+            } else {
+                Self::from(-0.5)
+            }
+        } else if self == 1.0 {
+            Self::from(0.25)
+        } else if self == -1.0 {
+            Self::from(-0.25)
+        } else if self.hi == 0.0 {
+            self
+        } else {
+            self.atan() / PI
+        }
+    }
+
+    /// Computes the four quadrant arctangent of `self` (y) and `other` (x)
+    /// divided by `π`. Return value is in half-turns in the range [-1, 1].
+    /// Signed zeros, infinities and NaN are handled as for `f64::atan2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let y = TwoFloat::from(-1.0);
+    /// let x = TwoFloat::from(-1.0);
+    /// let theta = TwoFloat::atan2_pi(y, x);
+    ///
+    /// assert_eq!(theta, -0.75);
+    /// ```
+    pub fn atan2_pi(self, other: Self) -> Self {
+        let quarter = |y_positive: bool, x_positive: bool| match (y_positive, x_positive) {
+            (true, true) => Self::from(0.25),
+            (true, false) => Self::from(0.75),
+            (false, true) => Self::from(-0.25),
+            (false, false) => Self::from(-0.75),
+        };
+        let axis = |turns: f64| Self::from(turns).copysign(&self);
+
+        if self.hi.is_nan() || other.hi.is_nan() {
+            Self::NAN
+        } else if self.hi == 0.0 {
+            if other.hi.is_sign_positive() {
+                axis(0.0)
+            } else {
+                axis(1.0)
+            }
+        } else if self.hi.is_infinite() {
+            if other.hi.is_infinite() {
+                quarter(self.hi > 0.0, other.hi > 0.0)
+            } else {
+                axis(0.5)
+            }
+        } else if other.hi.is_infinite() {
+            if other.hi > 0.0 {
+                axis(0.0)
+            } else {
+                axis(1.0)
+            }
+        } else if other.hi == 0.0 {
+            axis(0.5)
+        } else if self.abs() == other.abs() {
+            quarter(self.hi > 0.0, other.hi > 0.0)
+        } else {
+            self.atan2(other) / PI
+        }
+    }
+
+    /// Computes the sine of the value (in degrees). The argument reduction
+    /// is exact, so that multiples of 30 degrees have exact results where
+    /// the sine is rational.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(30.0).sind();
+    /// let b = TwoFloat::from(-270.0).sind();
+    ///
+    /// assert_eq!(a, 0.5);
+    /// assert_eq!(b, 1.0);
+    /// ```
+    pub fn sind(self) -> Self {
+        if !self.is_valid() {
+            return Self::NAN;
+        }
+        let (x, quadrant) = quadrant_deg(self);
+        let result = match quadrant {
+            0 => restricted_sin_deg(x),
+            1 => restricted_cos_deg(x),
+            2 => -restricted_sin_deg(x),
+            _ => -restricted_cos_deg(x),
+        };
+        signed_zero(result, self)
+    }
+
+    /// Computes the cosine of the value (in degrees). The argument reduction
+    /// is exact, so that multiples of 30 degrees have exact results where
+    /// the cosine is rational.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(60.0).cosd();
+    /// let b = TwoFloat::from(90.0).cosd();
+    ///
+    /// assert_eq!(a, 0.5);
+    /// assert_eq!(b, 0.0);
+    /// ```
+    pub fn cosd(self) -> Self {
+        if !self.is_valid() {
+            return Self::NAN;
+        }
+        let (x, quadrant) = quadrant_deg(self);
+        match quadrant {
+            0 => restricted_cos_deg(x),
+            1 => -restricted_sin_deg(x),
+            2 => -restricted_cos_deg(x),
+            _ => restricted_sin_deg(x),
+        }
+    }
+
+    /// Computes the tangent of the value (in degrees). The argument
+    /// reduction is exact, so that multiples of 45 degrees give exact
+    /// results. Odd multiples of 90 degrees return an infinite value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::TwoFloat;
+    /// let a = TwoFloat::from(45.0).tand();
+    /// let b = TwoFloat::from(135.0).tand();
+    ///
+    /// assert_eq!(a, 1.0);
+    /// assert_eq!(b, -1.0);
+    /// ```
+    pub fn tand(self) -> Self {
+        if !self.is_valid() {
+            return Self::NAN;
+        }
+        let (x, quadrant) = quadrant_deg(self);
+        let t = restricted_tan_deg(x);
+        match quadrant {
+            0 | 2 => t,
+            1 if t == 0.0 => Self::INFINITY,
+            3 if t == 0.0 => Self::NEG_INFINITY,
+            _ => -1.0 / t,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::quadrant;
+    use super::{quadrant, quadrant_deg, quadrant_pi};
     use crate::{
         consts::{FRAC_PI_2, FRAC_PI_4, PI},
         TwoFloat,
@@ -560,6 +954,47 @@ mod tests {
         assert_eq!(0, quadrant(TwoFloat::from(-6.0)).1);
     }
 
+    #[test]
+    fn quadrant_pi_test() {
+        assert_eq!(0, quadrant_pi(TwoFloat::from(0.2)).1);
+        assert_eq!(0, quadrant_pi(TwoFloat::from(-0.2)).1);
+
+        assert_eq!(1, quadrant_pi(TwoFloat::from(0.5)).1);
+        assert_eq!(3, quadrant_pi(TwoFloat::from(-0.5)).1);
+
+        assert_eq!(2, quadrant_pi(TwoFloat::from(1.0)).1);
+        assert_eq!(2, quadrant_pi(TwoFloat::from(-1.0)).1);
+
+        assert_eq!(3, quadrant_pi(TwoFloat::from(1.5)).1);
+        assert_eq!(1, quadrant_pi(TwoFloat::from(-1.5)).1);
+
+        assert_eq!(0, quadrant_pi(TwoFloat::from(2.1)).1);
+        assert_eq!(0, quadrant_pi(TwoFloat::from(-2.1)).1);
+
+        let (x, q) = quadrant_pi(TwoFloat::new_add(1e20, 0.5 + 1e-10));
+        assert_eq!(1, q);
+        assert_eq!(x, (0.5 + 1e-10) - 0.5);
+    }
+
+    #[test]
+    fn quadrant_deg_test() {
+        assert_eq!(0, quadrant_deg(TwoFloat::from(30.0)).1);
+        assert_eq!(0, quadrant_deg(TwoFloat::from(-30.0)).1);
+
+        assert_eq!(1, quadrant_deg(TwoFloat::from(100.0)).1);
+        assert_eq!(3, quadrant_deg(TwoFloat::from(-100.0)).1);
+
+        assert_eq!(2, quadrant_deg(TwoFloat::from(180.0)).1);
+        assert_eq!(2, quadrant_deg(TwoFloat::from(-180.0)).1);
+
+        assert_eq!(3, quadrant_deg(TwoFloat::from(300.0)).1);
+        assert_eq!(1, quadrant_deg(TwoFloat::from(-300.0)).1);
+
+        let (x, q) = quadrant_deg(TwoFloat::new_add(360.0 * 1e15, 390.0));
+        assert_eq!(0, q);
+        assert_eq!(x, 30.0);
+    }
+
     #[test]
     fn sin_test() {
         assert_eq!(0.0, TwoFloat::from(0.0).sin());
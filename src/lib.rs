@@ -33,7 +33,35 @@ Operations on non-finite values are not supported. At the moment this is not
 automatically checked. The `is_valid()` method is provided for this purpose.
 
 If the `serde` feature is enabled, serialization and deserialization is
-possible through the Serde library.
+possible through the Serde library. The `twofloat::serde` module provides
+alternative representations, such as decimal strings, for use with
+`#[serde(with = "...")]`.
+
+If the `approx` feature is enabled, `TwoFloat` implements the `AbsDiffEq`,
+`RelativeEq` and `UlpsEq` traits from the `approx` crate. Ulps are measured
+with a 106-bit significand.
+
+If the `bytemuck` feature is enabled, `TwoFloat` implements the
+`bytemuck::Pod` and `bytemuck::Zeroable` traits, allowing slices of values
+to be cast to and from bytes. Values read in this way are not checked, so
+`is_valid()` should be used on untrusted data.
+
+If the `rand` feature is enabled, random values can be generated using the
+`rand` crate, including uniform samples over ranges and samples from the
+standard normal distribution.
+
+If the `nalgebra` feature is enabled, `TwoFloat` implements the
+`RealField` and `ComplexField` traits from the `simba` crate, so it can be
+used as the scalar type for `nalgebra` matrices and decompositions. This
+feature also enables the `math_funcs` and `approx` features.
+
+If the `ndarray` feature is enabled, `TwoFloat` can be used as a scalar
+operand for `ndarray` arrays, and the [`array`] module provides elementwise
+functions and accurate summation of `f64` arrays.
+
+If the `num_rational` feature is enabled, exact conversions to and
+correctly rounded conversions from `num_rational::BigRational` are
+available.
 
 ## Known issues
 
@@ -56,7 +84,11 @@ possible through the Serde library.
   28–31.
 */
 
-#![forbid(unsafe_code)]
+#![cfg_attr(
+    not(any(feature = "bytemuck", feature = "nalgebra")),
+    forbid(unsafe_code)
+)]
+#![cfg_attr(any(feature = "bytemuck", feature = "nalgebra"), deny(unsafe_code))]
 // Disable irrelevant lints
 #![allow(clippy::approx_constant)]
 #![allow(clippy::excessive_precision)]
@@ -85,21 +117,49 @@ mod base;
 pub mod consts;
 
 mod convert;
+#[cfg(feature = "math_funcs")]
+pub mod fft;
 mod format;
 mod functions;
 mod num_integration;
+pub mod ode;
+mod polynomial;
+#[cfg(feature = "math_funcs")]
+pub mod quadrature;
+pub mod roots;
+mod simd;
+pub mod slice;
+
+#[cfg(feature = "approx")]
+mod approx_eq;
+#[cfg(feature = "ndarray")]
+pub mod array;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(feature = "num_rational")]
+mod rational;
+#[cfg(feature = "nalgebra")]
+mod real_field;
+#[cfg(feature = "serde")]
+pub mod serde;
 
 pub use base::no_overlap;
+pub use format::{FormatOptions, FormattedTwoFloat, Notation};
+pub use functions::continued_fraction::ContinuedFraction;
+pub use polynomial::Polynomial;
+pub use simd::{TwoFloatX4, TwoFloatX8};
 
 #[cfg(feature = "serde")]
 mod serde_helper {
     use super::{TwoFloat, TwoFloatError};
 
-    #[derive(serde::Deserialize)]
+    #[derive(::serde::Deserialize)]
     #[serde(rename = "TwoFloat")]
-    pub(super) struct TwoFloatDeserializeHelper {
-        hi: f64,
-        lo: f64,
+    pub(crate) struct TwoFloatDeserializeHelper {
+        pub(crate) hi: f64,
+        pub(crate) lo: f64,
     }
 
     impl core::convert::TryFrom<TwoFloatDeserializeHelper> for TwoFloat {
@@ -113,8 +173,11 @@ mod serde_helper {
 
 /// Represents a two-word floating point type, represented as the sum of two
 /// non-overlapping f64 values.
+///
+/// The struct has the same layout as `[f64; 2]`, with the high word first.
 #[derive(Debug, Default, Clone, Copy)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[cfg_attr(
     feature = "serde",
     serde(try_from = "serde_helper::TwoFloatDeserializeHelper")
@@ -130,17 +193,40 @@ pub struct TwoFloat {
 pub enum TwoFloatError {
     /// Indicates invalid conversion to/from `TwoFloat`
     ConversionError,
+    /// Indicates a string that could not be parsed as a `TwoFloat`
     ParseError,
+    /// Indicates an interval that does not bracket a root
+    BracketError,
+    /// Indicates an iteration that did not converge
+    ConvergenceError,
 }
 
 impl fmt::Display for TwoFloatError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::ConversionError => f.pad("invalid TwoFloat conversion"),
-            Self::ParseError => f.pad("parsing not supported"),
+            Self::ParseError => f.pad("invalid TwoFloat string"),
+            Self::BracketError => f.pad("interval does not bracket a root"),
+            Self::ConvergenceError => f.pad("iteration did not converge"),
         }
     }
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for TwoFloatError {}
+
+/// Rounding modes for operations that produce a rounded result.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to nearest, with ties to even.
+    #[default]
+    NearestEven,
+    /// Round to nearest, with ties away from zero.
+    NearestAway,
+    /// Round towards zero.
+    TowardZero,
+    /// Round towards positive infinity.
+    TowardPositive,
+    /// Round towards negative infinity.
+    TowardNegative,
+}
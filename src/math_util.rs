@@ -1,3 +1,5 @@
+use hexf::hexf64;
+
 /// A wrapper struct for mathematical operations on `f64`s.
 ///
 /// It uses "libm" if it's enabled, which is required for "no_std".
@@ -29,6 +31,10 @@ pub(crate) mod mathfn {
         libm::modf(x).0
     }
     #[inline(always)]
+    pub fn powf(x: f64, y: f64) -> f64 {
+        libm::pow(x, y)
+    }
+    #[inline(always)]
     pub fn round(x: f64) -> f64 {
         libm::round(x)
     }
@@ -76,6 +82,10 @@ pub(crate) mod mathfn {
         x.fract()
     }
     #[inline(always)]
+    pub fn powf(x: f64, y: f64) -> f64 {
+        x.powf(y)
+    }
+    #[inline(always)]
     pub fn round(x: f64) -> f64 {
         x.round()
     }
@@ -88,3 +98,29 @@ pub(crate) mod mathfn {
         x.trunc()
     }
 }
+
+/// Multiplies `x` by `2^y`, rounding only if the result is subnormal.
+pub(crate) fn mul_pow2(mut x: f64, mut y: i32) -> f64 {
+    while y > 1023 {
+        x *= hexf64!("0x1.0p1023");
+        y -= 1023;
+    }
+    // Each step leaves a remaining factor below 2^-53, so a step can only
+    // round when the final result is below half the smallest subnormal, and
+    // otherwise the last multiplication is the only one that rounds.
+    while y < -1022 {
+        x *= hexf64!("0x1.0p-969");
+        y += 969;
+    }
+    x * f64::from_bits(((y + 1023) as u64) << 52)
+}
+
+/// Returns the exponent of the leading bit of a finite, nonzero `x`.
+pub(crate) fn exponent(x: f64) -> i32 {
+    let biased = ((x.to_bits() >> 52) & 0x7ff) as i32;
+    if biased == 0 {
+        exponent(x * hexf64!("0x1.0p64")) - 64
+    } else {
+        biased - 1023
+    }
+}
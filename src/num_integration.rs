@@ -3,13 +3,47 @@ use core::{convert::TryFrom, num::FpCategory};
 use hexf::hexf64;
 use num_traits::{Inv, Pow};
 
-use crate::{consts, math_util::mathfn, TwoFloat, TwoFloatError};
+use crate::{
+    consts,
+    math_util::{mathfn, mul_pow2},
+    TwoFloat, TwoFloatError,
+};
+
+// Decodes the value with the mantissa rounded to 64 bits, with ties to even.
+fn integer_decode_u64(value: TwoFloat) -> (u64, i16, i8) {
+    if value.hi == 0.0 || !value.hi.is_finite() {
+        return num_traits::float::FloatCore::integer_decode(value.hi);
+    }
+
+    // Both words scale exactly, leaving an integer high word
+    let exponent = (value.ilogb() - 63).max(-1074);
+    let hi = mathfn::abs(mul_pow2(value.hi, -exponent));
+    let lo = mathfn::signum(value.hi) * mul_pow2(value.lo, -exponent);
+
+    let lo_floor = mathfn::floor(lo);
+    let fraction = lo - lo_floor;
+    let mut mantissa = (hi as i128 + lo_floor as i128) as u128;
+    if fraction > 0.5 || (fraction == 0.5 && mantissa & 1 == 1) {
+        mantissa += 1;
+    }
+
+    let sign = if value.hi < 0.0 { -1 } else { 1 };
+    if mantissa >> 64 != 0 {
+        ((mantissa >> 1) as u64, exponent as i16 + 1, sign)
+    } else {
+        (mantissa as u64, exponent as i16, sign)
+    }
+}
 
 impl num_traits::Num for TwoFloat {
     type FromStrRadixErr = TwoFloatError;
 
-    fn from_str_radix(_str: &str, _radix: u32) -> Result<Self, Self::FromStrRadixErr> {
-        Err(TwoFloatError::ParseError)
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if radix == 10 {
+            str.parse()
+        } else {
+            Err(TwoFloatError::ParseError)
+        }
     }
 }
 
@@ -390,7 +424,7 @@ impl num_traits::float::FloatCore for TwoFloat {
     }
 
     fn integer_decode(self) -> (u64, i16, i8) {
-        panic!("cannot decode mantissa to u64")
+        integer_decode_u64(self)
     }
 
     #[inline]
@@ -535,7 +569,7 @@ impl num_traits::Float for TwoFloat {
     }
 
     fn integer_decode(self) -> (u64, i16, i8) {
-        panic!("cannot decode mantissa to u64")
+        integer_decode_u64(self)
     }
 
     #[inline]
@@ -625,7 +659,7 @@ impl num_traits::Float for TwoFloat {
 
     #[inline]
     fn mul_add(self, a: Self, b: Self) -> Self {
-        (self * a) + b
+        TwoFloat::mul_add(self, a, b)
     }
 
     #[inline]
@@ -0,0 +1,510 @@
+//! Integration of ordinary differential equations with a `TwoFloat` state.
+//!
+//! Three families of integrators are provided:
+//!
+//! * [`rk4`] takes a fixed number of steps of the classical fourth-order
+//!   Runge–Kutta method.
+//! * [`dormand_prince`] uses the adaptive fifth-order Dormand–Prince
+//!   method, choosing the step size to meet a given tolerance.
+//! * [`leapfrog`] and [`yoshida`] are symplectic integrators of second and
+//!   fourth order for separable Hamiltonian systems, which keep the energy
+//!   error bounded over long times.
+//!
+//! The state may be any type implementing [`State`], such as
+//! `[TwoFloat; N]` or `Vec<TwoFloat>`. The derivative function is called as
+//! `f(t, y, dydt)` and writes the derivative of `y` into `dydt`, so no
+//! allocation is needed for array states.
+//!
+//! The time is accumulated with a compensated sum, so that the error in
+//! the time after many steps does not grow with the number of steps.
+//!
+//! # Examples
+//!
+//! ```
+//! # use twofloat::{ode, TwoFloat};
+//! // y' = y^2 with y(0) = 1 has the solution y = 1 / (1 - t)
+//! let mut y = [TwoFloat::from(1.0)];
+//! let t = ode::rk4(
+//!     |_, y: &[TwoFloat; 1], dydt: &mut [TwoFloat; 1]| dydt[0] = y[0] * y[0],
+//!     TwoFloat::from(0.0),
+//!     &mut y,
+//!     TwoFloat::from(0.5) / 4096.0,
+//!     4096,
+//! );
+//!
+//! assert_eq!(t, 0.5);
+//! assert!((y[0] - 2.0).abs() < 1e-14);
+//! ```
+
+use hexf::hexf64;
+
+use crate::{math_util::mathfn, TwoFloat, TwoFloatError};
+
+/// A state vector of `TwoFloat` values that can be integrated.
+///
+/// Integrators create temporary states by cloning the initial state, so
+/// all states in an integration have the same length.
+pub trait State: Clone {
+    /// Returns the components of the state.
+    fn as_slice(&self) -> &[TwoFloat];
+
+    /// Returns the components of the state mutably.
+    fn as_mut_slice(&mut self) -> &mut [TwoFloat];
+}
+
+impl<const N: usize> State for [TwoFloat; N] {
+    fn as_slice(&self) -> &[TwoFloat] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [TwoFloat] {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl State for Vec<TwoFloat> {
+    fn as_slice(&self) -> &[TwoFloat] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [TwoFloat] {
+        self
+    }
+}
+
+// Time accumulated using Kahan summation, which keeps the rounding error
+// of the sum bounded independently of the number of steps.
+#[derive(Clone, Copy)]
+struct Time {
+    value: TwoFloat,
+    compensation: TwoFloat,
+}
+
+impl Time {
+    fn new(value: TwoFloat) -> Self {
+        Self {
+            value,
+            compensation: TwoFloat::from(0.0),
+        }
+    }
+
+    fn advance(&mut self, step: TwoFloat) {
+        let y = step - self.compensation;
+        let t = self.value + y;
+        self.compensation = (t - self.value) - y;
+        self.value = t;
+    }
+}
+
+// Sets `out` to `y + h * (c1 * k1 + c2 * k2 + ...)`.
+fn combine<S: State>(out: &mut S, y: &S, h: TwoFloat, terms: &[(TwoFloat, &S)]) {
+    for (i, out_i) in out.as_mut_slice().iter_mut().enumerate() {
+        let sum = terms.iter().fold(TwoFloat::from(0.0), |acc, (c, k)| {
+            acc + *c * k.as_slice()[i]
+        });
+        *out_i = y.as_slice()[i] + h * sum;
+    }
+}
+
+fn is_valid_state<S: State>(y: &S) -> bool {
+    y.as_slice().iter().all(|x| x.is_valid())
+}
+
+/// Integrates `y' = f(t, y)` starting from time `t0`, taking `steps` steps
+/// of size `h` with the classical fourth-order Runge–Kutta method. The
+/// state `y` is updated in place, and the final time is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{ode, TwoFloat};
+/// // y' = t^3 is integrated exactly
+/// let mut y = [TwoFloat::from(0.0)];
+/// let t = ode::rk4(
+///     |t, _, dydt: &mut [TwoFloat; 1]| dydt[0] = t * t * t,
+///     TwoFloat::from(0.0),
+///     &mut y,
+///     TwoFloat::from(0.25),
+///     8,
+/// );
+///
+/// assert_eq!(t, 2.0);
+/// assert!((y[0] - 4.0).abs() < 1e-30);
+/// ```
+pub fn rk4<S: State>(
+    f: impl Fn(TwoFloat, &S, &mut S),
+    t0: TwoFloat,
+    y: &mut S,
+    h: TwoFloat,
+    steps: usize,
+) -> TwoFloat {
+    let half = h * 0.5;
+    let sixth = h / 6.0;
+    let one = TwoFloat::from(1.0);
+    let two = TwoFloat::from(2.0);
+    let mut k1 = y.clone();
+    let mut k2 = y.clone();
+    let mut k3 = y.clone();
+    let mut k4 = y.clone();
+    let mut stage = y.clone();
+
+    let mut time = Time::new(t0);
+    for _ in 0..steps {
+        let t = time.value;
+        f(t, y, &mut k1);
+        combine(&mut stage, y, half, &[(one, &k1)]);
+        f(t + half, &stage, &mut k2);
+        combine(&mut stage, y, half, &[(one, &k2)]);
+        f(t + half, &stage, &mut k3);
+        combine(&mut stage, y, h, &[(one, &k3)]);
+        f(t + h, &stage, &mut k4);
+        combine(
+            &mut stage,
+            y,
+            sixth,
+            &[(one, &k1), (two, &k2), (two, &k3), (one, &k4)],
+        );
+        core::mem::swap(y, &mut stage);
+        time.advance(h);
+    }
+    time.value
+}
+
+// Coefficients of the Dormand–Prince method, where `b` gives the
+// fifth-order solution and `e` the difference from the fourth-order
+// solution.
+struct DormandPrince {
+    c: [TwoFloat; 5],
+    a: [[TwoFloat; 5]; 5],
+    b: [TwoFloat; 6],
+    e: [TwoFloat; 7],
+}
+
+impl DormandPrince {
+    fn new() -> Self {
+        let r = TwoFloat::new_div;
+        let zero = TwoFloat::from(0.0);
+        Self {
+            c: [
+                r(1.0, 5.0),
+                r(3.0, 10.0),
+                r(4.0, 5.0),
+                r(8.0, 9.0),
+                r(1.0, 1.0),
+            ],
+            a: [
+                [r(1.0, 5.0), zero, zero, zero, zero],
+                [r(3.0, 40.0), r(9.0, 40.0), zero, zero, zero],
+                [r(44.0, 45.0), r(-56.0, 15.0), r(32.0, 9.0), zero, zero],
+                [
+                    r(19372.0, 6561.0),
+                    r(-25360.0, 2187.0),
+                    r(64448.0, 6561.0),
+                    r(-212.0, 729.0),
+                    zero,
+                ],
+                [
+                    r(9017.0, 3168.0),
+                    r(-355.0, 33.0),
+                    r(46732.0, 5247.0),
+                    r(49.0, 176.0),
+                    r(-5103.0, 18656.0),
+                ],
+            ],
+            b: [
+                r(35.0, 384.0),
+                zero,
+                r(500.0, 1113.0),
+                r(125.0, 192.0),
+                r(-2187.0, 6784.0),
+                r(11.0, 84.0),
+            ],
+            e: [
+                r(71.0, 57600.0),
+                zero,
+                r(-71.0, 16695.0),
+                r(71.0, 1920.0),
+                r(-17253.0, 339200.0),
+                r(22.0, 525.0),
+                r(-1.0, 40.0),
+            ],
+        }
+    }
+}
+
+// Bounds on the factor by which the step size may change after a step.
+const MIN_STEP_FACTOR: f64 = 0.2;
+const MAX_STEP_FACTOR: f64 = 5.0;
+const STEP_SAFETY: f64 = 0.9;
+
+/// Integrates `y' = f(t, y)` from time `t0` to `t1` using the adaptive
+/// Dormand–Prince method, updating the state `y` in place and returning
+/// the number of steps taken, including rejected steps. Integration
+/// backwards in time is possible if `t1` is less than `t0`.
+///
+/// The step size starts at the magnitude of `initial_step`, and is then
+/// chosen so that the estimated local error in each component is at most
+/// `tolerance * (1 + |y|)`, which is an absolute tolerance for small
+/// components and a relative tolerance for large ones.
+///
+/// # Errors
+///
+/// Returns `ConvergenceError` if the integration needs more than
+/// `max_steps` steps or the step size becomes too small to change the
+/// time, or `ConversionError` if a time or the state is not valid. In
+/// either case, `y` contains the state after the last accepted step.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{ode, TwoFloat};
+/// // y' = y^2 with y(0) = 1 has the solution y = 1 / (1 - t)
+/// let mut y = [TwoFloat::from(1.0)];
+/// ode::dormand_prince(
+///     |_, y: &[TwoFloat; 1], dydt: &mut [TwoFloat; 1]| dydt[0] = y[0] * y[0],
+///     TwoFloat::from(0.0),
+///     TwoFloat::from(0.5),
+///     &mut y,
+///     TwoFloat::from(1e-25),
+///     TwoFloat::from(1e-3),
+///     100000,
+/// )
+/// .unwrap();
+///
+/// assert!((y[0] - 2.0).abs() < 1e-23);
+/// ```
+pub fn dormand_prince<S: State>(
+    f: impl Fn(TwoFloat, &S, &mut S),
+    t0: TwoFloat,
+    t1: TwoFloat,
+    y: &mut S,
+    tolerance: TwoFloat,
+    initial_step: TwoFloat,
+    max_steps: usize,
+) -> Result<usize, TwoFloatError> {
+    if !t0.is_valid() || !t1.is_valid() || !initial_step.is_valid() || !is_valid_state(y) {
+        return Err(TwoFloatError::ConversionError);
+    } else if t0 == t1 {
+        return Ok(0);
+    }
+
+    let tableau = DormandPrince::new();
+    let forward = t1 > t0;
+    let mut h = if forward {
+        initial_step.abs()
+    } else {
+        -initial_step.abs()
+    };
+    let mut k: [S; 7] = core::array::from_fn(|_| y.clone());
+    let mut stage = y.clone();
+    let mut time = Time::new(t0);
+    let mut steps = 0;
+
+    f(t0, y, &mut k[0]);
+    loop {
+        let remaining = t1 - time.value;
+        let last = if forward {
+            h >= remaining
+        } else {
+            h <= remaining
+        };
+        if last {
+            h = remaining;
+        }
+        if steps == max_steps || time.value + h == time.value {
+            return Err(TwoFloatError::ConvergenceError);
+        }
+
+        let t = time.value;
+        for i in 0..5 {
+            let (done, rest) = k.split_at_mut(i + 1);
+            {
+                let terms: [(TwoFloat, &S); 5] =
+                    core::array::from_fn(|j| (tableau.a[i][j], &done[j.min(i)]));
+                combine(&mut stage, y, h, &terms[..=i]);
+            }
+            f(t + h * tableau.c[i], &stage, &mut rest[0]);
+        }
+        {
+            let terms: [(TwoFloat, &S); 6] = core::array::from_fn(|j| (tableau.b[j], &k[j]));
+            combine(&mut stage, y, h, &terms);
+        }
+        let (evaluated, last_stage) = k.split_at_mut(6);
+        let t_next = if last { t1 } else { t + h };
+        f(t_next, &stage, &mut last_stage[0]);
+
+        // Largest error relative to the tolerance for each component
+        let mut error = 0.0f64;
+        for i in 0..stage.as_slice().len() {
+            let difference = evaluated
+                .iter()
+                .chain(last_stage.iter())
+                .zip(&tableau.e)
+                .fold(TwoFloat::from(0.0), |acc, (k_j, e_j)| {
+                    acc + *e_j * k_j.as_slice()[i]
+                })
+                * h;
+            let scale = y.as_slice()[i].abs().max(stage.as_slice()[i].abs()) + 1.0;
+            error = error.max((difference.abs() / (tolerance * scale)).hi);
+        }
+        if error.is_nan() || !is_valid_state(&stage) {
+            return Err(TwoFloatError::ConversionError);
+        }
+
+        steps += 1;
+        if error <= 1.0 {
+            core::mem::swap(y, &mut stage);
+            k.swap(0, 6);
+            if last {
+                return Ok(steps);
+            }
+            time.advance(h);
+        }
+
+        let factor = if error == 0.0 {
+            MAX_STEP_FACTOR
+        } else {
+            (STEP_SAFETY * mathfn::powf(error, -0.2)).clamp(MIN_STEP_FACTOR, MAX_STEP_FACTOR)
+        };
+        h *= factor;
+    }
+}
+
+// Weights of the fourth-order Yoshida composition, 1 / (2 - 2^(1/3)) and
+// -2^(1/3) / (2 - 2^(1/3)).
+const YOSHIDA_WEIGHTS: [TwoFloat; 3] = [
+    TwoFloat {
+        hi: hexf64!("0x1.59e8b6eb96338p+0"),
+        lo: hexf64!("0x1.84a5634b09527p-54"),
+    },
+    TwoFloat {
+        hi: hexf64!("-0x1.b3d16dd72c671p+0"),
+        lo: hexf64!("0x1.ed6a72d3dab65p-55"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.59e8b6eb96338p+0"),
+        lo: hexf64!("0x1.84a5634b09527p-54"),
+    },
+];
+
+// Takes `steps` steps, each of which is a composition of kick-drift-kick
+// leapfrog steps with sizes `weights[i] * h`.
+fn symplectic<S: State>(
+    acceleration: impl Fn(&S, &mut S),
+    position: &mut S,
+    velocity: &mut S,
+    h: TwoFloat,
+    steps: usize,
+    weights: &[TwoFloat],
+) {
+    assert_eq!(
+        position.as_slice().len(),
+        velocity.as_slice().len(),
+        "position and velocity have different lengths"
+    );
+
+    let one = TwoFloat::from(1.0);
+    let mut a = position.clone();
+    let mut next = position.clone();
+    acceleration(position, &mut a);
+    for _ in 0..steps {
+        for &weight in weights {
+            let half_kick = h * weight * 0.5;
+            combine(&mut next, velocity, half_kick, &[(one, &a)]);
+            core::mem::swap(velocity, &mut next);
+            combine(&mut next, position, h * weight, &[(one, velocity)]);
+            core::mem::swap(position, &mut next);
+            acceleration(position, &mut a);
+            combine(&mut next, velocity, half_kick, &[(one, &a)]);
+            core::mem::swap(velocity, &mut next);
+        }
+    }
+}
+
+/// Integrates the system `x' = v`, `v' = a(x)` using `steps` steps of size
+/// `h` of the second-order leapfrog (velocity Verlet) method, updating
+/// `position` and `velocity` in place. The acceleration function is called
+/// as `acceleration(x, a)` and writes the acceleration into `a`.
+///
+/// The method is symplectic and time-reversible, and evaluates the
+/// acceleration once per step.
+///
+/// # Panics
+///
+/// Panics if `position` and `velocity` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{ode, TwoFloat};
+/// // Constant acceleration is integrated exactly
+/// let mut x = [TwoFloat::from(0.0)];
+/// let mut v = [TwoFloat::from(1.0)];
+/// ode::leapfrog(
+///     |_, a: &mut [TwoFloat; 1]| a[0] = TwoFloat::from(-2.0),
+///     &mut x,
+///     &mut v,
+///     TwoFloat::from(0.125),
+///     8,
+/// );
+///
+/// assert!(x[0].abs() < 1e-30);
+/// assert!((v[0] + 1.0).abs() < 1e-30);
+/// ```
+pub fn leapfrog<S: State>(
+    acceleration: impl Fn(&S, &mut S),
+    position: &mut S,
+    velocity: &mut S,
+    h: TwoFloat,
+    steps: usize,
+) {
+    symplectic(
+        acceleration,
+        position,
+        velocity,
+        h,
+        steps,
+        &[TwoFloat::from(1.0)],
+    );
+}
+
+/// Integrates the system `x' = v`, `v' = a(x)` using `steps` steps of size
+/// `h` of Yoshida's fourth-order symplectic method, updating `position` and
+/// `velocity` in place. The acceleration function is called as
+/// `acceleration(x, a)` and writes the acceleration into `a`.
+///
+/// Each step is a composition of three leapfrog steps, so the acceleration
+/// is evaluated three times per step.
+///
+/// # Panics
+///
+/// Panics if `position` and `velocity` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{ode, TwoFloat};
+/// // Harmonic oscillator, whose energy is conserved
+/// let mut x = [TwoFloat::from(1.0)];
+/// let mut v = [TwoFloat::from(0.0)];
+/// ode::yoshida(
+///     |x: &[TwoFloat; 1], a: &mut [TwoFloat; 1]| a[0] = -x[0],
+///     &mut x,
+///     &mut v,
+///     TwoFloat::from(0.01),
+///     1000,
+/// );
+///
+/// let energy = (x[0] * x[0] + v[0] * v[0]) * 0.5;
+/// assert!((energy - 0.5).abs() < 1e-9);
+/// ```
+pub fn yoshida<S: State>(
+    acceleration: impl Fn(&S, &mut S),
+    position: &mut S,
+    velocity: &mut S,
+    h: TwoFloat,
+    steps: usize,
+) {
+    symplectic(acceleration, position, velocity, h, steps, &YOSHIDA_WEIGHTS);
+}
@@ -0,0 +1,13 @@
+use crate::TwoFloat;
+
+// SAFETY: the all-zero bit pattern is the value zero.
+#[allow(unsafe_code)]
+unsafe impl bytemuck::Zeroable for TwoFloat {}
+
+// SAFETY: `TwoFloat` is `repr(C)` with two `f64` fields, so it has no
+// padding and every bit pattern is a possible value. Bit patterns that do not
+// satisfy the `no_overlap` condition are not valid for arithmetic but are
+// detected by `is_valid`, in the same way as values built from non-finite
+// words.
+#[allow(unsafe_code)]
+unsafe impl bytemuck::Pod for TwoFloat {}
@@ -0,0 +1,214 @@
+use hexf::hexf64;
+
+use crate::TwoFloat;
+
+// Bound on the relative error of multiplying two `TwoFloat` values, with
+// slack for using the high words when computing error bounds.
+const MUL_ERROR: f64 = hexf64!("0x1.8p-104");
+
+// Bound on the relative error of adding two `TwoFloat` values, with slack
+// for using the high words when computing error bounds.
+const ADD_ERROR: f64 = hexf64!("0x1.0p-104");
+
+/// A polynomial with coefficients stored in a borrowed slice in increasing
+/// order of degree, so that `coefficients[i]` is the coefficient of `x^i`.
+/// An empty slice represents the zero polynomial.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{Polynomial, TwoFloat};
+/// // 1 - 2x + 3x^2
+/// let coefficients = [1.0, -2.0, 3.0].map(TwoFloat::from);
+/// let p = Polynomial::new(&coefficients);
+/// let x = TwoFloat::from(0.5);
+///
+/// assert_eq!(p.horner(x), 0.75);
+/// assert_eq!(p.estrin(x), 0.75);
+/// assert_eq!(p.horner_with_derivative(x), (TwoFloat::from(0.75), TwoFloat::from(1.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Polynomial<'a, T> {
+    coefficients: &'a [T],
+}
+
+impl<'a, T> Polynomial<'a, T> {
+    /// Creates a polynomial from a slice of coefficients in increasing order
+    /// of degree.
+    pub const fn new(coefficients: &'a [T]) -> Self {
+        Self { coefficients }
+    }
+
+    /// Returns the coefficients of the polynomial.
+    pub const fn coefficients(&self) -> &'a [T] {
+        self.coefficients
+    }
+}
+
+// Evaluates the coefficients, of which there are at most `2^level`, using
+// Estrin's scheme, where `powers[k]` holds `x^(2^k)`.
+fn estrin_impl(coefficients: &[TwoFloat], powers: &[TwoFloat], level: usize) -> TwoFloat {
+    match coefficients.len() {
+        0 => TwoFloat::from(0.0),
+        1 => coefficients[0],
+        len => {
+            let half = 1 << (level - 1);
+            if len <= half {
+                estrin_impl(coefficients, powers, level - 1)
+            } else {
+                let (low, high) = coefficients.split_at(half);
+                estrin_impl(low, powers, level - 1)
+                    + powers[level - 1] * estrin_impl(high, powers, level - 1)
+            }
+        }
+    }
+}
+
+impl<'a> Polynomial<'a, TwoFloat> {
+    /// Evaluates the polynomial at `x` using Horner's method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{Polynomial, TwoFloat};
+    /// let coefficients = [1.0, 1.0, 0.5, 1.0 / 6.0].map(TwoFloat::from);
+    /// let p = Polynomial::new(&coefficients);
+    ///
+    /// assert!((p.horner(TwoFloat::from(1.0)) - 8.0 / 3.0).abs() < 1e-15);
+    /// ```
+    pub fn horner(&self, x: TwoFloat) -> TwoFloat {
+        let mut iter = self.coefficients.iter().rev();
+        match iter.next() {
+            Some(&init) => iter.fold(init, |acc, &c| x * acc + c),
+            None => TwoFloat::from(0.0),
+        }
+    }
+
+    /// Evaluates the polynomial at `x` using Estrin's scheme, which splits
+    /// the polynomial into independent subexpressions that can be evaluated
+    /// in parallel. The result may differ from that of `horner` by a few
+    /// ulps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{Polynomial, TwoFloat};
+    /// let coefficients: Vec<_> = (1..=9).map(TwoFloat::from).collect();
+    /// let p = Polynomial::new(&coefficients);
+    /// let x = TwoFloat::from(2.0);
+    ///
+    /// assert_eq!(p.estrin(x), p.horner(x));
+    /// ```
+    pub fn estrin(&self, x: TwoFloat) -> TwoFloat {
+        let len = self.coefficients.len();
+        if len < 2 {
+            return self.horner(x);
+        }
+
+        let levels = (usize::BITS - (len - 1).leading_zeros()) as usize;
+        let mut powers = [x; usize::BITS as usize];
+        for k in 1..levels {
+            powers[k] = powers[k - 1] * powers[k - 1];
+        }
+        estrin_impl(self.coefficients, &powers[..levels], levels)
+    }
+
+    /// Evaluates the polynomial and its first derivative at `x` using
+    /// Horner's method, returning the pair `(p(x), p'(x))`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{Polynomial, TwoFloat};
+    /// // x^3 - 2x
+    /// let coefficients = [0.0, -2.0, 0.0, 1.0].map(TwoFloat::from);
+    /// let p = Polynomial::new(&coefficients);
+    /// let (value, slope) = p.horner_with_derivative(TwoFloat::from(3.0));
+    ///
+    /// assert_eq!(value, 21.0);
+    /// assert_eq!(slope, 25.0);
+    /// ```
+    pub fn horner_with_derivative(&self, x: TwoFloat) -> (TwoFloat, TwoFloat) {
+        let mut iter = self.coefficients.iter().rev();
+        let zero = TwoFloat::from(0.0);
+        match iter.next() {
+            Some(&init) => iter.fold((init, zero), |(value, slope), &c| {
+                (x * value + c, x * slope + value)
+            }),
+            None => (zero, zero),
+        }
+    }
+
+    /// Evaluates the polynomial at `x` using Horner's method, together with
+    /// a running error bound on the absolute rounding error of the result.
+    ///
+    /// The bound accounts for the rounding errors in the evaluation, treating
+    /// `x` and the coefficients as exact, and holds provided that no
+    /// intermediate value underflows or overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{Polynomial, TwoFloat};
+    /// let coefficients = [1.0, -3.0, 3.0, -1.0].map(TwoFloat::from);
+    /// let p = Polynomial::new(&coefficients);
+    /// let x = TwoFloat::from(1.0) / 3.0;
+    /// let (value, bound) = p.horner_with_error_bound(x);
+    ///
+    /// // The exact value is (1 - x)^3 = 8/27
+    /// assert!((value - TwoFloat::from(8.0) / 27.0).abs() <= bound + 1e-31);
+    /// assert!(bound < 1e-30);
+    /// ```
+    pub fn horner_with_error_bound(&self, x: TwoFloat) -> (TwoFloat, f64) {
+        let mut iter = self.coefficients.iter().rev();
+        let mut value = match iter.next() {
+            Some(&init) => init,
+            None => return (TwoFloat::from(0.0), 0.0),
+        };
+
+        // Running error analysis following Higham (2002) Algorithm 5.1,
+        // with one term for the product and one for the sum at each step
+        let abs_x = x.hi.abs();
+        let mut bound = 0.0;
+        for &c in iter {
+            let product = x * value;
+            value = product + c;
+            bound = abs_x * bound + MUL_ERROR * product.hi.abs() + ADD_ERROR * value.hi.abs();
+        }
+        (value, bound)
+    }
+}
+
+impl<'a> Polynomial<'a, f64> {
+    /// Evaluates the polynomial at the `f64` value `x` using the compensated
+    /// Horner scheme of Graillat et al. (2005). The result is as accurate as
+    /// if it was computed using Horner's method in twice the working
+    /// precision, and is returned as a `TwoFloat`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{Polynomial, TwoFloat};
+    /// // (x - 1)^3 expanded, which is ill-conditioned near x = 1
+    /// let p = Polynomial::new(&[-1.0, 3.0, -3.0, 1.0]);
+    /// let x = 1.0 + 1.0 / 1024.0;
+    ///
+    /// assert_eq!(p.compensated_horner(x), 2f64.powi(-30));
+    /// ```
+    pub fn compensated_horner(&self, x: f64) -> TwoFloat {
+        let mut iter = self.coefficients.iter().rev();
+        let mut sum = match iter.next() {
+            Some(&init) => init,
+            None => return TwoFloat::from(0.0),
+        };
+
+        let mut correction = 0.0;
+        for &c in iter {
+            let (product, product_err) = TwoFloat::new_mul(sum, x).into();
+            let (new_sum, sum_err) = TwoFloat::new_add(product, c).into();
+            sum = new_sum;
+            correction = correction * x + (product_err + sum_err);
+        }
+        TwoFloat::new_add(sum, correction)
+    }
+}
@@ -0,0 +1,481 @@
+//! Numerical integration of functions of a `TwoFloat` value.
+//!
+//! Three methods are provided:
+//!
+//! * [`gauss_kronrod`] adaptively subdivides the interval using a 15-point
+//!   Gauss–Kronrod rule, and is suited to smooth integrands.
+//! * [`tanh_sinh`] uses the double exponential transformation, which
+//!   converges rapidly even for integrands with singularities at the ends
+//!   of the interval.
+//! * [`gauss_legendre`] applies a Gauss–Legendre rule of a given order,
+//!   with the nodes and weights computed at runtime.
+//!
+//! Either limit of integration may be infinite, in which case the integral
+//! is transformed to one over a finite interval. If `lower` is greater than
+//! `upper`, the integral is negated as usual.
+//!
+//! # Examples
+//!
+//! ```
+//! # use twofloat::{quadrature, TwoFloat};
+//! let one = TwoFloat::from(1.0);
+//! let (value, error) = quadrature::tanh_sinh(
+//!     |x| one / (x * x + 1.0),
+//!     TwoFloat::from(0.0),
+//!     TwoFloat::INFINITY,
+//!     TwoFloat::from(1e-30),
+//!     10,
+//! )
+//! .unwrap();
+//!
+//! assert!((value - twofloat::consts::FRAC_PI_2).abs() < 1e-30);
+//! assert!(error <= 1e-30);
+//! ```
+
+use hexf::hexf64;
+
+use crate::{consts::FRAC_PI_2, TwoFloat, TwoFloatError};
+
+type Integrand<'a> = &'a dyn Fn(TwoFloat) -> TwoFloat;
+type Method<'a> =
+    &'a dyn Fn(Integrand<'_>, TwoFloat, TwoFloat) -> Result<(TwoFloat, TwoFloat), TwoFloatError>;
+
+// Nodes of the 15-point Kronrod rule on [0, 1] in decreasing order, where
+// the nodes with odd indices are the nodes of the 7-point Gauss rule
+const KRONROD_NODES: [TwoFloat; 8] = [
+    TwoFloat {
+        hi: hexf64!("0x1.fba009d4d09b1p-1"),
+        lo: hexf64!("0x1.f800d0d725e70p-56"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.e5f178e7c6229p-1"),
+        lo: hexf64!("0x1.60de1160da0d9p-55"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.bacf827b9bb3ep-1"),
+        lo: hexf64!("-0x1.b8a6de2ee2c12p-56"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.7ba9f9be3a1d6p-1"),
+        lo: hexf64!("-0x1.74fee30124566p-56"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.2c13a049dfa24p-1"),
+        lo: hexf64!("-0x1.42356fff7079dp-56"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.9f95df119fd62p-2"),
+        lo: hexf64!("-0x1.3e3166754b924p-56"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.a98b2892e0c77p-3"),
+        lo: hexf64!("-0x1.e7fd5e19e4d19p-57"),
+    },
+    TwoFloat { hi: 0.0, lo: 0.0 },
+];
+
+// Weights of the 15-point Kronrod rule, in the same order as the nodes
+const KRONROD_WEIGHTS: [TwoFloat; 8] = [
+    TwoFloat {
+        hi: hexf64!("0x1.77c5b67d57470p-6"),
+        lo: hexf64!("0x1.5fa65de2d12b9p-61"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.026cdaa7b61c4p-4"),
+        lo: hexf64!("-0x1.4ebdace9b20f0p-58"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.ad384a34814c6p-4"),
+        lo: hexf64!("-0x1.204152a10b05dp-58"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.200ed0f46e8c1p-3"),
+        lo: hexf64!("-0x1.2547461855dfdp-62"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.5a1f266e47d5cp-3"),
+        lo: hexf64!("-0x1.1726f036d7079p-57"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.85d6861c80eb1p-3"),
+        lo: hexf64!("-0x1.62c966665fa55p-57"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.a2adbcbec9cd8p-3"),
+        lo: hexf64!("0x1.f15a9718a39f7p-58"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.ad04f9087090fp-3"),
+        lo: hexf64!("0x1.57e4be51b2079p-57"),
+    },
+];
+
+// Weights of the 7-point Gauss rule for the nodes with odd indices
+const GAUSS_WEIGHTS: [TwoFloat; 4] = [
+    TwoFloat {
+        hi: hexf64!("0x1.092f69f826d57p-3"),
+        lo: hexf64!("-0x1.631dcb9234e6fp-57"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.1e6b1713d8644p-2"),
+        lo: hexf64!("0x1.ad3426eb6ee1ep-56"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.86fe74ee32b3dp-2"),
+        lo: hexf64!("0x1.934be873ed303p-56"),
+    },
+    TwoFloat {
+        hi: hexf64!("0x1.abfd7e03c2fa6p-2"),
+        lo: hexf64!("-0x1.1de2532c833d4p-56"),
+    },
+];
+
+// Minimum number of levels of the tanh-sinh rule before the error estimate
+// is trusted.
+const TANH_SINH_MIN_LEVELS: usize = 3;
+
+// Calls `method` on a finite interval, transforming the integrand if either
+// limit is infinite, and handling reversed limits.
+fn integrate(
+    f: Integrand<'_>,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    method: Method<'_>,
+) -> Result<(TwoFloat, TwoFloat), TwoFloatError> {
+    if lower.hi.is_nan() || upper.hi.is_nan() {
+        return Err(TwoFloatError::ConversionError);
+    }
+
+    // Infinite values do not compare as usual, so compare the words
+    // directly, which gives the same order for finite values
+    let (lower_words, upper_words) = ((lower.hi, lower.lo), (upper.hi, upper.lo));
+    if lower_words == upper_words {
+        return Ok((TwoFloat::from(0.0), TwoFloat::from(0.0)));
+    } else if lower_words > upper_words {
+        let (value, error) = integrate(f, upper, lower, method)?;
+        return Ok((-value, error));
+    }
+
+    let zero = TwoFloat::from(0.0);
+    let one = TwoFloat::from(1.0);
+    match (lower.hi.is_finite(), upper.hi.is_finite()) {
+        (true, true) => method(f, lower, upper),
+        // Substitute x = lower + t / (1 - t)
+        (true, false) => method(
+            &|t| {
+                let s = one - t;
+                f(lower + t / s) / (s * s)
+            },
+            zero,
+            one,
+        ),
+        // Substitute x = upper - t / (1 - t)
+        (false, true) => method(
+            &|t| {
+                let s = one - t;
+                f(upper - t / s) / (s * s)
+            },
+            zero,
+            one,
+        ),
+        // Substitute x = t / (1 - t^2)
+        (false, false) => method(
+            &|t| {
+                let s = one - t * t;
+                f(t / s) * (one + t * t) / (s * s)
+            },
+            -one,
+            one,
+        ),
+    }
+}
+
+// Applies the 15-point Gauss–Kronrod rule, returning the Kronrod estimate
+// and its difference from the Gauss estimate.
+fn kronrod_rule(f: Integrand<'_>, lower: TwoFloat, upper: TwoFloat) -> (TwoFloat, TwoFloat) {
+    let half = (upper - lower) * 0.5;
+    let center = lower + half;
+
+    let f_center = f(center);
+    let mut kronrod = f_center * KRONROD_WEIGHTS[7];
+    let mut gauss = f_center * GAUSS_WEIGHTS[3];
+    for j in 0..7 {
+        let offset = half * KRONROD_NODES[j];
+        let sum = f(center - offset) + f(center + offset);
+        kronrod += KRONROD_WEIGHTS[j] * sum;
+        if j % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[j / 2] * sum;
+        }
+    }
+    (kronrod * half, ((kronrod - gauss) * half).abs())
+}
+
+fn adaptive_kronrod(
+    f: Integrand<'_>,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    depth: usize,
+) -> Result<(TwoFloat, TwoFloat), TwoFloatError> {
+    let (value, error) = kronrod_rule(f, lower, upper);
+    if !value.is_valid() {
+        return Err(TwoFloatError::ConversionError);
+    } else if error <= tolerance {
+        return Ok((value, error));
+    }
+
+    let mid = lower + (upper - lower) * 0.5;
+    if depth == 0 || mid <= lower || mid >= upper {
+        return Err(TwoFloatError::ConvergenceError);
+    }
+
+    let (left, left_error) = adaptive_kronrod(f, lower, mid, tolerance * 0.5, depth - 1)?;
+    let (right, right_error) = adaptive_kronrod(f, mid, upper, tolerance * 0.5, depth - 1)?;
+    Ok((left + right, left_error + right_error))
+}
+
+/// Integrates `f` between `lower` and `upper` using an adaptive 15-point
+/// Gauss–Kronrod rule, returning the pair `(value, error)` where `error`
+/// is an estimate of the absolute error.
+///
+/// Intervals are bisected until the difference between the Kronrod and
+/// Gauss estimates on each is within its share of `tolerance`, with at
+/// most `max_depth` levels of bisection.
+///
+/// # Errors
+///
+/// Returns `ConvergenceError` if the error estimate exceeds `tolerance`
+/// after `max_depth` levels of bisection, or `ConversionError` if a limit
+/// is NaN or the integrand produces an invalid value.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{consts::PI, quadrature, TwoFloat};
+/// let (value, _) = quadrature::gauss_kronrod(
+///     |x| TwoFloat::from(4.0) / (x * x + 1.0),
+///     TwoFloat::from(0.0),
+///     TwoFloat::from(1.0),
+///     TwoFloat::from(1e-30),
+///     20,
+/// )
+/// .unwrap();
+///
+/// assert!((value - PI).abs() < 1e-30);
+/// ```
+pub fn gauss_kronrod(
+    f: impl Fn(TwoFloat) -> TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    max_depth: usize,
+) -> Result<(TwoFloat, TwoFloat), TwoFloatError> {
+    integrate(&f, lower, upper, &|g, a, b| {
+        adaptive_kronrod(g, a, b, tolerance, max_depth)
+    })
+}
+
+// Sums the terms of the tanh-sinh rule on a finite interval for abscissas
+// `t = start, start + step, ...`, stopping once the nodes are
+// indistinguishable from the ends of the interval.
+fn tanh_sinh_terms(
+    f: Integrand<'_>,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    start: TwoFloat,
+    step: TwoFloat,
+) -> TwoFloat {
+    let width = upper - lower;
+    let mut sum = TwoFloat::from(0.0);
+    let mut t = start;
+    loop {
+        // With u = pi/2 sinh(t) and e = exp(-2u), the nodes are at
+        // distance width * e / (1 + e) from the ends of the interval, and
+        // the weight is pi/2 cosh(t) sech^2(u) = 2 pi cosh(t) e / (1 + e)^2
+        let exp_t = t.exp();
+        let recip_exp_t = TwoFloat::from(1.0) / exp_t;
+        let u = FRAC_PI_2 * (exp_t - recip_exp_t) * 0.5;
+        let e = (u * -2.0).exp();
+        let q = e / (e + 1.0);
+        let offset = width * q;
+        let (x1, x2) = (lower + offset, upper - offset);
+        if offset.hi == 0.0 || x1 <= lower || x2 >= upper {
+            break;
+        }
+
+        let value = f(x1) + f(x2);
+        if !value.is_valid() {
+            break;
+        }
+        let weight = FRAC_PI_2 * (exp_t + recip_exp_t) * q / (e + 1.0) * 2.0;
+        sum += weight * value;
+        t += step;
+    }
+    sum
+}
+
+fn tanh_sinh_finite(
+    f: Integrand<'_>,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    max_levels: usize,
+) -> Result<(TwoFloat, TwoFloat), TwoFloatError> {
+    let half = (upper - lower) * 0.5;
+    let mut step = TwoFloat::from(1.0);
+    let mut sum = FRAC_PI_2 * f(lower + half) + tanh_sinh_terms(f, lower, upper, step, step);
+    let mut estimate = sum * step * half;
+    if !estimate.is_valid() {
+        return Err(TwoFloatError::ConversionError);
+    }
+
+    // Each level halves the step, so only the new odd multiples are added
+    for level in 1..=max_levels {
+        sum += tanh_sinh_terms(f, lower, upper, step * 0.5, step);
+        step *= 0.5;
+        let next = sum * step * half;
+        if !next.is_valid() {
+            return Err(TwoFloatError::ConversionError);
+        }
+
+        let error = (next - estimate).abs();
+        estimate = next;
+        if level >= TANH_SINH_MIN_LEVELS && error <= tolerance {
+            return Ok((estimate, error));
+        }
+    }
+    Err(TwoFloatError::ConvergenceError)
+}
+
+/// Integrates `f` between `lower` and `upper` using the tanh-sinh (double
+/// exponential) rule, returning the pair `(value, error)` where `error` is
+/// an estimate of the absolute error.
+///
+/// The step size is halved at each level until the estimates from
+/// successive levels differ by at most `tolerance`, with at most
+/// `max_levels` levels. The integrand is never evaluated at the ends of the
+/// interval, and terms where it is not finite are omitted, so the rule is
+/// suitable for integrands with singularities at the ends. Nodes close to
+/// a nonzero limit are rounded relative to that limit, so full accuracy is
+/// only obtained if any singularities are at zero.
+///
+/// # Errors
+///
+/// Returns `ConvergenceError` if the error estimate exceeds `tolerance`
+/// after `max_levels` levels, or `ConversionError` if a limit is NaN or
+/// the estimate is not valid.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{quadrature, TwoFloat};
+/// // Singular at zero
+/// let (value, _) = quadrature::tanh_sinh(
+///     |x| x.sqrt().recip(),
+///     TwoFloat::from(0.0),
+///     TwoFloat::from(1.0),
+///     TwoFloat::from(1e-30),
+///     10,
+/// )
+/// .unwrap();
+///
+/// assert!((value - 2.0).abs() < 1e-30);
+/// ```
+pub fn tanh_sinh(
+    f: impl Fn(TwoFloat) -> TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    max_levels: usize,
+) -> Result<(TwoFloat, TwoFloat), TwoFloatError> {
+    integrate(&f, lower, upper, &|g, a, b| {
+        tanh_sinh_finite(g, a, b, tolerance, max_levels)
+    })
+}
+
+// Evaluates the Legendre polynomial of degree `n` and its derivative.
+fn legendre(n: usize, x: TwoFloat) -> (TwoFloat, TwoFloat) {
+    let mut previous = TwoFloat::from(1.0);
+    let mut current = x;
+    for k in 2..=n {
+        let k = k as f64;
+        let next = (x * current * (2.0 * k - 1.0) - previous * (k - 1.0)) / k;
+        previous = current;
+        current = next;
+    }
+    let slope = (x * current - previous) * n as f64 / (x * x - 1.0);
+    (current, slope)
+}
+
+// Returns the node and weight of the Gauss–Legendre rule of order `n` with
+// index `i`, where the nodes are in decreasing order.
+fn legendre_node(n: usize, i: usize) -> (TwoFloat, TwoFloat) {
+    let guess = (crate::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+    let mut x = guess;
+    let mut slope = TwoFloat::from(1.0);
+    for _ in 0..100 {
+        let (value, derivative) = legendre(n, x);
+        slope = derivative;
+        let dx = value / derivative;
+        x -= dx;
+        if dx.abs() <= hexf64!("0x1.0p-104") {
+            slope = legendre(n, x).1;
+            break;
+        }
+    }
+    let weight = TwoFloat::from(2.0) / ((TwoFloat::from(1.0) - x * x) * slope * slope);
+    (x, weight)
+}
+
+/// Integrates `f` between `lower` and `upper` using the Gauss–Legendre
+/// rule with `order` nodes, which is exact for polynomials of degree up to
+/// `2 * order - 1`. The nodes and weights are computed at runtime using
+/// Newton's method, which takes time proportional to the square of
+/// `order`.
+///
+/// # Errors
+///
+/// Returns `ConversionError` if a limit is NaN.
+///
+/// # Panics
+///
+/// Panics if `order` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{quadrature, TwoFloat};
+/// let value = quadrature::gauss_legendre(
+///     |x| (x + 1.0).recip(),
+///     TwoFloat::from(0.0),
+///     TwoFloat::from(1.0),
+///     24,
+/// )
+/// .unwrap();
+///
+/// assert!((value - twofloat::consts::LN_2).abs() < 1e-30);
+/// ```
+pub fn gauss_legendre(
+    f: impl Fn(TwoFloat) -> TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    order: usize,
+) -> Result<TwoFloat, TwoFloatError> {
+    assert!(order > 0, "order of Gauss–Legendre rule must be positive");
+    let (value, _) = integrate(&f, lower, upper, &|g, a, b| {
+        let half = (b - a) * 0.5;
+        let center = a + half;
+        let mut sum = TwoFloat::from(0.0);
+        for i in 0..order / 2 {
+            let (x, weight) = legendre_node(order, i);
+            let offset = half * x;
+            sum += weight * (g(center - offset) + g(center + offset));
+        }
+        if order % 2 == 1 {
+            let (_, slope) = legendre(order, TwoFloat::from(0.0));
+            sum += g(center) * 2.0 / (slope * slope);
+        }
+        Ok((sum * half, TwoFloat::from(0.0)))
+    })?;
+    Ok(value)
+}
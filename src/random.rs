@@ -0,0 +1,157 @@
+//! Random sampling of `TwoFloat` values using the `rand` crate.
+//!
+//! The `Standard` distribution produces values uniformly distributed in
+//! `[0, 1)` with 106 random bits, and `TwoFloat` implements
+//! `SampleUniform` so that ranges can be sampled using `Uniform` or
+//! `Rng::gen_range`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use rand::{distributions::Uniform, Rng};
+//! # use twofloat::TwoFloat;
+//! let mut rng = rand::thread_rng();
+//! let a: TwoFloat = rng.gen();
+//! let b = rng.sample(Uniform::new(TwoFloat::from(-1.0), TwoFloat::from(1.0)));
+//! let c = rng.gen_range(TwoFloat::from(2.0)..=TwoFloat::from(3.0));
+//!
+//! assert!(a >= 0.0 && a < 1.0);
+//! assert!(b >= -1.0 && b < 1.0);
+//! assert!(c >= 2.0 && c <= 3.0);
+//! ```
+
+use rand::{
+    distributions::{
+        uniform::{SampleBorrow, SampleUniform, UniformSampler},
+        Distribution, Standard,
+    },
+    Rng,
+};
+
+use crate::TwoFloat;
+
+// Number of random bits in a sample from `Standard`
+const RANDOM_BITS: i32 = 106;
+
+impl Distribution<TwoFloat> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TwoFloat {
+        let bits = rng.gen::<u128>() >> (128 - RANDOM_BITS);
+        TwoFloat::from(bits).ldexp(-RANDOM_BITS)
+    }
+}
+
+/// Sampler for `TwoFloat` values uniformly distributed over a range, used
+/// by `Uniform<TwoFloat>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniformTwoFloat {
+    low: TwoFloat,
+    high: TwoFloat,
+    scale: TwoFloat,
+    inclusive: bool,
+}
+
+impl UniformSampler for UniformTwoFloat {
+    type X = TwoFloat;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<TwoFloat> + Sized,
+        B2: SampleBorrow<TwoFloat> + Sized,
+    {
+        let low = *low.borrow();
+        let high = *high.borrow();
+        assert!(
+            low.is_valid() && high.is_valid(),
+            "Uniform::new called with invalid bounds"
+        );
+        assert!(low < high, "Uniform::new called with `low >= high`");
+        let scale = high - low;
+        assert!(scale.is_valid(), "Uniform::new: range overflow");
+
+        Self {
+            low,
+            high,
+            scale,
+            inclusive: false,
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<TwoFloat> + Sized,
+        B2: SampleBorrow<TwoFloat> + Sized,
+    {
+        let low = *low.borrow();
+        let high = *high.borrow();
+        assert!(
+            low.is_valid() && high.is_valid(),
+            "Uniform::new_inclusive called with invalid bounds"
+        );
+        assert!(
+            low <= high,
+            "Uniform::new_inclusive called with `low > high`"
+        );
+        let scale = high - low;
+        assert!(scale.is_valid(), "Uniform::new_inclusive: range overflow");
+
+        Self {
+            low,
+            high,
+            scale,
+            inclusive: true,
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TwoFloat {
+        loop {
+            let unit: TwoFloat = rng.gen();
+            let value = self.low + self.scale * unit;
+
+            // Rounding may produce the upper bound, which is excluded from
+            // half-open ranges
+            if value < self.high || (self.inclusive && value == self.high) {
+                return value;
+            }
+        }
+    }
+}
+
+impl SampleUniform for TwoFloat {
+    type Sampler = UniformTwoFloat;
+}
+
+/// The standard normal distribution, with mean 0 and standard deviation 1,
+/// sampled using the Marsaglia polar method with `TwoFloat` arithmetic
+/// throughout.
+///
+/// The transform uses `ln` and `sqrt`, which are accurate to the full
+/// precision of a `TwoFloat`, so each sample carries only a few ulps of
+/// rounding error rather than the error of an `f64` transform.
+///
+/// # Examples
+///
+/// ```
+/// # use rand::Rng;
+/// # use twofloat::{random::StandardNormal, TwoFloat};
+/// let mut rng = rand::thread_rng();
+/// let a: TwoFloat = rng.sample(StandardNormal);
+///
+/// assert!(a.is_valid());
+/// ```
+#[cfg(feature = "math_funcs")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StandardNormal;
+
+#[cfg(feature = "math_funcs")]
+impl Distribution<TwoFloat> for StandardNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TwoFloat {
+        loop {
+            let x = 2.0 * rng.gen::<TwoFloat>() - 1.0;
+            let y = 2.0 * rng.gen::<TwoFloat>() - 1.0;
+            let s = x * x + y * y;
+            if s > 0.0 && s < 1.0 {
+                return x * (-2.0 * s.ln() / s).sqrt();
+            }
+        }
+    }
+}
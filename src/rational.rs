@@ -0,0 +1,66 @@
+use core::convert::TryFrom;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+
+use crate::{RoundingMode, TwoFloat, TwoFloatError};
+
+// Enough limbs for the numerator of any finite value, which spans at most
+// 2100 bits
+const RATIO_LIMBS: usize = 34;
+
+impl TryFrom<TwoFloat> for BigRational {
+    type Error = TwoFloatError;
+
+    fn try_from(value: TwoFloat) -> Result<Self, Self::Error> {
+        let mut limbs = [0; RATIO_LIMBS];
+        let exponent = value.to_ratio_limbs(&mut limbs)?;
+        let magnitude = limbs
+            .iter()
+            .rev()
+            .fold(BigUint::zero(), |acc, &limb| (acc << 64u32) + limb);
+        let sign = if value.hi < 0.0 {
+            Sign::Minus
+        } else {
+            Sign::Plus
+        };
+        let numerator = BigInt::from_biguint(sign, magnitude);
+
+        // The numerator is odd, so the ratio is already in lowest terms
+        Ok(if exponent >= 0 {
+            BigRational::new_raw(numerator << exponent as u32, BigInt::one())
+        } else {
+            BigRational::new_raw(numerator, BigInt::one() << (-exponent) as u32)
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a TwoFloat> for BigRational {
+    type Error = TwoFloatError;
+
+    fn try_from(value: &'a TwoFloat) -> Result<Self, Self::Error> {
+        Self::try_from(*value)
+    }
+}
+
+impl<'a> From<&'a BigRational> for TwoFloat {
+    fn from(value: &'a BigRational) -> Self {
+        let numerator = value.numer().magnitude().to_u64_digits();
+        let denominator = value.denom().magnitude().to_u64_digits();
+        let negative =
+            (value.numer().sign() == Sign::Minus) != (value.denom().sign() == Sign::Minus);
+        TwoFloat::from_ratio_limbs(
+            &numerator,
+            &denominator,
+            negative,
+            RoundingMode::NearestEven,
+        )
+    }
+}
+
+impl From<BigRational> for TwoFloat {
+    fn from(value: BigRational) -> Self {
+        Self::from(&value)
+    }
+}
@@ -0,0 +1,386 @@
+use simba::{
+    scalar::{ComplexField, Field, RealField, SubsetOf},
+    simd::SimdValue,
+};
+
+use crate::{consts, TwoFloat};
+
+impl SimdValue for TwoFloat {
+    const LANES: usize = 1;
+    type Element = TwoFloat;
+    type SimdBool = bool;
+
+    fn splat(val: TwoFloat) -> Self {
+        val
+    }
+
+    fn extract(&self, _: usize) -> TwoFloat {
+        *self
+    }
+
+    // The method is unsafe in the trait, but any index is safe to ignore
+    #[allow(unsafe_code)]
+    unsafe fn extract_unchecked(&self, _: usize) -> TwoFloat {
+        *self
+    }
+
+    fn replace(&mut self, _: usize, val: TwoFloat) {
+        *self = val;
+    }
+
+    #[allow(unsafe_code)]
+    unsafe fn replace_unchecked(&mut self, _: usize, val: TwoFloat) {
+        *self = val;
+    }
+
+    fn select(self, cond: bool, other: Self) -> Self {
+        if cond {
+            self
+        } else {
+            other
+        }
+    }
+}
+
+impl Field for TwoFloat {}
+
+impl SubsetOf<TwoFloat> for TwoFloat {
+    fn to_superset(&self) -> TwoFloat {
+        *self
+    }
+
+    fn from_superset_unchecked(element: &TwoFloat) -> Self {
+        *element
+    }
+
+    fn is_in_subset(_: &TwoFloat) -> bool {
+        true
+    }
+}
+
+impl SubsetOf<TwoFloat> for f64 {
+    fn to_superset(&self) -> TwoFloat {
+        TwoFloat::from(*self)
+    }
+
+    fn from_superset_unchecked(element: &TwoFloat) -> Self {
+        f64::from(*element)
+    }
+
+    fn is_in_subset(_: &TwoFloat) -> bool {
+        true
+    }
+}
+
+impl SubsetOf<TwoFloat> for f32 {
+    fn to_superset(&self) -> TwoFloat {
+        TwoFloat::from(*self as f64)
+    }
+
+    fn from_superset_unchecked(element: &TwoFloat) -> Self {
+        f64::from(*element) as f32
+    }
+
+    fn is_in_subset(_: &TwoFloat) -> bool {
+        true
+    }
+}
+
+impl ComplexField for TwoFloat {
+    type RealField = TwoFloat;
+
+    fn from_real(re: TwoFloat) -> Self {
+        re
+    }
+
+    fn real(self) -> TwoFloat {
+        self
+    }
+
+    fn imaginary(self) -> TwoFloat {
+        TwoFloat::from(0.0)
+    }
+
+    fn modulus(self) -> TwoFloat {
+        self.abs()
+    }
+
+    fn modulus_squared(self) -> TwoFloat {
+        self * self
+    }
+
+    fn argument(self) -> TwoFloat {
+        if self.hi >= 0.0 {
+            TwoFloat::from(0.0)
+        } else {
+            consts::PI
+        }
+    }
+
+    fn norm1(self) -> TwoFloat {
+        self.abs()
+    }
+
+    fn scale(self, factor: TwoFloat) -> Self {
+        self * factor
+    }
+
+    fn unscale(self, factor: TwoFloat) -> Self {
+        self / factor
+    }
+
+    fn floor(self) -> Self {
+        TwoFloat::floor(self)
+    }
+
+    fn ceil(self) -> Self {
+        TwoFloat::ceil(self)
+    }
+
+    fn round(self) -> Self {
+        TwoFloat::round(self)
+    }
+
+    fn trunc(self) -> Self {
+        TwoFloat::trunc(self)
+    }
+
+    fn fract(self) -> Self {
+        TwoFloat::fract(self)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        TwoFloat::mul_add(self, a, b)
+    }
+
+    fn abs(self) -> TwoFloat {
+        TwoFloat::abs(&self)
+    }
+
+    fn hypot(self, other: Self) -> TwoFloat {
+        TwoFloat::hypot(self, other)
+    }
+
+    fn recip(self) -> Self {
+        TwoFloat::recip(self)
+    }
+
+    fn conjugate(self) -> Self {
+        self
+    }
+
+    fn sin(self) -> Self {
+        TwoFloat::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        TwoFloat::cos(self)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        TwoFloat::sin_cos(self)
+    }
+
+    fn tan(self) -> Self {
+        TwoFloat::tan(self)
+    }
+
+    fn asin(self) -> Self {
+        TwoFloat::asin(self)
+    }
+
+    fn acos(self) -> Self {
+        TwoFloat::acos(self)
+    }
+
+    fn atan(self) -> Self {
+        TwoFloat::atan(self)
+    }
+
+    fn sinh(self) -> Self {
+        TwoFloat::sinh(self)
+    }
+
+    fn cosh(self) -> Self {
+        TwoFloat::cosh(self)
+    }
+
+    fn tanh(self) -> Self {
+        TwoFloat::tanh(self)
+    }
+
+    fn asinh(self) -> Self {
+        TwoFloat::asinh(self)
+    }
+
+    fn acosh(self) -> Self {
+        TwoFloat::acosh(self)
+    }
+
+    fn atanh(self) -> Self {
+        TwoFloat::atanh(self)
+    }
+
+    fn log(self, base: TwoFloat) -> Self {
+        TwoFloat::log(self, base)
+    }
+
+    fn log2(self) -> Self {
+        TwoFloat::log2(self)
+    }
+
+    fn log10(self) -> Self {
+        TwoFloat::log10(self)
+    }
+
+    fn ln(self) -> Self {
+        TwoFloat::ln(self)
+    }
+
+    fn ln_1p(self) -> Self {
+        TwoFloat::ln_1p(self)
+    }
+
+    fn sqrt(self) -> Self {
+        TwoFloat::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        TwoFloat::exp(self)
+    }
+
+    fn exp2(self) -> Self {
+        TwoFloat::exp2(self)
+    }
+
+    fn exp_m1(self) -> Self {
+        TwoFloat::exp_m1(self)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        TwoFloat::powi(self, n)
+    }
+
+    fn powf(self, n: TwoFloat) -> Self {
+        TwoFloat::powf(self, n)
+    }
+
+    fn powc(self, n: Self) -> Self {
+        TwoFloat::powf(self, n)
+    }
+
+    fn cbrt(self) -> Self {
+        TwoFloat::cbrt(self)
+    }
+
+    fn is_finite(&self) -> bool {
+        self.hi.is_finite() && self.lo.is_finite()
+    }
+
+    fn try_sqrt(self) -> Option<Self> {
+        if self.hi >= 0.0 {
+            Some(TwoFloat::sqrt(self))
+        } else {
+            None
+        }
+    }
+}
+
+impl RealField for TwoFloat {
+    fn is_sign_positive(&self) -> bool {
+        TwoFloat::is_sign_positive(self)
+    }
+
+    fn is_sign_negative(&self) -> bool {
+        TwoFloat::is_sign_negative(self)
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        TwoFloat::copysign(&self, &sign)
+    }
+
+    fn max(self, other: Self) -> Self {
+        TwoFloat::max(self, other)
+    }
+
+    fn min(self, other: Self) -> Self {
+        TwoFloat::min(self, other)
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Self {
+        assert!(min <= max);
+        TwoFloat::max(TwoFloat::min(self, max), min)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        TwoFloat::atan2(self, other)
+    }
+
+    fn min_value() -> Option<Self> {
+        Some(TwoFloat::MIN)
+    }
+
+    fn max_value() -> Option<Self> {
+        Some(TwoFloat::MAX)
+    }
+
+    fn pi() -> Self {
+        consts::PI
+    }
+
+    fn two_pi() -> Self {
+        consts::TAU
+    }
+
+    fn frac_pi_2() -> Self {
+        consts::FRAC_PI_2
+    }
+
+    fn frac_pi_3() -> Self {
+        consts::FRAC_PI_3
+    }
+
+    fn frac_pi_4() -> Self {
+        consts::FRAC_PI_4
+    }
+
+    fn frac_pi_6() -> Self {
+        consts::FRAC_PI_6
+    }
+
+    fn frac_pi_8() -> Self {
+        consts::FRAC_PI_8
+    }
+
+    fn frac_1_pi() -> Self {
+        consts::FRAC_1_PI
+    }
+
+    fn frac_2_pi() -> Self {
+        consts::FRAC_2_PI
+    }
+
+    fn frac_2_sqrt_pi() -> Self {
+        consts::FRAC_2_SQRT_PI
+    }
+
+    fn e() -> Self {
+        consts::E
+    }
+
+    fn log2_e() -> Self {
+        consts::LOG2_E
+    }
+
+    fn log10_e() -> Self {
+        consts::LOG10_E
+    }
+
+    fn ln_2() -> Self {
+        consts::LN_2
+    }
+
+    fn ln_10() -> Self {
+        consts::LN_10
+    }
+}
@@ -0,0 +1,672 @@
+//! Root finding for functions of a `TwoFloat` value.
+//!
+//! The bracketing methods [`bisection`] and [`brent`] require an interval
+//! over which the function changes sign, and converge for any continuous
+//! function. The safeguarded [`newton`] and [`halley`] methods also keep
+//! track of a bracketing interval, and fall back to bisection whenever a
+//! step would leave it. If the `math_funcs` feature is enabled, the [`aberth`] and
+//! [`durand_kerner`] functions find all complex roots of a polynomial.
+//!
+//! In each method, `tolerance` is the absolute accuracy required of the
+//! root. A tolerance of zero iterates until the root is found to the full
+//! precision of `TwoFloat`. Each method returns `ConvergenceError` if the
+//! root is not found within `max_iterations` iterations.
+//!
+//! # Examples
+//!
+//! ```
+//! # use twofloat::{roots, TwoFloat};
+//! let two = TwoFloat::from(2.0);
+//! let zero = TwoFloat::from(0.0);
+//! let root = roots::brent(|x| x * x - two, zero, two, zero, 200).unwrap();
+//!
+//! assert!((root * root - two).abs() < 1e-31);
+//! ```
+
+#[cfg(feature = "math_funcs")]
+use core::ops::{Add, Div, Mul, Sub};
+
+use hexf::hexf64;
+
+#[cfg(feature = "math_funcs")]
+use crate::{consts::TAU, Polynomial};
+use crate::{TwoFloat, TwoFloatError};
+
+// Relative accuracy at which iterations are considered converged.
+const RELATIVE_TOLERANCE: f64 = hexf64!("0x1.0p-104");
+
+// Returns the width below which an interval around `x` is considered to
+// have converged.
+fn convergence_width(x: TwoFloat, tolerance: TwoFloat) -> TwoFloat {
+    tolerance + x.abs() * RELATIVE_TOLERANCE
+}
+
+fn is_negative(value: TwoFloat) -> bool {
+    value.hi < 0.0
+}
+
+// Evaluates the function at both ends of the interval, returning the
+// function values if the interval brackets a root.
+fn check_bracket(
+    f: &impl Fn(TwoFloat) -> TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+) -> Result<(TwoFloat, TwoFloat), TwoFloatError> {
+    let f_lower = f(lower);
+    let f_upper = f(upper);
+    if !f_lower.is_valid() || !f_upper.is_valid() || !lower.is_valid() || !upper.is_valid() {
+        Err(TwoFloatError::ConversionError)
+    } else if f_lower != 0.0 && f_upper != 0.0 && is_negative(f_lower) == is_negative(f_upper) {
+        Err(TwoFloatError::BracketError)
+    } else {
+        Ok((f_lower, f_upper))
+    }
+}
+
+/// Finds a root of `f` in the interval between `lower` and `upper` using
+/// the bisection method. Each iteration halves the interval, so finding a
+/// root of magnitude around one to full precision in an interval of unit
+/// width takes about 106 iterations.
+///
+/// # Errors
+///
+/// Returns `BracketError` if `f` has the same sign at both ends of the
+/// interval, `ConversionError` if an endpoint or the value of `f` at an
+/// endpoint is not valid, or `ConvergenceError` if the root is not found
+/// within `max_iterations` iterations.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{roots, TwoFloat};
+/// let root = roots::bisection(
+///     |x| x * x * x - 3.0,
+///     TwoFloat::from(1.0),
+///     TwoFloat::from(2.0),
+///     TwoFloat::from(1e-20),
+///     100,
+/// )
+/// .unwrap();
+///
+/// assert!((root * root * root - 3.0).abs() <= 1e-19);
+/// ```
+pub fn bisection(
+    f: impl Fn(TwoFloat) -> TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    max_iterations: usize,
+) -> Result<TwoFloat, TwoFloatError> {
+    let (f_lower, f_upper) = check_bracket(&f, lower, upper)?;
+    if f_lower == 0.0 {
+        return Ok(lower);
+    } else if f_upper == 0.0 {
+        return Ok(upper);
+    }
+
+    let (mut a, mut b) = (lower, upper);
+    let lower_negative = is_negative(f_lower);
+    for _ in 0..max_iterations {
+        let mid = a + (b - a) * 0.5;
+        if (b - a).abs() <= convergence_width(mid, tolerance) || mid == a || mid == b {
+            return Ok(mid);
+        }
+
+        let f_mid = f(mid);
+        if f_mid == 0.0 {
+            return Ok(mid);
+        } else if is_negative(f_mid) == lower_negative {
+            a = mid;
+        } else {
+            b = mid;
+        }
+    }
+    Err(TwoFloatError::ConvergenceError)
+}
+
+/// Finds a root of `f` in the interval between `lower` and `upper` using
+/// Brent's method, which combines inverse quadratic interpolation, the
+/// secant method and bisection.
+///
+/// # Errors
+///
+/// Returns `BracketError` if `f` has the same sign at both ends of the
+/// interval, `ConversionError` if an endpoint or the value of `f` at an
+/// endpoint is not valid, or `ConvergenceError` if the root is not found
+/// within `max_iterations` iterations.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{roots, TwoFloat};
+/// // Wallis's equation
+/// let f = |x: TwoFloat| x * x * x - x * 2.0 - 5.0;
+/// let zero = TwoFloat::from(0.0);
+/// let root = roots::brent(f, zero, TwoFloat::from(3.0), zero, 100).unwrap();
+///
+/// assert!(f(root).abs() < 1e-30);
+/// ```
+pub fn brent(
+    f: impl Fn(TwoFloat) -> TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    max_iterations: usize,
+) -> Result<TwoFloat, TwoFloatError> {
+    let (f_lower, f_upper) = check_bracket(&f, lower, upper)?;
+
+    // Brent (1973) procedure zero, where b is the best estimate of the
+    // root and c is the other end of the bracketing interval
+    let (mut a, mut b) = (lower, upper);
+    let (mut fa, mut fb) = (f_lower, f_upper);
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..max_iterations {
+        // Keep the root between b and c
+        if fb != 0.0 && fc != 0.0 && is_negative(fb) == is_negative(fc) {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol = convergence_width(b, tolerance) * 0.5;
+        let m = (c - b) * 0.5;
+        if m.abs() <= tol || fb == 0.0 {
+            return Ok(b);
+        }
+
+        if e.abs() < tol || fa.abs() <= fb.abs() {
+            d = m;
+            e = m;
+        } else {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // Secant step
+                (m * s * 2.0, 1.0 - s)
+            } else {
+                // Inverse quadratic interpolation
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (m * q * (q - r) * 2.0 - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p.hi > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+
+            if p * 2.0 < m * q * 3.0 - (tol * q).abs() && p < (e * q * 0.5).abs() {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol {
+            d
+        } else if m.hi > 0.0 {
+            tol
+        } else {
+            -tol
+        };
+        fb = f(b);
+    }
+    Err(TwoFloatError::ConvergenceError)
+}
+
+// Runs a safeguarded iteration where `step` returns the function value and
+// the proposed step at a point.
+fn safeguarded(
+    step: impl Fn(TwoFloat) -> (TwoFloat, TwoFloat),
+    guess: TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    max_iterations: usize,
+) -> Result<TwoFloat, TwoFloatError> {
+    let (f_lower, _) = check_bracket(&|x| step(x).0, lower, upper)?;
+    let lower_negative = is_negative(f_lower);
+    let (mut a, mut b) = if lower < upper {
+        (lower, upper)
+    } else {
+        (upper, lower)
+    };
+    let a_negative = if lower < upper {
+        lower_negative
+    } else {
+        !lower_negative
+    };
+
+    let mut x = if guess >= a && guess <= b {
+        guess
+    } else {
+        a + (b - a) * 0.5
+    };
+    for _ in 0..max_iterations {
+        let (value, dx) = step(x);
+        if value == 0.0 {
+            return Ok(x);
+        }
+
+        if is_negative(value) == a_negative {
+            a = x;
+        } else {
+            b = x;
+        }
+
+        let next = x - dx;
+        let (next, width) = if dx.is_valid() && next > a && next < b {
+            (next, dx.abs())
+        } else {
+            // Bisect when the step leaves the interval
+            (a + (b - a) * 0.5, (b - a) * 0.5)
+        };
+        if width <= convergence_width(next, tolerance) {
+            return Ok(next);
+        }
+        x = next;
+    }
+    Err(TwoFloatError::ConvergenceError)
+}
+
+/// Finds a root of a function using Newton's method, safeguarded by
+/// bisection so that the iterates remain between `lower` and `upper`. The
+/// function `f` returns the pair `(f(x), f'(x))`.
+///
+/// # Errors
+///
+/// Returns `BracketError` if the function has the same sign at both ends of
+/// the interval, `ConversionError` if an endpoint or the value of the
+/// function at an endpoint is not valid, or `ConvergenceError` if the root
+/// is not found within `max_iterations` iterations.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{roots, TwoFloat};
+/// let root = roots::newton(
+///     |x| (x * x - 3.0, x * 2.0),
+///     TwoFloat::from(1.0),
+///     TwoFloat::from(0.0),
+///     TwoFloat::from(2.0),
+///     TwoFloat::from(0.0),
+///     50,
+/// )
+/// .unwrap();
+///
+/// assert!((root * root - 3.0).abs() < 1e-30);
+/// ```
+pub fn newton(
+    f: impl Fn(TwoFloat) -> (TwoFloat, TwoFloat),
+    guess: TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    max_iterations: usize,
+) -> Result<TwoFloat, TwoFloatError> {
+    safeguarded(
+        |x| {
+            let (value, slope) = f(x);
+            (value, value / slope)
+        },
+        guess,
+        lower,
+        upper,
+        tolerance,
+        max_iterations,
+    )
+}
+
+/// Finds a root of a function using Halley's method, safeguarded by
+/// bisection so that the iterates remain between `lower` and `upper`. The
+/// function `f` returns the triple `(f(x), f'(x), f''(x))`.
+///
+/// # Errors
+///
+/// Returns `BracketError` if the function has the same sign at both ends of
+/// the interval, `ConversionError` if an endpoint or the value of the
+/// function at an endpoint is not valid, or `ConvergenceError` if the root
+/// is not found within `max_iterations` iterations.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{roots, TwoFloat};
+/// let root = roots::halley(
+///     |x| (x * x * x - 2.0, x * x * 3.0, x * 6.0),
+///     TwoFloat::from(1.0),
+///     TwoFloat::from(1.0),
+///     TwoFloat::from(2.0),
+///     TwoFloat::from(0.0),
+///     50,
+/// )
+/// .unwrap();
+///
+/// assert!((root * root * root - 2.0).abs() < 1e-30);
+/// ```
+pub fn halley(
+    f: impl Fn(TwoFloat) -> (TwoFloat, TwoFloat, TwoFloat),
+    guess: TwoFloat,
+    lower: TwoFloat,
+    upper: TwoFloat,
+    tolerance: TwoFloat,
+    max_iterations: usize,
+) -> Result<TwoFloat, TwoFloatError> {
+    safeguarded(
+        |x| {
+            let (value, slope, curvature) = f(x);
+            let step = value * slope * 2.0 / (slope * slope * 2.0 - value * curvature);
+            (value, step)
+        },
+        guess,
+        lower,
+        upper,
+        tolerance,
+        max_iterations,
+    )
+}
+
+#[cfg(feature = "math_funcs")]
+#[derive(Clone, Copy)]
+struct Complex {
+    re: TwoFloat,
+    im: TwoFloat,
+}
+
+#[cfg(feature = "math_funcs")]
+impl Complex {
+    fn new(re: TwoFloat, im: TwoFloat) -> Self {
+        Self { re, im }
+    }
+
+    // Returns the 1-norm, which is within a factor of sqrt(2) of the modulus
+    fn norm1(self) -> TwoFloat {
+        self.re.abs() + self.im.abs()
+    }
+
+    fn is_valid(self) -> bool {
+        self.re.is_valid() && self.im.is_valid()
+    }
+}
+
+#[cfg(feature = "math_funcs")]
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+#[cfg(feature = "math_funcs")]
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+#[cfg(feature = "math_funcs")]
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+#[cfg(feature = "math_funcs")]
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        // Smith's algorithm, which avoids overflow in the denominator
+        if rhs.re.abs() >= rhs.im.abs() {
+            let r = rhs.im / rhs.re;
+            let d = rhs.re + rhs.im * r;
+            Self::new((self.re + self.im * r) / d, (self.im - self.re * r) / d)
+        } else {
+            let r = rhs.re / rhs.im;
+            let d = rhs.re * r + rhs.im;
+            Self::new((self.re * r + self.im) / d, (self.im * r - self.re) / d)
+        }
+    }
+}
+
+// Evaluates the polynomial and its derivative at a complex point.
+#[cfg(feature = "math_funcs")]
+fn evaluate_complex(coefficients: &[TwoFloat], z: Complex) -> (Complex, Complex) {
+    let zero = Complex::new(TwoFloat::from(0.0), TwoFloat::from(0.0));
+    coefficients
+        .iter()
+        .rev()
+        .fold((zero, zero), |(value, slope), &c| {
+            (
+                value * z + Complex::new(c, TwoFloat::from(0.0)),
+                slope * z + value,
+            )
+        })
+}
+
+// Returns whether the value of the polynomial at `z` is within the rounding
+// error of its evaluation, so that `z` is an exact root of a polynomial
+// with slightly perturbed coefficients.
+#[cfg(feature = "math_funcs")]
+fn is_converged(coefficients: &[TwoFloat], z: Complex, value: Complex) -> bool {
+    let modulus = z.norm1();
+    let scale = coefficients
+        .iter()
+        .rev()
+        .fold(TwoFloat::from(0.0), |acc, c| acc * modulus + c.abs());
+    value.norm1() <= scale * (RELATIVE_TOLERANCE * 4.0)
+}
+
+// Runs a simultaneous iteration for the roots of a polynomial, where
+// `correction` computes the correction to the root with the given index.
+#[cfg(feature = "math_funcs")]
+fn simultaneous(
+    polynomial: Polynomial<'_, TwoFloat>,
+    roots: &mut [(TwoFloat, TwoFloat)],
+    max_iterations: usize,
+    correction: impl Fn(&[TwoFloat], &[(TwoFloat, TwoFloat)], usize, Complex, Complex) -> Complex,
+) -> Result<(), TwoFloatError> {
+    let coefficients = polynomial.coefficients();
+    let degree = coefficients
+        .iter()
+        .rposition(|&c| c != 0.0)
+        .unwrap_or_default();
+    let coefficients = &coefficients[..coefficients.len().min(degree + 1)];
+    assert_eq!(
+        roots.len(),
+        degree,
+        "number of roots differs from the degree of the polynomial"
+    );
+    if coefficients.iter().any(|c| !c.is_valid()) {
+        return Err(TwoFloatError::ConversionError);
+    }
+    if degree == 0 {
+        return Ok(());
+    }
+
+    // Start from points on a circle with the radius of the Fujiwara bound,
+    // offset from the real axis so that conjugate pairs can be separated
+    let leading = coefficients[degree].abs();
+    let mut radius = TwoFloat::from(0.0);
+    for (i, c) in coefficients[..degree].iter().enumerate() {
+        let ratio = c.abs() / leading;
+        let ratio = if i == 0 { ratio * 0.5 } else { ratio };
+        if ratio != 0.0 {
+            radius = radius.max(ratio.powf(TwoFloat::from(1.0) / (degree - i) as f64));
+        }
+    }
+    radius *= 2.0;
+    if radius == 0.0 {
+        radius = TwoFloat::from(1.0);
+    }
+    for (k, root) in roots.iter_mut().enumerate() {
+        let angle = TAU * (k as f64) / (degree as f64) + 0.4;
+        let (sin, cos) = angle.sin_cos();
+        *root = (radius * cos, radius * sin);
+    }
+
+    for _ in 0..max_iterations {
+        let mut all_converged = true;
+        for k in 0..degree {
+            let z = Complex::new(roots[k].0, roots[k].1);
+            let (value, slope) = evaluate_complex(coefficients, z);
+            if is_converged(coefficients, z, value) {
+                continue;
+            }
+
+            all_converged = false;
+            let next = z - correction(coefficients, roots, k, value, slope);
+            if !next.is_valid() {
+                return Err(TwoFloatError::ConvergenceError);
+            }
+            roots[k] = (next.re, next.im);
+        }
+
+        if all_converged {
+            return Ok(());
+        }
+    }
+    Err(TwoFloatError::ConvergenceError)
+}
+
+/// Finds all complex roots of a polynomial with `TwoFloat` coefficients
+/// using the Aberth–Ehrlich method, which converges cubically to simple
+/// roots. The roots are written to `roots` as `(re, im)` pairs in no
+/// particular order.
+///
+/// Trailing zero coefficients are ignored when determining the degree of
+/// the polynomial. Each root is considered converged once the value of the
+/// polynomial is within its rounding error, so multiple roots are only
+/// found to a fraction of the full precision.
+///
+/// # Errors
+///
+/// Returns `ConvergenceError` if the roots are not found within
+/// `max_iterations` iterations, or `ConversionError` if a coefficient is
+/// not valid.
+///
+/// # Panics
+///
+/// Panics if the length of `roots` is not equal to the degree of the
+/// polynomial.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{roots, Polynomial, TwoFloat};
+/// // x^3 - x^2 + x - 1 = (x - 1)(x^2 + 1)
+/// let coefficients = [-1.0, 1.0, -1.0, 1.0].map(TwoFloat::from);
+/// let mut found = [(TwoFloat::from(0.0), TwoFloat::from(0.0)); 3];
+/// roots::aberth(Polynomial::new(&coefficients), &mut found, 100).unwrap();
+///
+/// found.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+/// assert!((found[0].0).abs() < 1e-30 && (found[0].1 + 1.0).abs() < 1e-30);
+/// assert!((found[1].0 - 1.0).abs() < 1e-30 && (found[1].1).abs() < 1e-30);
+/// assert!((found[2].0).abs() < 1e-30 && (found[2].1 - 1.0).abs() < 1e-30);
+/// ```
+#[cfg(feature = "math_funcs")]
+pub fn aberth(
+    polynomial: Polynomial<'_, TwoFloat>,
+    roots: &mut [(TwoFloat, TwoFloat)],
+    max_iterations: usize,
+) -> Result<(), TwoFloatError> {
+    simultaneous(
+        polynomial,
+        roots,
+        max_iterations,
+        |_, roots, k, value, slope| {
+            let z = Complex::new(roots[k].0, roots[k].1);
+            let one = Complex::new(TwoFloat::from(1.0), TwoFloat::from(0.0));
+            let mut sum = Complex::new(TwoFloat::from(0.0), TwoFloat::from(0.0));
+            for (j, &(re, im)) in roots.iter().enumerate() {
+                if j != k {
+                    sum = sum + one / (z - Complex::new(re, im));
+                }
+            }
+            let ratio = value / slope;
+            ratio / (one - ratio * sum)
+        },
+    )
+}
+
+/// Finds all complex roots of a polynomial with `TwoFloat` coefficients
+/// using the Durand–Kerner (Weierstrass) method, which converges
+/// quadratically to simple roots. The roots are written to `roots` as
+/// `(re, im)` pairs in no particular order.
+///
+/// Trailing zero coefficients are ignored when determining the degree of
+/// the polynomial. Each root is considered converged once the value of the
+/// polynomial is within its rounding error, so multiple roots are only
+/// found to a fraction of the full precision.
+///
+/// # Errors
+///
+/// Returns `ConvergenceError` if the roots are not found within
+/// `max_iterations` iterations, or `ConversionError` if a coefficient is
+/// not valid.
+///
+/// # Panics
+///
+/// Panics if the length of `roots` is not equal to the degree of the
+/// polynomial.
+///
+/// # Examples
+///
+/// ```
+/// # use twofloat::{roots, Polynomial, TwoFloat};
+/// // x^2 - 2
+/// let coefficients = [-2.0, 0.0, 1.0].map(TwoFloat::from);
+/// let mut found = [(TwoFloat::from(0.0), TwoFloat::from(0.0)); 2];
+/// roots::durand_kerner(Polynomial::new(&coefficients), &mut found, 100).unwrap();
+///
+/// let root = found[0].0.abs();
+/// assert!((root - TwoFloat::from(2.0).sqrt()).abs() < 1e-30);
+/// ```
+#[cfg(feature = "math_funcs")]
+pub fn durand_kerner(
+    polynomial: Polynomial<'_, TwoFloat>,
+    roots: &mut [(TwoFloat, TwoFloat)],
+    max_iterations: usize,
+) -> Result<(), TwoFloatError> {
+    simultaneous(
+        polynomial,
+        roots,
+        max_iterations,
+        |coefficients, roots, k, value, _| {
+            let z = Complex::new(roots[k].0, roots[k].1);
+            let leading = coefficients[coefficients.len() - 1];
+            let mut product = Complex::new(leading, TwoFloat::from(0.0));
+            for (j, &(re, im)) in roots.iter().enumerate() {
+                if j != k {
+                    product = product * (z - Complex::new(re, im));
+                }
+            }
+            value / product
+        },
+    )
+}
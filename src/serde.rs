@@ -0,0 +1,348 @@
+//! Helper modules for use with `#[serde(with = "...")]`, selecting the
+//! representation of a `TwoFloat` field.
+//!
+//! By default a `TwoFloat` is serialized as a struct with the fields `hi`
+//! and `lo`. The modules in this module provide alternative
+//! representations, and all of them reject values that do not satisfy the
+//! `no_overlap` condition when deserializing.
+//!
+//! # Examples
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! # use twofloat::TwoFloat;
+//! #[derive(Serialize, Deserialize)]
+//! struct Measurement {
+//!     #[serde(with = "twofloat::serde::as_string")]
+//!     value: TwoFloat,
+//!     #[serde(with = "twofloat::serde::as_tuple")]
+//!     error: TwoFloat,
+//!     #[serde(with = "twofloat::serde::flexible")]
+//!     scale: TwoFloat,
+//! }
+//! ```
+
+use core::{convert::TryFrom, str};
+
+use serde::de::{self, Unexpected};
+
+use crate::{
+    format::parse_decimal, serde_helper::TwoFloatDeserializeHelper, RoundingMode, TwoFloat,
+    TwoFloatError,
+};
+
+// Significant digits tried first when converting to a string. Trailing zeros
+// are removed, so exactly representable values may use fewer digits.
+const MIN_STRING_DIGITS: usize = 32;
+
+// Longest string produced by `DecimalString`
+const MAX_STRING_LEN: usize = 64;
+
+// Checks a pair of words in the same way as the default representation
+fn validate<E: de::Error>(hi: f64, lo: f64) -> Result<TwoFloat, E> {
+    TwoFloat::try_from(TwoFloatDeserializeHelper { hi, lo }).map_err(E::custom)
+}
+
+// Decimal string representation with the fewest digits, up to
+// `MAX_DECIMAL_DIGITS`, that converts back to the value rounded to 106 bits
+struct DecimalString {
+    bytes: [u8; MAX_STRING_LEN],
+    len: usize,
+}
+
+impl DecimalString {
+    fn new(value: TwoFloat) -> Result<Self, TwoFloatError> {
+        // Conversions from decimal round to 106 bits, so that is the value
+        // the string should convert back to
+        let (mantissa, exponent, sign) = value.integer_decode();
+        let target = TwoFloat::from(mantissa).ldexp(exponent as i32) * sign as f64;
+
+        let mut count = MIN_STRING_DIGITS;
+        let (negative, digits, exp10) = loop {
+            let rounded = value.to_decimal(count, RoundingMode::NearestEven)?;
+            let (negative, digits, exp10) = rounded;
+            if count == TwoFloat::MAX_DECIMAL_DIGITS
+                || TwoFloat::from_decimal(negative, &digits[..count], exp10)? == target
+            {
+                break rounded;
+            }
+            count += 1;
+        };
+
+        let len = digits
+            .iter()
+            .rposition(|&digit| digit != 0)
+            .map_or(0, |last| last + 1);
+        let lead = if len == 0 {
+            0
+        } else {
+            exp10 + count as i32 - 1
+        };
+
+        let mut result = Self {
+            bytes: [0; MAX_STRING_LEN],
+            len: 0,
+        };
+        if negative {
+            result.push(b'-');
+        }
+
+        let digit = |index: i32| {
+            if index >= 0 && (index as usize) < len {
+                b'0' + digits[index as usize]
+            } else {
+                b'0'
+            }
+        };
+        if (-6..21).contains(&lead) {
+            // Fixed-point notation
+            for position in (0..=lead.max(0)).rev() {
+                result.push(digit(lead - position));
+            }
+            let fraction = len as i32 - 1 - lead;
+            if fraction > 0 {
+                result.push(b'.');
+                for position in 1..=fraction {
+                    result.push(digit(lead + position));
+                }
+            }
+        } else {
+            // Scientific notation
+            result.push(digit(0));
+            if len > 1 {
+                result.push(b'.');
+                for index in 1..len as i32 {
+                    result.push(digit(index));
+                }
+            }
+            result.push(b'e');
+            if lead < 0 {
+                result.push(b'-');
+            }
+            let exponent = lead.unsigned_abs();
+            for power in [100, 10, 1] {
+                if exponent >= power || power == 1 {
+                    result.push(b'0' + (exponent / power % 10) as u8);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn as_str(&self) -> &str {
+        str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+// Converts a parsed string to a value, rejecting strings that are not
+// decimal numbers and values that overflow
+fn from_str<E: de::Error>(text: &str) -> Result<TwoFloat, E> {
+    let value = parse_decimal(text)
+        .ok_or_else(|| E::invalid_value(Unexpected::Str(text), &"a decimal number"))?;
+    validate(value.hi, value.lo)
+}
+
+/// Serializes a `TwoFloat` as a decimal string with enough significant
+/// digits to convert back to the same value, such as `"1.5"` or
+/// `"0.100000000000000005551115123125783"`. Values with more than 106
+/// significant bits, where the low word is much smaller than the high word,
+/// convert back to a nearby value with 106 significant bits.
+///
+/// Deserialization accepts decimal numbers with an optional sign, decimal
+/// point and exponent, correctly rounded to the nearest `TwoFloat`.
+///
+/// # Examples
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use twofloat::TwoFloat;
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "twofloat::serde::as_string")]
+///     tolerance: TwoFloat,
+/// }
+/// ```
+pub mod as_string {
+    use serde::{de, ser, Deserializer, Serializer};
+
+    use super::{from_str, DecimalString};
+    use crate::TwoFloat;
+
+    /// Serializes the value as a decimal string.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not valid.
+    pub fn serialize<S: Serializer>(value: &TwoFloat, serializer: S) -> Result<S::Ok, S::Error> {
+        if !value.is_valid() {
+            return Err(ser::Error::custom(crate::TwoFloatError::ConversionError));
+        }
+        let decimal = DecimalString::new(*value).map_err(ser::Error::custom)?;
+        serializer.serialize_str(decimal.as_str())
+    }
+
+    /// Deserializes a value from a decimal string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TwoFloat, D::Error> {
+        struct StringVisitor;
+
+        impl<'de> de::Visitor<'de> for StringVisitor {
+            type Value = TwoFloat;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("a decimal string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<TwoFloat, E> {
+                from_str(v)
+            }
+        }
+
+        deserializer.deserialize_str(StringVisitor)
+    }
+}
+
+/// Serializes a `TwoFloat` as a tuple `[hi, lo]` of its high and low
+/// words, which is exact and more compact than the default struct
+/// representation.
+///
+/// # Examples
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use twofloat::TwoFloat;
+/// #[derive(Serialize, Deserialize)]
+/// struct Sample {
+///     #[serde(with = "twofloat::serde::as_tuple")]
+///     value: TwoFloat,
+/// }
+/// ```
+pub mod as_tuple {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::validate;
+    use crate::TwoFloat;
+
+    /// Serializes the value as a tuple of its high and low words.
+    pub fn serialize<S: Serializer>(value: &TwoFloat, serializer: S) -> Result<S::Ok, S::Error> {
+        (value.hi, value.lo).serialize(serializer)
+    }
+
+    /// Deserializes a value from a tuple of its high and low words.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TwoFloat, D::Error> {
+        let (hi, lo) = <(f64, f64)>::deserialize(deserializer)?;
+        validate(hi, lo)
+    }
+}
+
+/// Serializes a `TwoFloat` as a single `f64`, discarding the low word.
+/// This is intended for interoperability with consumers that only accept
+/// plain numbers.
+///
+/// Deserialization accepts any finite number, which is converted exactly.
+///
+/// # Examples
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use twofloat::TwoFloat;
+/// #[derive(Serialize, Deserialize)]
+/// struct Summary {
+///     #[serde(with = "twofloat::serde::as_f64_lossy")]
+///     mean: TwoFloat,
+/// }
+/// ```
+pub mod as_f64_lossy {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::validate;
+    use crate::TwoFloat;
+
+    /// Serializes the value rounded to an `f64`.
+    pub fn serialize<S: Serializer>(value: &TwoFloat, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(value.hi)
+    }
+
+    /// Deserializes a value from an `f64`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TwoFloat, D::Error> {
+        validate(f64::deserialize(deserializer)?, 0.0)
+    }
+}
+
+/// Serializes a `TwoFloat` using the default struct representation, but
+/// accepts any of the representations when deserializing: a struct or map
+/// with the fields `hi` and `lo`, a sequence `[hi, lo]`, a plain number or
+/// a decimal string. This is useful for configuration files written by
+/// hand.
+///
+/// This requires a self-describing format such as JSON or TOML.
+///
+/// # Examples
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use twofloat::TwoFloat;
+/// #[derive(Serialize, Deserialize)]
+/// struct Settings {
+///     #[serde(with = "twofloat::serde::flexible")]
+///     threshold: TwoFloat,
+/// }
+/// ```
+pub mod flexible {
+    use serde::{
+        de::{self, value::MapAccessDeserializer, value::SeqAccessDeserializer},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::{from_str, validate};
+    use crate::TwoFloat;
+
+    /// Serializes the value using the default representation.
+    pub fn serialize<S: Serializer>(value: &TwoFloat, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    /// Deserializes a value from a struct, sequence, number or string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TwoFloat, D::Error> {
+        struct FlexibleVisitor;
+
+        impl<'de> de::Visitor<'de> for FlexibleVisitor {
+            type Value = TwoFloat;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("a TwoFloat struct, sequence, number or decimal string")
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<TwoFloat, E> {
+                validate(v, 0.0)
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<TwoFloat, E> {
+                Ok(TwoFloat::from(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<TwoFloat, E> {
+                Ok(TwoFloat::from(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<TwoFloat, E> {
+                from_str(v)
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<TwoFloat, A::Error> {
+                TwoFloat::deserialize(SeqAccessDeserializer::new(seq))
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<TwoFloat, A::Error> {
+                TwoFloat::deserialize(MapAccessDeserializer::new(map))
+            }
+        }
+
+        deserializer.deserialize_any(FlexibleVisitor)
+    }
+}
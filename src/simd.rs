@@ -0,0 +1,326 @@
+use core::{
+    array,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::{math_util::mathfn, TwoFloat};
+
+// Lane-wise versions of the error-free transforms and arithmetic algorithms
+// from Joldes et al. (2017) used by the scalar operators, operating on
+// separate arrays of high and low words. Each step is written as a whole-array
+// operation so that the compiler can vectorize it, and the operations are
+// performed in the same order as for `TwoFloat` so that every lane gives
+// identical results.
+
+type Lanes<const N: usize> = [f64; N];
+
+#[inline(always)]
+fn fast_two_sum<const N: usize>(a: Lanes<N>, b: Lanes<N>) -> (Lanes<N>, Lanes<N>) {
+    let s: Lanes<N> = array::from_fn(|i| a[i] + b[i]);
+    let z: Lanes<N> = array::from_fn(|i| s[i] - a[i]);
+    (s, array::from_fn(|i| b[i] - z[i]))
+}
+
+#[inline(always)]
+fn two_sum<const N: usize>(a: Lanes<N>, b: Lanes<N>) -> (Lanes<N>, Lanes<N>) {
+    let s: Lanes<N> = array::from_fn(|i| a[i] + b[i]);
+    let aa: Lanes<N> = array::from_fn(|i| s[i] - b[i]);
+    let bb: Lanes<N> = array::from_fn(|i| s[i] - aa[i]);
+    let da: Lanes<N> = array::from_fn(|i| a[i] - aa[i]);
+    let db: Lanes<N> = array::from_fn(|i| b[i] - bb[i]);
+    (s, array::from_fn(|i| da[i] + db[i]))
+}
+
+#[inline(always)]
+fn two_diff<const N: usize>(a: Lanes<N>, b: Lanes<N>) -> (Lanes<N>, Lanes<N>) {
+    let s: Lanes<N> = array::from_fn(|i| a[i] - b[i]);
+    let aa: Lanes<N> = array::from_fn(|i| s[i] + b[i]);
+    let bb: Lanes<N> = array::from_fn(|i| s[i] - aa[i]);
+    let da: Lanes<N> = array::from_fn(|i| a[i] - aa[i]);
+    let db: Lanes<N> = array::from_fn(|i| b[i] + bb[i]);
+    (s, array::from_fn(|i| da[i] - db[i]))
+}
+
+#[inline(always)]
+fn two_prod<const N: usize>(a: Lanes<N>, b: Lanes<N>) -> (Lanes<N>, Lanes<N>) {
+    let p: Lanes<N> = array::from_fn(|i| a[i] * b[i]);
+    (p, array::from_fn(|i| mathfn::fma(a[i], b[i], -p[i])))
+}
+
+// Algorithm 6
+#[inline(always)]
+fn add<const N: usize>(x: (Lanes<N>, Lanes<N>), y: (Lanes<N>, Lanes<N>)) -> (Lanes<N>, Lanes<N>) {
+    let (sh, sl) = two_sum(x.0, y.0);
+    let (th, tl) = two_sum(x.1, y.1);
+    let c = array::from_fn(|i| sl[i] + th[i]);
+    let (vh, vl) = fast_two_sum(sh, c);
+    let w = array::from_fn(|i| tl[i] + vl[i]);
+    fast_two_sum(vh, w)
+}
+
+// Algorithm 6 modified for a negative right-hand side
+#[inline(always)]
+fn sub<const N: usize>(x: (Lanes<N>, Lanes<N>), y: (Lanes<N>, Lanes<N>)) -> (Lanes<N>, Lanes<N>) {
+    let (sh, sl) = two_diff(x.0, y.0);
+    let (th, tl) = two_diff(x.1, y.1);
+    let c = array::from_fn(|i| sl[i] + th[i]);
+    let (vh, vl) = fast_two_sum(sh, c);
+    let w = array::from_fn(|i| tl[i] + vl[i]);
+    fast_two_sum(vh, w)
+}
+
+// Algorithm 12
+#[inline(always)]
+fn mul<const N: usize>(x: (Lanes<N>, Lanes<N>), y: (Lanes<N>, Lanes<N>)) -> (Lanes<N>, Lanes<N>) {
+    let (ch, cl1) = two_prod(x.0, y.0);
+    let tl0: Lanes<N> = array::from_fn(|i| x.1[i] * y.1[i]);
+    let tl1: Lanes<N> = array::from_fn(|i| mathfn::fma(x.0[i], y.1[i], tl0[i]));
+    let cl2: Lanes<N> = array::from_fn(|i| mathfn::fma(x.1[i], y.0[i], tl1[i]));
+    let cl3 = array::from_fn(|i| cl1[i] + cl2[i]);
+    fast_two_sum(ch, cl3)
+}
+
+// Algorithm 18
+#[inline(always)]
+fn div<const N: usize>(x: (Lanes<N>, Lanes<N>), y: (Lanes<N>, Lanes<N>)) -> (Lanes<N>, Lanes<N>) {
+    let th: Lanes<N> = array::from_fn(|i| y.0[i].recip());
+    let rh = array::from_fn(|i| mathfn::fma(-y.0[i], th[i], 1.0));
+    let rl = array::from_fn(|i| -(y.1[i] * th[i]));
+    let (eh, el) = fast_two_sum(rh, rl);
+
+    // d = e * th, using Algorithm 9
+    let (dh, dl1) = two_prod(eh, th);
+    let dl3 = array::from_fn(|i| mathfn::fma(el[i], th[i], dl1[i]));
+    let (dh, dl) = fast_two_sum(dh, dl3);
+
+    // m = d + th, using Algorithm 4
+    let (sh, sl) = two_sum(dh, th);
+    let v = array::from_fn(|i| dl[i] + sl[i]);
+    let m = fast_two_sum(sh, v);
+
+    mul(x, m)
+}
+
+macro_rules! simd_binary_op {
+    ($name:ident, $trait:ident::$op:ident, $assign_trait:ident::$assign_op:ident) => {
+        impl $trait for $name {
+            type Output = Self;
+
+            #[inline]
+            fn $op(self, rhs: Self) -> Self {
+                let (hi, lo) = $op((self.hi, self.lo), (rhs.hi, rhs.lo));
+                Self { hi, lo }
+            }
+        }
+
+        impl $assign_trait for $name {
+            #[inline]
+            fn $assign_op(&mut self, rhs: Self) {
+                *self = (*self).$op(rhs);
+            }
+        }
+    };
+}
+
+macro_rules! simd_type {
+    ($(#[$meta:meta])* $name:ident, $lanes:literal) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct $name {
+            hi: [f64; $lanes],
+            lo: [f64; $lanes],
+        }
+
+        impl $name {
+            /// The number of lanes.
+            pub const LANES: usize = $lanes;
+
+            /// Creates a value with all lanes set to `value`.
+            pub fn splat(value: TwoFloat) -> Self {
+                Self {
+                    hi: [value.hi; $lanes],
+                    lo: [value.lo; $lanes],
+                }
+            }
+
+            /// Creates a value from an array of `TwoFloat` values.
+            pub fn from_array(values: [TwoFloat; $lanes]) -> Self {
+                Self {
+                    hi: array::from_fn(|i| values[i].hi),
+                    lo: array::from_fn(|i| values[i].lo),
+                }
+            }
+
+            /// Returns the lanes as an array of `TwoFloat` values.
+            pub fn to_array(self) -> [TwoFloat; $lanes] {
+                array::from_fn(|i| self.extract(i))
+            }
+
+            /// Loads the lanes from the start of a slice.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the slice is shorter than the number of lanes.
+            pub fn load(values: &[TwoFloat]) -> Self {
+                let values = &values[..$lanes];
+                Self {
+                    hi: array::from_fn(|i| values[i].hi),
+                    lo: array::from_fn(|i| values[i].lo),
+                }
+            }
+
+            /// Stores the lanes to the start of a slice.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the slice is shorter than the number of lanes.
+            pub fn store(self, values: &mut [TwoFloat]) {
+                values[..$lanes].copy_from_slice(&self.to_array());
+            }
+
+            /// Returns the value in the lane at `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not less than the number of lanes.
+            pub fn extract(self, index: usize) -> TwoFloat {
+                TwoFloat {
+                    hi: self.hi[index],
+                    lo: self.lo[index],
+                }
+            }
+
+            /// Replaces the value in the lane at `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not less than the number of lanes.
+            pub fn replace(&mut self, index: usize, value: TwoFloat) {
+                self.hi[index] = value.hi;
+                self.lo[index] = value.lo;
+            }
+
+            /// Returns the high words of each lane.
+            pub fn hi(self) -> [f64; $lanes] {
+                self.hi
+            }
+
+            /// Returns the low words of each lane.
+            pub fn lo(self) -> [f64; $lanes] {
+                self.lo
+            }
+
+            /// Returns the sum of all lanes, added in lane order.
+            pub fn sum(self) -> TwoFloat {
+                self.to_array()
+                    .iter()
+                    .fold(TwoFloat::from(0.0), |acc, &x| acc + x)
+            }
+
+            /// Returns the absolute value of each lane.
+            pub fn abs(self) -> Self {
+                self.map(|x| x.abs())
+            }
+
+            /// Returns the square root of each lane. The result in each lane
+            /// is identical to that of [`TwoFloat::sqrt`].
+            #[cfg(feature = "math_funcs")]
+            pub fn sqrt(self) -> Self {
+                self.map(TwoFloat::sqrt)
+            }
+
+            /// Returns `e^x` for each lane. The result in each lane is
+            /// identical to that of [`TwoFloat::exp`].
+            #[cfg(feature = "math_funcs")]
+            pub fn exp(self) -> Self {
+                self.map(TwoFloat::exp)
+            }
+
+            #[inline]
+            fn map(self, f: impl Fn(TwoFloat) -> TwoFloat) -> Self {
+                let mut result = self;
+                for i in 0..$lanes {
+                    let value = f(self.extract(i));
+                    result.hi[i] = value.hi;
+                    result.lo[i] = value.lo;
+                }
+                result
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::splat(TwoFloat::from(0.0))
+            }
+        }
+
+        impl From<[TwoFloat; $lanes]> for $name {
+            fn from(values: [TwoFloat; $lanes]) -> Self {
+                Self::from_array(values)
+            }
+        }
+
+        impl From<$name> for [TwoFloat; $lanes] {
+            fn from(value: $name) -> Self {
+                value.to_array()
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self {
+                    hi: self.hi.map(|x| -x),
+                    lo: self.lo.map(|x| -x),
+                }
+            }
+        }
+
+        simd_binary_op!($name, Add::add, AddAssign::add_assign);
+        simd_binary_op!($name, Sub::sub, SubAssign::sub_assign);
+        simd_binary_op!($name, Mul::mul, MulAssign::mul_assign);
+        simd_binary_op!($name, Div::div, DivAssign::div_assign);
+    };
+}
+
+simd_type! {
+    /// Four `TwoFloat` values stored as separate arrays of high and low
+    /// words, so that lane-parallel arithmetic can be vectorized by the
+    /// compiler. Each lane gives identical results to the corresponding
+    /// `TwoFloat` operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{TwoFloat, TwoFloatX4};
+    /// let values = [1.0, 2.0, 3.0, 4.0].map(TwoFloat::from);
+    /// let a = TwoFloatX4::from_array(values);
+    /// let b = a * a + TwoFloatX4::splat(TwoFloat::from(1.0));
+    ///
+    /// assert_eq!(b.extract(2), 10.0);
+    /// assert_eq!(b.sum(), 34.0);
+    /// ```
+    TwoFloatX4, 4
+}
+
+simd_type! {
+    /// Eight `TwoFloat` values stored as separate arrays of high and low
+    /// words, so that lane-parallel arithmetic can be vectorized by the
+    /// compiler. Each lane gives identical results to the corresponding
+    /// `TwoFloat` operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use twofloat::{TwoFloat, TwoFloatX8};
+    /// let values: Vec<TwoFloat> = (1..=8).map(|i| TwoFloat::from(i) / 3.0).collect();
+    /// let a = TwoFloatX8::load(&values);
+    /// let mut output = [TwoFloat::from(0.0); 8];
+    /// (a * TwoFloatX8::splat(TwoFloat::from(3.0))).store(&mut output);
+    ///
+    /// assert!((output[4] - 5.0).abs() < 1e-30);
+    /// ```
+    TwoFloatX8, 8
+}
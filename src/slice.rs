@@ -0,0 +1,194 @@
+//! Elementwise operations on slices of `TwoFloat` values.
+//!
+//! Each operation is available in an out-of-place form, which writes the
+//! results to an output slice, and an in-place form with an `_assign`
+//! suffix, which overwrites its first argument. The arithmetic operations
+//! process the slices in blocks of [`TwoFloatX4`] so that the compiler can
+//! vectorize the loops, while `mul_add`, `sqrt`, `exp` and `ln` evaluate the
+//! scalar `TwoFloat` function on one element at a time. Every element of the
+//! result is identical to that of the corresponding `TwoFloat` operation.
+//!
+//! # Panics
+//!
+//! All functions panic if the slices passed to them have different lengths.
+//!
+//! # Examples
+//!
+//! ```
+//! # use twofloat::{slice, TwoFloat};
+//! let a = [1.0, 2.0, 3.0].map(TwoFloat::from);
+//! let b = [0.5, 0.25, 0.125].map(TwoFloat::from);
+//! let mut c = [TwoFloat::from(0.0); 3];
+//!
+//! slice::mul(&a, &b, &mut c);
+//! assert_eq!(c[2], 0.375);
+//!
+//! slice::axpy(TwoFloat::from(2.0), &a, &mut c);
+//! assert_eq!(c[2], 6.375);
+//! ```
+
+use crate::{TwoFloat, TwoFloatX4};
+
+const LANES: usize = TwoFloatX4::LANES;
+
+fn check_len(a: &[TwoFloat], b: &[TwoFloat]) {
+    assert_eq!(a.len(), b.len(), "slices have different lengths");
+}
+
+fn unary_impl(
+    input: &[TwoFloat],
+    out: &mut [TwoFloat],
+    vector: impl Fn(TwoFloatX4) -> TwoFloatX4,
+    scalar: impl Fn(TwoFloat) -> TwoFloat,
+) {
+    check_len(input, out);
+    let split = input.len() - input.len() % LANES;
+    for (x, z) in input[..split]
+        .chunks_exact(LANES)
+        .zip(out[..split].chunks_exact_mut(LANES))
+    {
+        vector(TwoFloatX4::load(x)).store(z);
+    }
+    for (x, z) in input[split..].iter().zip(&mut out[split..]) {
+        *z = scalar(*x);
+    }
+}
+
+fn unary_assign_impl(
+    values: &mut [TwoFloat],
+    vector: impl Fn(TwoFloatX4) -> TwoFloatX4,
+    scalar: impl Fn(TwoFloat) -> TwoFloat,
+) {
+    let split = values.len() - values.len() % LANES;
+    for z in values[..split].chunks_exact_mut(LANES) {
+        vector(TwoFloatX4::load(z)).store(z);
+    }
+    for z in &mut values[split..] {
+        *z = scalar(*z);
+    }
+}
+
+fn binary_impl(
+    a: &[TwoFloat],
+    b: &[TwoFloat],
+    out: &mut [TwoFloat],
+    vector: impl Fn(TwoFloatX4, TwoFloatX4) -> TwoFloatX4,
+    scalar: impl Fn(TwoFloat, TwoFloat) -> TwoFloat,
+) {
+    check_len(a, b);
+    check_len(a, out);
+    let split = a.len() - a.len() % LANES;
+    for ((x, y), z) in a[..split]
+        .chunks_exact(LANES)
+        .zip(b[..split].chunks_exact(LANES))
+        .zip(out[..split].chunks_exact_mut(LANES))
+    {
+        vector(TwoFloatX4::load(x), TwoFloatX4::load(y)).store(z);
+    }
+    for ((x, y), z) in a[split..].iter().zip(&b[split..]).zip(&mut out[split..]) {
+        *z = scalar(*x, *y);
+    }
+}
+
+fn binary_assign_impl(
+    a: &mut [TwoFloat],
+    b: &[TwoFloat],
+    vector: impl Fn(TwoFloatX4, TwoFloatX4) -> TwoFloatX4,
+    scalar: impl Fn(TwoFloat, TwoFloat) -> TwoFloat,
+) {
+    check_len(a, b);
+    let split = a.len() - a.len() % LANES;
+    for (z, y) in a[..split]
+        .chunks_exact_mut(LANES)
+        .zip(b[..split].chunks_exact(LANES))
+    {
+        vector(TwoFloatX4::load(z), TwoFloatX4::load(y)).store(z);
+    }
+    for (z, y) in a[split..].iter_mut().zip(&b[split..]) {
+        *z = scalar(*z, *y);
+    }
+}
+
+macro_rules! binary_slice_ops {
+    ($($op:tt, $name:ident, $assign_name:ident, $desc:literal;)*) => {
+        $(
+            #[doc = concat!("Computes `out[i] = a[i] ", stringify!($op), " b[i]` for each element.")]
+            pub fn $name(a: &[TwoFloat], b: &[TwoFloat], out: &mut [TwoFloat]) {
+                binary_impl(a, b, out, |x, y| x $op y, |x, y| x $op y);
+            }
+
+            #[doc = $desc]
+            pub fn $assign_name(a: &mut [TwoFloat], b: &[TwoFloat]) {
+                binary_assign_impl(a, b, |x, y| x $op y, |x, y| x $op y);
+            }
+        )*
+    };
+}
+
+binary_slice_ops! {
+    +, add, add_assign, "Adds each element of `b` to the corresponding element of `a`.";
+    -, sub, sub_assign, "Subtracts each element of `b` from the corresponding element of `a`.";
+    *, mul, mul_assign, "Multiplies each element of `a` by the corresponding element of `b`.";
+    /, div, div_assign, "Divides each element of `a` by the corresponding element of `b`.";
+}
+
+/// Computes `out[i] = a[i] * factor` for each element.
+pub fn scale(a: &[TwoFloat], factor: TwoFloat, out: &mut [TwoFloat]) {
+    let factors = TwoFloatX4::splat(factor);
+    unary_impl(a, out, |x| x * factors, |x| x * factor);
+}
+
+/// Multiplies each element of `a` by `factor` in place.
+pub fn scale_assign(a: &mut [TwoFloat], factor: TwoFloat) {
+    let factors = TwoFloatX4::splat(factor);
+    unary_assign_impl(a, |x| x * factors, |x| x * factor);
+}
+
+/// Computes `y[i] = alpha * x[i] + y[i]` for each element.
+pub fn axpy(alpha: TwoFloat, x: &[TwoFloat], y: &mut [TwoFloat]) {
+    let alphas = TwoFloatX4::splat(alpha);
+    binary_assign_impl(y, x, |y, x| alphas * x + y, |y, x| alpha * x + y);
+}
+
+/// Computes `out[i] = a[i].mul_add(b[i], c[i])` for each element, so that
+/// each product is calculated exactly before adding. The elements are
+/// evaluated one at a time using [`TwoFloat::mul_add`].
+pub fn mul_add(a: &[TwoFloat], b: &[TwoFloat], c: &[TwoFloat], out: &mut [TwoFloat]) {
+    check_len(a, b);
+    check_len(a, c);
+    check_len(a, out);
+    for (((x, y), z), result) in a.iter().zip(b).zip(c).zip(out) {
+        *result = x.mul_add(*y, *z);
+    }
+}
+
+#[cfg(feature = "math_funcs")]
+macro_rules! unary_slice_ops {
+    ($($name:ident, $assign_name:ident;)*) => {
+        $(
+            #[doc = concat!("Computes `out[i] = input[i].", stringify!($name), "()` for each element.")]
+            #[doc = ""]
+            #[doc = concat!("The elements are evaluated one at a time using [`TwoFloat::", stringify!($name), "`].")]
+            pub fn $name(input: &[TwoFloat], out: &mut [TwoFloat]) {
+                check_len(input, out);
+                for (x, z) in input.iter().zip(out) {
+                    *z = x.$name();
+                }
+            }
+
+            #[doc = concat!("Replaces each element with its [`TwoFloat::", stringify!($name), "`] in place.")]
+            pub fn $assign_name(values: &mut [TwoFloat]) {
+                for z in values {
+                    *z = z.$name();
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "math_funcs")]
+unary_slice_ops! {
+    sqrt, sqrt_assign;
+    exp, exp_assign;
+    ln, ln_assign;
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "approx")]
+
+use approx::{
+    abs_diff_eq, assert_abs_diff_eq, assert_relative_eq, assert_ulps_eq, relative_eq, ulps_eq,
+};
+use twofloat::TwoFloat;
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+// Returns the value rounded to 106 significant bits.
+fn round_106_bits(value: TwoFloat) -> TwoFloat {
+    let (mantissa, exponent, sign) = value.integer_decode();
+    TwoFloat::from(mantissa).ldexp(exponent as i32) * sign as f64
+}
+
+#[test]
+fn abs_diff_eq_test() {
+    repeated_test(|| {
+        let a = get_valid_twofloat(|x, _| x.abs() < 1e15);
+        let b = a + 0.5;
+
+        assert_abs_diff_eq!(a, a);
+        assert_abs_diff_eq!(a, b, epsilon = TwoFloat::from(1.0));
+        assert!(!abs_diff_eq!(a, b, epsilon = TwoFloat::from(0.25)));
+    });
+
+    let one = TwoFloat::from(1.0);
+    assert_abs_diff_eq!(one, one + 2f64.powi(-105));
+    assert!(!abs_diff_eq!(one, one + 2f64.powi(-104)));
+    assert!(!abs_diff_eq!(TwoFloat::NAN, TwoFloat::NAN));
+}
+
+#[test]
+fn relative_eq_test() {
+    repeated_test(|| {
+        let a = get_valid_twofloat(|x, _| x.abs() > 1e-290 && x.abs() < 1e290);
+        let close = a + a * 1e-20;
+        let far = a + a * 1e-10;
+        let zero = TwoFloat::from(0.0);
+
+        assert_relative_eq!(
+            a,
+            close,
+            epsilon = zero,
+            max_relative = TwoFloat::from(1e-19)
+        );
+        assert!(!relative_eq!(
+            a,
+            far,
+            epsilon = zero,
+            max_relative = TwoFloat::from(1e-11)
+        ));
+        assert!(!relative_eq!(a, -a, epsilon = zero));
+    });
+
+    let third = TwoFloat::from(1.0) / 3.0;
+    assert_relative_eq!(third * 3.0, TwoFloat::from(1.0));
+    assert_relative_eq!(TwoFloat::INFINITY, TwoFloat::INFINITY);
+    assert!(!relative_eq!(TwoFloat::INFINITY, TwoFloat::NEG_INFINITY));
+    assert!(!relative_eq!(TwoFloat::INFINITY, TwoFloat::MAX));
+}
+
+#[test]
+fn ulps_eq_test() {
+    repeated_test(|| {
+        let a = round_106_bits(get_valid_twofloat(|x, _| {
+            x.abs() > 1e-290 && x.abs() < 1e290
+        }));
+        let mut b = a;
+        for _ in 0..4 {
+            b = b.next_up();
+        }
+
+        assert_ulps_eq!(a, b, epsilon = TwoFloat::from(0.0));
+        assert_ulps_eq!(b, a, epsilon = TwoFloat::from(0.0));
+        assert!(!ulps_eq!(
+            a,
+            b.next_up().next_up(),
+            epsilon = TwoFloat::from(0.0)
+        ));
+        assert!(ulps_eq!(a, b, epsilon = TwoFloat::from(0.0), max_ulps = 4));
+        assert!(!ulps_eq!(a, b, epsilon = TwoFloat::from(0.0), max_ulps = 2));
+    });
+
+    // One ulp of 1 is 2^-105, but the ulp just below 1 is 2^-106
+    let one = TwoFloat::from(1.0);
+    let below = one - 2f64.powi(-104);
+    assert_ulps_eq!(one, below, epsilon = TwoFloat::from(0.0));
+    assert!(!ulps_eq!(
+        one,
+        below,
+        epsilon = TwoFloat::from(0.0),
+        max_ulps = 2
+    ));
+    assert!(!ulps_eq!(
+        TwoFloat::from(1e-300),
+        TwoFloat::from(-1e-300),
+        epsilon = TwoFloat::from(0.0)
+    ));
+    assert_ulps_eq!(TwoFloat::NEG_INFINITY, TwoFloat::NEG_INFINITY);
+    assert!(!ulps_eq!(TwoFloat::INFINITY, TwoFloat::NEG_INFINITY));
+}
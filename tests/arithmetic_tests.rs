@@ -2,6 +2,7 @@
 
 use core::{convert::TryFrom, fmt::Debug};
 
+use hexf::hexf64;
 use rand::Rng;
 
 use twofloat::{no_overlap, TwoFloat};
@@ -104,6 +105,118 @@ fn new_div_test() {
     });
 }
 
+#[test]
+fn div_residual_test() {
+    repeated_test(|| {
+        let (a, b) = loop {
+            let a = get_valid_twofloat(|x, _| x.abs() > 1e-100 && x.abs() < 1e100);
+            let b = get_valid_twofloat(|x, _| x.abs() > 1e-100 && x.abs() < 1e100);
+            if (a / b).is_valid() {
+                break (a, b);
+            }
+        };
+
+        let quotients = [a / b, {
+            let mut x = a;
+            x /= b;
+            x
+        }];
+        for quotient in &quotients {
+            let residual = quotient.mul_add(b, -a);
+            assert!(
+                residual.abs() <= a.abs() * 1e-30,
+                "{:?} / {:?} has residual {:?}",
+                a,
+                b,
+                residual
+            );
+        }
+
+        let quotient = a.hi() / b;
+        let residual = quotient.mul_add(b, TwoFloat::from(-a.hi()));
+        assert!(
+            residual.abs() <= a.hi().abs() * 1e-30,
+            "{} / {:?} has residual {:?}",
+            a.hi(),
+            b,
+            residual
+        );
+    });
+}
+
+// Tests for fused multiply-add
+
+#[test]
+fn mul_add_exact_test() {
+    let x = TwoFloat::try_from((1.0 + 2f64.powi(-30), 2f64.powi(-90))).unwrap();
+    let a = TwoFloat::try_from((1.0 - 2f64.powi(-30), 2f64.powi(-95))).unwrap();
+    let b = TwoFloat::new_add(-1.0, 2f64.powi(-60));
+    let expected = TwoFloat::try_from((
+        2f64.powi(-90) + 2f64.powi(-95) - 2f64.powi(-120) + 2f64.powi(-125),
+        2f64.powi(-185),
+    ))
+    .unwrap();
+
+    assert_eq!(x.mul_add(a, b), expected);
+    assert_ne!(x * a + b, expected);
+}
+
+#[test]
+fn mul_add_test() {
+    repeated_test(|| {
+        let (x, a, b) = loop {
+            let x = get_twofloat();
+            let a = get_twofloat();
+            let b = get_twofloat();
+            if (x * a + b).is_valid() && (x * a).is_valid() {
+                break (x, a, b);
+            }
+        };
+
+        let expected = x * a + b;
+        let result = x.mul_add(a, b);
+        assert!(
+            result.is_valid(),
+            "mul_add({:?}, {:?}, {:?}) produced invalid value",
+            x,
+            a,
+            b
+        );
+
+        let scale = (x * a).abs().max(b.abs());
+        assert!(
+            (result - expected).abs() <= scale * 1e-30,
+            "mul_add({:?}, {:?}, {:?}) differs from x * a + b",
+            x,
+            a,
+            b
+        );
+    });
+}
+
+#[test]
+fn mul_add_f64_test() {
+    repeated_test(|| {
+        let (x, a, b) = loop {
+            let x = get_twofloat();
+            let a = random_float();
+            let b = get_twofloat();
+            if (x * a + b).is_valid() && (x * a).is_valid() {
+                break (x, a, b);
+            }
+        };
+
+        assert_eq!(
+            x.mul_add_f64(a, b),
+            x.mul_add(TwoFloat::from(a), b),
+            "mul_add_f64({:?}, {}, {:?}) differs from mul_add",
+            x,
+            a,
+            b
+        );
+    });
+}
+
 // Test for negation operator
 
 #[test]
@@ -340,6 +453,7 @@ fn f64_twofloat_assign(op: Operator) {
         let value = get_valid_twofloat(|x, y| op.apply(x + y, c).is_finite());
         let result1 = op.apply(value, c);
         if result1.is_valid() {
+            #[allow(clippy::needless_borrows_for_generic_args)]
             let result2 = op.apply(&value, c);
             assert!(
                 result2.is_valid(),
@@ -492,3 +606,344 @@ op_test! {
     div_test(Operator::Div);
     rem_test(Operator::Rem);
 }
+
+// Tests for exact remainders
+
+fn random_integer_pair() -> (u128, u128) {
+    let mut rng = rand::thread_rng();
+    let x = rng.gen_range(0..1u128 << 100) >> rng.gen_range(0..100);
+    let y = rng.gen_range(1..1u128 << 64) >> rng.gen_range(0..63);
+    (x, y.max(1))
+}
+
+#[test]
+fn fmod_integer_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let (x, y) = random_integer_pair();
+        let scale = 2f64.powi(rng.gen_range(-600..600));
+        let x_sign = if rng.gen() { 1.0 } else { -1.0 };
+        let y_sign = if rng.gen() { 1.0 } else { -1.0 };
+        let a = TwoFloat::from(x) * scale * x_sign;
+        let b = TwoFloat::from(y) * scale * y_sign;
+
+        let expected = TwoFloat::from(x % y) * scale * x_sign;
+        assert_eq!(a.fmod(b), expected, "fmod({:?}, {:?}) is not exact", a, b);
+        assert_eq!(a % b, expected, "{:?} % {:?} is not exact", a, b);
+
+        let expected_euclid = if x_sign < 0.0 && x % y != 0 {
+            TwoFloat::from(y - x % y) * scale
+        } else {
+            TwoFloat::from(x % y) * scale
+        };
+        assert_eq!(
+            a.rem_euclid(b),
+            expected_euclid,
+            "rem_euclid({:?}, {:?}) is not exact",
+            a,
+            b
+        );
+
+        let quotient = if x_sign < 0.0 && x % y != 0 {
+            x / y + 1
+        } else {
+            x / y
+        };
+        let expected_quotient = TwoFloat::from(quotient) * x_sign * y_sign;
+        assert_eq!(
+            a.div_euclid(b),
+            expected_quotient,
+            "div_euclid({:?}, {:?}) is not exact",
+            a,
+            b
+        );
+    });
+}
+
+#[test]
+fn remquo_integer_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let (x, y) = random_integer_pair();
+        let scale = 2f64.powi(rng.gen_range(-600..600));
+        let x_sign = if rng.gen() { 1.0 } else { -1.0 };
+        let y_sign = if rng.gen() { 1.0 } else { -1.0 };
+        let a = TwoFloat::from(x) * scale * x_sign;
+        let b = TwoFloat::from(y) * scale * y_sign;
+
+        let mut quotient = x / y;
+        let mut remainder = TwoFloat::from(x % y);
+        if 2 * (x % y) > y || (2 * (x % y) == y && quotient % 2 == 1) {
+            quotient += 1;
+            remainder -= TwoFloat::from(y);
+        }
+        let expected = remainder * scale * x_sign;
+        let expected_bits = (quotient & 0x7fff_ffff) as i32 * (x_sign * y_sign) as i32;
+
+        assert_eq!(
+            a.remainder(b),
+            expected,
+            "remainder({:?}, {:?}) is not exact",
+            a,
+            b
+        );
+        assert_eq!(
+            a.remquo(b),
+            (expected, expected_bits),
+            "remquo({:?}, {:?}) is incorrect",
+            a,
+            b
+        );
+    });
+}
+
+#[test]
+fn fmod_large_quotient_test() {
+    let a = TwoFloat::from(2f64.powi(1000));
+    assert_eq!(a.fmod(TwoFloat::from(3.0)), TwoFloat::from(1.0));
+    assert_eq!(a.fmod(TwoFloat::from(10.0)), TwoFloat::from(6.0));
+    assert_eq!(
+        TwoFloat::from(f64::MAX).fmod(TwoFloat::from(3.0)),
+        TwoFloat::from(2.0)
+    );
+    assert_eq!(
+        TwoFloat::from(f64::MAX).fmod(TwoFloat::from(f64::MIN_POSITIVE)),
+        TwoFloat::from(0.0)
+    );
+
+    let b = TwoFloat::try_from((2f64.powi(1000), 2f64.powi(-1000))).unwrap();
+    let expected = TwoFloat::try_from((1.0, 2f64.powi(-1000))).unwrap();
+    assert_eq!(b.fmod(TwoFloat::from(3.0)), expected);
+    assert_eq!(
+        (-b).rem_euclid(TwoFloat::from(3.0)),
+        TwoFloat::new_sub(2.0, 2f64.powi(-1000))
+    );
+}
+
+#[test]
+fn div_euclid_large_quotient_test() {
+    let a = TwoFloat::from(1e300);
+    let b = TwoFloat::from(1.1e-7);
+    let expected = TwoFloat::try_from((
+        hexf64!("0x1.9e44af156f546p+1019"),
+        hexf64!("-0x1.00e6837f65f1fp+965"),
+    ))
+    .unwrap();
+    assert_eq!(a.div_euclid(b), expected);
+    assert_eq!((-a).div_euclid(-b), expected);
+
+    let c = TwoFloat::from(2f64.powi(1000));
+    let expected = TwoFloat::try_from((
+        hexf64!("-0x1.5555555555555p+998"),
+        hexf64!("-0x1.5555555555555p+944"),
+    ))
+    .unwrap();
+    assert_eq!((-c).div_euclid(TwoFloat::from(3.0)), expected);
+
+    assert_eq!(
+        TwoFloat::from(f64::MAX).div_euclid(TwoFloat::from(0.5)),
+        TwoFloat::INFINITY
+    );
+    assert_eq!(
+        TwoFloat::from(f64::MAX).div_euclid(TwoFloat::from(-f64::from_bits(3))),
+        TwoFloat::NEG_INFINITY
+    );
+    assert_eq!(
+        TwoFloat::from(f64::MAX).fmod(TwoFloat::from(f64::from_bits(3))),
+        TwoFloat::from(f64::from_bits(2))
+    );
+}
+
+#[test]
+fn div_euclid_special_test() {
+    let one = TwoFloat::from(1.0);
+    assert!(one.div_euclid(TwoFloat::from(0.0)).hi().is_nan());
+    assert!(TwoFloat::NAN.div_euclid(one).hi().is_nan());
+    assert!(TwoFloat::INFINITY.div_euclid(one).hi().is_nan());
+    assert_eq!(one.div_euclid(TwoFloat::INFINITY), 0.0);
+    assert_eq!((-one).div_euclid(TwoFloat::INFINITY), -1.0);
+    assert_eq!((-one).div_euclid(TwoFloat::NEG_INFINITY), 1.0);
+}
+
+#[test]
+fn fmod_range_test() {
+    repeated_test(|| {
+        let a = get_twofloat();
+        let b = get_valid_twofloat(|hi, _| hi != 0.0);
+
+        let result = a.fmod(b);
+        assert!(
+            result.abs() < b.abs(),
+            "fmod({:?}, {:?}) is out of range",
+            a,
+            b
+        );
+        assert!(
+            result == 0.0 || (result.hi() < 0.0) == (a.hi() < 0.0),
+            "fmod({:?}, {:?}) has the wrong sign",
+            a,
+            b
+        );
+
+        let result = a.rem_euclid(b);
+        assert!(
+            result >= 0.0 && (result < b.abs() || (result == b.abs() && a < 0.0)),
+            "rem_euclid({:?}, {:?}) is out of range",
+            a,
+            b
+        );
+
+        let result = a.remainder(b);
+        assert!(
+            result.abs() <= b.abs() / 2.0,
+            "remainder({:?}, {:?}) is out of range",
+            a,
+            b
+        );
+    });
+}
+
+#[test]
+fn fmod_special_test() {
+    let one = TwoFloat::from(1.0);
+    assert!(one.fmod(TwoFloat::from(0.0)).hi().is_nan());
+    assert!(TwoFloat::NAN.fmod(one).hi().is_nan());
+    assert!(TwoFloat::INFINITY.fmod(one).hi().is_nan());
+    assert_eq!(one.fmod(TwoFloat::INFINITY), one);
+    assert_eq!((-one).rem_euclid(TwoFloat::INFINITY), TwoFloat::INFINITY);
+}
+
+// Tests for directed rounding
+
+fn random_integer_twofloat() -> (i128, TwoFloat) {
+    let mut rng = rand::thread_rng();
+    let value = rng.gen_range(-(1i128 << 120)..1i128 << 120) >> rng.gen_range(0..120);
+    let result = TwoFloat::from(value);
+    (i128::try_from(result).unwrap(), result)
+}
+
+#[test]
+fn add_directed_integer_test() {
+    repeated_test(|| {
+        let (a_int, a) = random_integer_twofloat();
+        let (b_int, b) = random_integer_twofloat();
+        let sum = a_int + b_int;
+        let difference = a_int - b_int;
+
+        let up = a.add_up(b);
+        let down = a.add_down(b);
+        assert!(
+            i128::try_from(up).unwrap() >= sum && i128::try_from(down).unwrap() <= sum,
+            "add_up/add_down({:?}, {:?}) does not bracket the sum",
+            a,
+            b
+        );
+        assert!(up - down <= up.ulp() * 2.0);
+
+        let up = a.sub_up(b);
+        let down = a.sub_down(b);
+        assert!(
+            i128::try_from(up).unwrap() >= difference
+                && i128::try_from(down).unwrap() <= difference,
+            "sub_up/sub_down({:?}, {:?}) does not bracket the difference",
+            a,
+            b
+        );
+        assert!(up - down <= up.ulp() * 2.0);
+    });
+}
+
+#[test]
+fn add_directed_exact_test() {
+    repeated_test(|| {
+        let (a, b) = get_valid_pair(|x, y| (x + y).is_finite());
+        let a = TwoFloat::from(a);
+        let b = TwoFloat::from(b);
+        let sum = TwoFloat::new_add(a.hi(), b.hi());
+
+        assert_eq!(a.add_up(b), sum);
+        assert_eq!(a.add_down(b), sum);
+    });
+}
+
+#[test]
+fn mul_directed_test() {
+    repeated_test(|| {
+        let (a, b) = loop {
+            let a = get_twofloat();
+            let b = get_twofloat();
+            let product = (a * b).abs();
+            if product > 1e-280 && product < 1e300 {
+                break (a, b);
+            }
+        };
+
+        let up = a.mul_up(b);
+        let down = a.mul_down(b);
+        assert!(
+            a.mul_add(b, -up) <= 0.0,
+            "mul_up({:?}, {:?}) is less than the product",
+            a,
+            b
+        );
+        assert!(
+            a.mul_add(b, -down) >= 0.0,
+            "mul_down({:?}, {:?}) is greater than the product",
+            a,
+            b
+        );
+        assert!(up - down <= up.abs().ulp() * 2.0);
+    });
+}
+
+#[test]
+fn div_directed_test() {
+    repeated_test(|| {
+        let (a, b) = loop {
+            let a = get_twofloat();
+            let b = get_twofloat();
+            let quotient = (a / b).abs();
+            if quotient > 1e-280 && quotient < 1e280 && b.abs() > 1e-280 && b.abs() < 1e280 {
+                break (a, b);
+            }
+        };
+
+        let up = a.div_up(b);
+        let down = a.div_down(b);
+        let b_sign = if b > 0.0 { 1.0 } else { -1.0 };
+        assert!(
+            b.mul_add(up, -a) * b_sign >= 0.0,
+            "div_up({:?}, {:?}) is less than the quotient",
+            a,
+            b
+        );
+        assert!(
+            b.mul_add(down, -a) * b_sign <= 0.0,
+            "div_down({:?}, {:?}) is greater than the quotient",
+            a,
+            b
+        );
+        assert!(up - down <= up.abs().ulp() * 4.0);
+    });
+}
+
+#[test]
+fn directed_limits_test() {
+    assert_eq!(TwoFloat::MAX.add_up(TwoFloat::MAX), TwoFloat::INFINITY);
+    assert_eq!(TwoFloat::MAX.add_down(TwoFloat::MAX), TwoFloat::MAX);
+    assert_eq!(TwoFloat::MIN.sub_up(TwoFloat::MAX), TwoFloat::MIN);
+    assert_eq!(
+        TwoFloat::MAX.mul_down(TwoFloat::from(-2.0)),
+        TwoFloat::NEG_INFINITY
+    );
+    assert_eq!(TwoFloat::MAX.div_down(TwoFloat::from(0.5)), TwoFloat::MAX);
+
+    let a = TwoFloat::from(2f64.powi(-540));
+    let b = TwoFloat::from(1.5 * 2f64.powi(-540));
+    let smallest = TwoFloat::from(f64::from_bits(1));
+    assert!(a.mul_up(b) > 0.0 && a.mul_up(b) <= smallest * 2.0);
+    assert!(a.mul_down(b) <= 0.0 && a.mul_down(b) >= -smallest);
+    assert!((-a).mul_down(b) < 0.0 && (-a).mul_down(b) >= -smallest * 2.0);
+    assert_eq!(smallest.div_up(TwoFloat::from(3.0)), smallest);
+    assert_eq!(smallest.div_down(TwoFloat::from(3.0)), 0.0);
+}
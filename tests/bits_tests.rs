@@ -0,0 +1,350 @@
+#![allow(clippy::float_cmp)]
+
+use core::convert::TryFrom;
+
+use rand::Rng;
+use twofloat::TwoFloat;
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+fn random_integer_twofloat() -> (u128, i32, TwoFloat) {
+    let mut rng = rand::thread_rng();
+    let value = (rng.gen_range(1..1u128 << 100) >> rng.gen_range(0..100)).max(1);
+    let exp = rng.gen_range(-900..900);
+    (value, exp, TwoFloat::from(value) * 2f64.powi(exp))
+}
+
+// ldexp() tests
+
+#[test]
+fn ldexp_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let source = get_valid_twofloat(|x, y| {
+            x.abs() > 1e-100 && x.abs() < 1e100 && (y == 0.0 || y.abs() > 1e-150)
+        });
+        let exp = rng.gen_range(-400..400);
+        let result = source.ldexp(exp);
+
+        assert!(
+            result.is_valid(),
+            "ldexp({:?}, {}) produced invalid value",
+            source,
+            exp
+        );
+        assert_eq!(
+            result,
+            source * 2f64.powi(exp),
+            "ldexp({:?}, {}) is not exact",
+            source,
+            exp
+        );
+        assert_eq!(
+            result.ldexp(-exp),
+            source,
+            "ldexp({:?}, {}) did not round-trip",
+            source,
+            exp
+        );
+    });
+}
+
+#[test]
+fn ldexp_limits_test() {
+    let one = TwoFloat::from(1.0);
+    assert_eq!(one.ldexp(1024), TwoFloat::INFINITY);
+    assert_eq!((-one).ldexp(i32::MAX), TwoFloat::NEG_INFINITY);
+    assert_eq!(one.ldexp(-1074), TwoFloat::from(f64::from_bits(1)));
+    assert_eq!(
+        TwoFloat::from(1.0 + f64::EPSILON).ldexp(-1075),
+        TwoFloat::from(f64::from_bits(1))
+    );
+    assert_eq!(
+        TwoFloat::from(-1.5).ldexp(-1074),
+        TwoFloat::from(-f64::from_bits(2))
+    );
+    assert_eq!(
+        TwoFloat::from(f64::from_bits(3)).ldexp(2000).ldexp(-2001),
+        TwoFloat::from(f64::from_bits(2))
+    );
+    assert_eq!(one.ldexp(i32::MIN), TwoFloat::from(0.0));
+    assert_eq!(TwoFloat::MAX.ldexp(-1).ldexp(1), TwoFloat::MAX);
+}
+
+// frexp() and ilogb() tests
+
+#[test]
+fn frexp_test() {
+    repeated_test(|| {
+        let source = get_valid_twofloat(|x, y| {
+            x != 0.0 && x.abs() < 1e100 && (y == 0.0 || y.abs() > 1e-200)
+        });
+        let (fraction, exp) = source.frexp();
+
+        assert!(
+            fraction.abs() >= 0.5 && fraction.abs() < 1.0,
+            "frexp({:?}) fraction {:?} out of range",
+            source,
+            fraction
+        );
+        assert_eq!(
+            fraction.ldexp(exp),
+            source,
+            "frexp({:?}) did not round-trip",
+            source
+        );
+        assert_eq!(
+            source.ilogb(),
+            exp - 1,
+            "ilogb({:?}) does not match frexp",
+            source
+        );
+    });
+}
+
+#[test]
+fn ilogb_test() {
+    assert_eq!(TwoFloat::from(1.0).ilogb(), 0);
+    assert_eq!(TwoFloat::new_add(1.0, -1e-30).ilogb(), -1);
+    assert_eq!(TwoFloat::new_add(-1.0, 1e-30).ilogb(), -1);
+    assert_eq!(TwoFloat::new_add(1.5, -1e-30).ilogb(), 0);
+    assert_eq!(TwoFloat::from(f64::from_bits(1)).ilogb(), -1074);
+    assert_eq!(TwoFloat::MAX.ilogb(), 1023);
+    assert_eq!(TwoFloat::from(0.0).ilogb(), i32::MIN);
+    assert_eq!(TwoFloat::NAN.ilogb(), i32::MIN);
+    assert_eq!(TwoFloat::INFINITY.ilogb(), i32::MAX);
+
+    let (fraction, exp) = TwoFloat::new_add(1.0, -1e-30).frexp();
+    assert_eq!(fraction, TwoFloat::new_add(1.0, -1e-30));
+    assert_eq!(exp, 0);
+}
+
+// ulp(), next_up() and next_down() tests
+
+#[test]
+fn ulp_test() {
+    assert_eq!(TwoFloat::from(1.0).ulp(), 2f64.powi(-105));
+    assert_eq!(TwoFloat::from(-3.0).ulp(), 2f64.powi(-104));
+    assert_eq!(TwoFloat::new_add(1.0, -1e-30).ulp(), 2f64.powi(-106));
+    assert_eq!(TwoFloat::from(1e-300).ulp(), f64::from_bits(1));
+    assert_eq!(TwoFloat::MAX.ulp(), 2f64.powi(918));
+    assert!(TwoFloat::INFINITY.ulp().hi().is_nan());
+}
+
+#[test]
+fn next_up_down_test() {
+    repeated_test(|| {
+        let source = get_valid_twofloat(|x, _| x.abs() < 1e300);
+        let up = source.next_up();
+        let down = source.next_down();
+
+        assert!(
+            up.is_valid(),
+            "next_up({:?}) produced invalid value",
+            source
+        );
+        assert!(
+            down.is_valid(),
+            "next_down({:?}) produced invalid value",
+            source
+        );
+        assert!(up > source, "next_up({:?}) is not greater", source);
+        assert!(down < source, "next_down({:?}) is not less", source);
+        assert!(
+            up - source <= source.ulp() * 2.0,
+            "next_up({:?}) stepped too far",
+            source
+        );
+        assert!(
+            source - down <= source.ulp() * 2.0,
+            "next_down({:?}) stepped too far",
+            source
+        );
+    });
+}
+
+#[test]
+fn next_up_down_integer_test() {
+    repeated_test(|| {
+        let (_, _, source) = random_integer_twofloat();
+        for &value in &[source, -source] {
+            assert_eq!(
+                value.next_up().next_down(),
+                value,
+                "next_up({:?}) did not round-trip",
+                value
+            );
+            assert_eq!(
+                value.next_down().next_up(),
+                value,
+                "next_down({:?}) did not round-trip",
+                value
+            );
+        }
+    });
+}
+
+#[test]
+fn next_up_down_limits_test() {
+    let smallest = TwoFloat::from(f64::from_bits(1));
+    assert_eq!(TwoFloat::from(0.0).next_up(), smallest);
+    assert_eq!(TwoFloat::from(0.0).next_down(), -smallest);
+    assert_eq!(TwoFloat::MAX.next_up(), TwoFloat::INFINITY);
+    assert_eq!(TwoFloat::MIN.next_down(), TwoFloat::NEG_INFINITY);
+    assert_eq!(TwoFloat::NEG_INFINITY.next_up(), TwoFloat::MIN);
+    assert_eq!(TwoFloat::INFINITY.next_down(), TwoFloat::MAX);
+    assert_eq!(TwoFloat::INFINITY.next_up(), TwoFloat::INFINITY);
+}
+
+// integer_decode() tests
+
+#[test]
+fn integer_decode_test() {
+    repeated_test(|| {
+        let (value, exp, source) = random_integer_twofloat();
+        let (mantissa, exponent, sign) = source.integer_decode();
+        let shift = exp - exponent as i32;
+
+        assert!(
+            (0..128).contains(&shift),
+            "integer_decode({:?}) has unexpected exponent {}",
+            source,
+            exponent
+        );
+        assert_eq!(
+            mantissa,
+            value << shift,
+            "integer_decode({:?}) has wrong mantissa",
+            source
+        );
+        assert_eq!(sign, 1);
+        assert_eq!((-source).integer_decode(), (mantissa, exponent, -1));
+    });
+}
+
+#[test]
+fn integer_decode_rounding_test() {
+    let source = TwoFloat::try_from((1.0, 2f64.powi(-200))).unwrap();
+    assert_eq!(source.integer_decode(), (1 << 105, -105, 1));
+
+    // Rounding up to a power of two keeps the mantissa within 106 bits
+    let source = TwoFloat::try_from((1.0, -2f64.powi(-200))).unwrap();
+    assert_eq!(source.integer_decode(), (1 << 105, -105, 1));
+
+    let source = TwoFloat::new_add(1.0, -2f64.powi(-120));
+    assert_eq!(source.integer_decode(), (1 << 105, -105, 1));
+    assert_eq!((-source).integer_decode(), (1 << 105, -105, -1));
+
+    assert_eq!(TwoFloat::from(0.0).integer_decode(), (0, -1075, 1));
+    assert_eq!(TwoFloat::from(-0.0).integer_decode(), (0, -1075, -1));
+}
+
+#[test]
+fn float_core_integer_decode_test() {
+    use num_traits::float::FloatCore;
+
+    repeated_test(|| {
+        let source = get_valid_twofloat(|x, _| x.abs() < 1e300 && x.abs() > 1e-300);
+        let (mantissa, exponent, sign) = FloatCore::integer_decode(source);
+        let value = TwoFloat::from(mantissa).ldexp(exponent as i32) * sign as f64;
+        let difference = ((value - source) / source).abs();
+
+        assert!(
+            difference <= 2f64.powi(-63),
+            "FloatCore::integer_decode({:?}) is out of range",
+            source
+        );
+    });
+}
+
+#[test]
+fn float_core_integer_decode_rounding_test() {
+    use num_traits::float::FloatCore;
+
+    let tie = TwoFloat::try_from((1.0, 2f64.powi(-64))).unwrap();
+    assert_eq!(FloatCore::integer_decode(tie), (1 << 63, -63, 1));
+    assert_eq!(FloatCore::integer_decode(-tie), (1 << 63, -63, -1));
+
+    let odd_tie = TwoFloat::try_from((1.0, 1.5 * 2f64.powi(-63))).unwrap();
+    assert_eq!(FloatCore::integer_decode(odd_tie), ((1 << 63) + 2, -63, 1));
+
+    let above_tie = TwoFloat::try_from((1.0, (1.0 + f64::EPSILON) * 2f64.powi(-64))).unwrap();
+    assert_eq!(
+        FloatCore::integer_decode(above_tie),
+        ((1 << 63) + 1, -63, 1)
+    );
+
+    let carry = TwoFloat::try_from((2.0, -(2f64.powi(-65)))).unwrap();
+    assert_eq!(FloatCore::integer_decode(carry), (1 << 63, -62, 1));
+}
+
+// Byte conversion tests
+
+#[test]
+fn bits_roundtrip_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+        let (hi, lo) = source.to_bits();
+
+        assert_eq!(hi, source.hi().to_bits());
+        assert_eq!(lo, source.lo().to_bits());
+        assert_eq!(TwoFloat::from_bits((hi, lo)).unwrap(), source);
+    });
+}
+
+#[test]
+fn bytes_roundtrip_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+
+        let le = source.to_le_bytes();
+        assert_eq!(le[..8], source.hi().to_le_bytes());
+        assert_eq!(le[8..], source.lo().to_le_bytes());
+        assert_eq!(TwoFloat::from_le_bytes(le).unwrap(), source);
+
+        let be = source.to_be_bytes();
+        assert_eq!(be[..8], source.hi().to_be_bytes());
+        assert_eq!(be[8..], source.lo().to_be_bytes());
+        assert_eq!(TwoFloat::from_be_bytes(be).unwrap(), source);
+
+        let ne = source.to_ne_bytes();
+        assert_eq!(ne[..8], source.hi().to_ne_bytes());
+        assert_eq!(ne[8..], source.lo().to_ne_bytes());
+        assert_eq!(TwoFloat::from_ne_bytes(ne).unwrap(), source);
+    });
+}
+
+#[test]
+fn bytes_invalid_test() {
+    let le_bytes = |hi: f64, lo: f64| {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&hi.to_le_bytes());
+        bytes[8..].copy_from_slice(&lo.to_le_bytes());
+        bytes
+    };
+
+    repeated_test(|| {
+        let (a, b) = get_valid_pair(|x, y| (x / y).abs() < 1e10 && (y / x).abs() < 1e10);
+
+        assert!(
+            TwoFloat::from_le_bytes(le_bytes(a, b)).is_err(),
+            "Overlapping words ({:?}, {:?}) accepted",
+            a,
+            b
+        );
+        assert!(TwoFloat::from_bits((a.to_bits(), b.to_bits())).is_err());
+    });
+
+    for &(hi, lo) in &[
+        (f64::NAN, 0.0),
+        (f64::INFINITY, 0.0),
+        (1.0, f64::NAN),
+        (0.0, 1.0),
+    ] {
+        assert!(TwoFloat::from_le_bytes(le_bytes(hi, lo)).is_err());
+        assert!(TwoFloat::from_bits((hi.to_bits(), lo.to_bits())).is_err());
+    }
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "bytemuck")]
+
+use twofloat::TwoFloat;
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+#[test]
+fn cast_bytes_test() {
+    repeated_test(|| {
+        let values = [get_twofloat(), get_twofloat()];
+        let bytes: &[u8] = bytemuck::cast_slice(&values);
+
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(bytes[..16], values[0].to_ne_bytes());
+        assert_eq!(bytes[16..], values[1].to_ne_bytes());
+
+        let words: &[f64] = bytemuck::cast_slice(&values);
+        assert_eq!(
+            words,
+            [
+                values[0].hi(),
+                values[0].lo(),
+                values[1].hi(),
+                values[1].lo()
+            ]
+        );
+    });
+}
+
+#[test]
+fn zeroed_test() {
+    let value: TwoFloat = bytemuck::Zeroable::zeroed();
+
+    assert!(value.is_valid());
+    assert_eq!(value, 0.0);
+}
@@ -0,0 +1,166 @@
+#![allow(clippy::float_cmp)]
+
+use rand::Rng;
+use twofloat::TwoFloat;
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a.abs()
+}
+
+// continued_fraction() tests
+
+#[test]
+fn continued_fraction_dyadic_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let numerator = rng.gen_range(-(1i64 << 52)..1i64 << 52) | 1;
+        let exponent = rng.gen_range(0..60);
+        let source = TwoFloat::from(numerator as f64) * 2f64.powi(-exponent);
+
+        let (mut h_prev, mut k_prev, mut h, mut k) = (0i128, 1i128, 1i128, 0i128);
+        for (i, term) in source.continued_fraction(200).enumerate() {
+            assert!(
+                i == 0 || term > 0,
+                "continued_fraction({:?}) has non-positive term {}",
+                source,
+                term
+            );
+            let next = (term as i128 * h + h_prev, term as i128 * k + k_prev);
+            h_prev = h;
+            k_prev = k;
+            h = next.0;
+            k = next.1;
+        }
+
+        assert_eq!(
+            (h, k),
+            (numerator as i128, 1i128 << exponent),
+            "continued_fraction({:?}) does not reconstruct the value",
+            source
+        );
+    });
+}
+
+#[test]
+fn continued_fraction_limits_test() {
+    assert_eq!(
+        TwoFloat::from(2.0)
+            .continued_fraction(5)
+            .collect::<Vec<_>>(),
+        [2]
+    );
+    assert_eq!(TwoFloat::from(0.1).continued_fraction(0).count(), 0);
+    assert_eq!(TwoFloat::NAN.continued_fraction(5).count(), 0);
+    assert_eq!(TwoFloat::from(1e30).continued_fraction(5).count(), 0);
+    assert_eq!(
+        TwoFloat::from(f64::from_bits(1))
+            .continued_fraction(5)
+            .collect::<Vec<_>>(),
+        [0]
+    );
+}
+
+// best_rational() tests
+
+#[test]
+fn best_rational_recovers_ratio_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let p = rng.gen_range(-(1i128 << 40)..1i128 << 40);
+        let q = rng.gen_range(1i128..1i128 << 31);
+        let source = TwoFloat::from_ratio(p, q);
+        let g = gcd(p, q);
+
+        assert_eq!(
+            source.best_rational(q as u64).unwrap(),
+            ((p / g) as i64, (q / g) as u64),
+            "best_rational({:?}) did not recover {}/{}",
+            source,
+            p,
+            q
+        );
+    });
+}
+
+#[test]
+fn best_rational_brute_force_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.gen_range(-10.0..10.0)) + rng.gen_range(-1e-20..1e-20);
+        let max_denominator = rng.gen_range(1..60u64);
+        let (p, q) = source.best_rational(max_denominator).unwrap();
+        let scaled_distance =
+            |p: i64, q: u64| source.mul_add_f64(q as f64, -TwoFloat::from(p)).abs();
+
+        assert!(q >= 1 && q <= max_denominator);
+        for candidate_q in 1..=max_denominator {
+            let candidate_p = (source * candidate_q as f64).round().hi() as i64;
+            let distance = scaled_distance(p, q) * candidate_q as f64;
+            let candidate = scaled_distance(candidate_p, candidate_q) * q as f64;
+            assert!(
+                distance <= candidate + 1e-25,
+                "best_rational({:?}, {}) gave {}/{} but {}/{} is closer",
+                source,
+                max_denominator,
+                p,
+                q,
+                candidate_p,
+                candidate_q
+            );
+        }
+    });
+}
+
+#[test]
+fn best_rational_exact_test() {
+    // Values that are multiples of 2^-106, for which every remainder of the
+    // expansion is exact, compared using integer residuals
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let numerator = rng.gen_range(-(1i128 << 109)..1i128 << 109) >> rng.gen_range(0..100);
+        let source = TwoFloat::from_ratio(numerator, 1 << 106);
+        let scaled = source.ldexp(106).to_i128_exact().unwrap();
+        let max_denominator = rng.gen_range(1..300u64);
+        let (p, q) = source.best_rational(max_denominator).unwrap();
+        let residual = |p: i64, q: u64| (scaled * q as i128 - ((p as i128) << 106)).abs();
+
+        assert!(q >= 1 && q <= max_denominator);
+        for candidate_q in 1..=max_denominator {
+            let candidate_p = (source * candidate_q as f64).round().hi() as i64;
+            assert!(
+                residual(p, q) * candidate_q as i128
+                    <= residual(candidate_p, candidate_q) * q as i128,
+                "best_rational({:?}, {}) gave {}/{} but {}/{} is closer",
+                source,
+                max_denominator,
+                p,
+                q,
+                candidate_p,
+                candidate_q
+            );
+        }
+    });
+}
+
+#[test]
+fn best_rational_limits_test() {
+    assert_eq!(TwoFloat::from(2.7).best_rational(0).unwrap(), (3, 1));
+    assert_eq!(TwoFloat::from(0.5).best_rational(1).unwrap(), (0, 1));
+    assert_eq!(TwoFloat::from(-7.0).best_rational(100).unwrap(), (-7, 1));
+    assert_eq!(
+        TwoFloat::new_div(1.0, 3.0).best_rational(u64::MAX).unwrap(),
+        (1, 3)
+    );
+    assert!(TwoFloat::NAN.best_rational(10).is_err());
+    assert!(TwoFloat::from(1e30).best_rational(10).is_err());
+}
@@ -5,7 +5,7 @@ use core::{convert::TryFrom, fmt::Debug, mem::discriminant, ops::Range};
 use num_traits::{one, zero};
 use rand::{distributions::uniform::SampleUniform, Rng};
 
-use twofloat::{no_overlap, TwoFloat, TwoFloatError};
+use twofloat::{no_overlap, RoundingMode, TwoFloat, TwoFloatError};
 
 #[macro_use]
 pub mod common;
@@ -940,3 +940,329 @@ int128_test! {
     i128_test::<i128>();
     u128_test::<u128>();
 }
+
+// Tests for rounded and exact big integer conversions
+
+const ROUNDING_MODES: [RoundingMode; 5] = [
+    RoundingMode::NearestEven,
+    RoundingMode::NearestAway,
+    RoundingMode::TowardZero,
+    RoundingMode::TowardPositive,
+    RoundingMode::TowardNegative,
+];
+
+fn random_i128(max_bits: u32) -> i128 {
+    let mut rng = rand::thread_rng();
+    rng.gen::<i128>() >> (128 - max_bits + rng.gen_range(0..max_bits))
+}
+
+#[test]
+fn from_i128_rounded_exact_test() {
+    repeated_test(|| {
+        let value = random_i128(106);
+        let expected = TwoFloat::from(value);
+        for &mode in &ROUNDING_MODES {
+            assert_eq!(
+                TwoFloat::from_i128_rounded(value, mode),
+                expected,
+                "from_i128_rounded({}, {:?}) is not exact",
+                value,
+                mode
+            );
+        }
+        assert_eq!(
+            expected.to_i128_exact().unwrap(),
+            value,
+            "to_i128_exact({:?}) did not round-trip",
+            expected
+        );
+    });
+}
+
+#[test]
+fn from_i128_rounded_test() {
+    repeated_test(|| {
+        let value = random_i128(127);
+        let rounded = ROUNDING_MODES
+            .iter()
+            .map(|&mode| {
+                TwoFloat::from_i128_rounded(value, mode)
+                    .to_i128_exact()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let (up, down) = (rounded[3], rounded[4]);
+        let step = 1i128 << (128 - value.unsigned_abs().leading_zeros()).saturating_sub(106);
+
+        assert!(
+            down <= value && value <= up,
+            "from_i128_rounded({}) does not bracket the value",
+            value
+        );
+        assert!(
+            up - down == 0 || up - down == step,
+            "from_i128_rounded({}) brackets are too far apart",
+            value
+        );
+        assert_eq!(rounded[2], if value < 0 { up } else { down });
+        for &nearest in &rounded[..2] {
+            assert!(
+                (nearest - value).abs() <= step / 2,
+                "from_i128_rounded({}) is not rounded to nearest",
+                value
+            );
+        }
+        if up - down == step && value - down == step / 2 {
+            assert_eq!(rounded[0] & step, 0);
+            assert_eq!(rounded[1], if value < 0 { down } else { up });
+        }
+    });
+}
+
+#[test]
+fn from_u128_rounded_test() {
+    let a = TwoFloat::from_u128_rounded(u128::MAX, RoundingMode::NearestEven);
+    assert_eq!(a, TwoFloat::from(2f64.powi(128)));
+    assert!(a.to_u128_exact().is_err());
+
+    let b = TwoFloat::from_u128_rounded(u128::MAX, RoundingMode::TowardNegative);
+    assert_eq!(b.to_u128_exact().unwrap(), u128::MAX - (1 << 22) + 1);
+    assert_eq!(TwoFloat::from(-0.0).to_u128_exact().unwrap(), 0);
+}
+
+#[test]
+fn to_i128_exact_test() {
+    assert_eq!(
+        TwoFloat::from(-2f64.powi(127)).to_i128_exact().unwrap(),
+        i128::MIN
+    );
+    assert!(TwoFloat::from(2f64.powi(127)).to_i128_exact().is_err());
+    assert!(TwoFloat::new_add(1e20, 0.5).to_i128_exact().is_err());
+    assert!(TwoFloat::NAN.to_i128_exact().is_err());
+    assert!(TwoFloat::INFINITY.to_i128_exact().is_err());
+}
+
+#[test]
+fn limbs_roundtrip_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let value = random_i128(106).unsigned_abs();
+        let shift = rng.gen_range(0..600);
+        let mut expected = [0u64; 12];
+        for (i, limb) in expected.iter_mut().enumerate() {
+            let start = (64 * i) as i32 - shift;
+            *limb = if start <= -128 || start >= 128 {
+                0
+            } else if start >= 0 {
+                (value >> start) as u64
+            } else {
+                (value << -start) as u64
+            };
+        }
+
+        let source = TwoFloat::from(value).ldexp(shift);
+        let mut result = [0u64; 12];
+        source.to_limbs(&mut result).unwrap();
+        assert_eq!(result, expected, "to_limbs({:?}) is incorrect", source);
+
+        for &mode in &ROUNDING_MODES {
+            assert_eq!(
+                TwoFloat::from_limbs(&expected, false, mode),
+                source,
+                "from_limbs({:?}) is not exact",
+                expected
+            );
+        }
+    });
+}
+
+#[test]
+fn limbs_limits_test() {
+    let mut limbs = [0u64; 16];
+    TwoFloat::MAX.to_limbs(&mut limbs).unwrap();
+    assert_eq!(
+        TwoFloat::from_limbs(&limbs, false, RoundingMode::NearestEven),
+        TwoFloat::MAX
+    );
+    assert!(TwoFloat::MAX.to_limbs(&mut limbs[..15]).is_err());
+
+    let huge = [u64::MAX; 17];
+    assert_eq!(
+        TwoFloat::from_limbs(&huge, false, RoundingMode::NearestEven),
+        TwoFloat::INFINITY
+    );
+    assert_eq!(
+        TwoFloat::from_limbs(&huge, false, RoundingMode::TowardZero),
+        TwoFloat::MAX
+    );
+    assert_eq!(
+        TwoFloat::from_limbs(&huge, true, RoundingMode::TowardPositive),
+        TwoFloat::MIN
+    );
+    assert_eq!(
+        TwoFloat::from_limbs(&huge, true, RoundingMode::TowardNegative),
+        TwoFloat::NEG_INFINITY
+    );
+    assert_eq!(
+        TwoFloat::from_limbs(&[], false, RoundingMode::NearestEven),
+        TwoFloat::from(0.0)
+    );
+}
+
+// Tests for rational conversions
+
+#[test]
+fn to_ratio_roundtrip_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+        let mut numerator = [0u64; 40];
+        let exponent = source.to_ratio_limbs(&mut numerator).unwrap();
+
+        assert!(
+            source.hi() == 0.0 || numerator[0] & 1 == 1,
+            "to_ratio_limbs({:?}) numerator is not reduced",
+            source
+        );
+
+        let negative = source.is_sign_negative();
+        let result = if exponent >= 0 {
+            TwoFloat::from_limbs(&numerator, negative, RoundingMode::NearestEven).ldexp(exponent)
+        } else {
+            let mut denominator = [0u64; 20];
+            denominator[-exponent as usize / 64] = 1 << (-exponent % 64);
+            TwoFloat::from_ratio_limbs(
+                &numerator,
+                &denominator,
+                negative,
+                RoundingMode::NearestEven,
+            )
+        };
+
+        assert!(
+            (result - source).abs() <= source.ulp() * 0.5,
+            "to_ratio_limbs({:?}) did not round-trip",
+            source
+        );
+    });
+}
+
+#[test]
+fn to_ratio_test() {
+    assert_eq!(TwoFloat::from(0.0).to_ratio().unwrap(), (0, 0));
+    assert_eq!(TwoFloat::from(-12.0).to_ratio().unwrap(), (-3, 2));
+    assert_eq!(
+        TwoFloat::new_add(1.0, 2f64.powi(-100)).to_ratio().unwrap(),
+        ((1 << 100) + 1, -100)
+    );
+    assert_eq!(
+        TwoFloat::from(f64::from_bits(1)).to_ratio().unwrap(),
+        (1, -1074)
+    );
+    assert!(TwoFloat::new_add(1.0, 2f64.powi(-200)).to_ratio().is_err());
+    assert!(TwoFloat::NAN.to_ratio().is_err());
+}
+
+#[test]
+fn from_ratio_directed_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let p = rng.gen_range(1..1u64 << 53);
+        let q = rng.gen_range(1..1u64 << 53) >> rng.gen_range(0..52);
+        let q = q.max(1);
+        let down = TwoFloat::from_ratio_limbs(&[p], &[q], false, RoundingMode::TowardNegative);
+        let up = TwoFloat::from_ratio_limbs(&[p], &[q], false, RoundingMode::TowardPositive);
+        let nearest = TwoFloat::from_ratio_limbs(&[p], &[q], false, RoundingMode::NearestEven);
+        let (p, q) = (TwoFloat::from(p as f64), TwoFloat::from(q as f64));
+
+        assert!(
+            down.mul_add(q, -p) <= 0.0,
+            "from_ratio({:?}, {:?}) rounded down is too large",
+            p,
+            q
+        );
+        assert!(
+            up.mul_add(q, -p) >= 0.0,
+            "from_ratio({:?}, {:?}) rounded up is too small",
+            p,
+            q
+        );
+        assert!(
+            up == down || up == down.next_up(),
+            "from_ratio({:?}, {:?}) is not tight",
+            p,
+            q
+        );
+        assert!(nearest == down || nearest == up);
+        assert_eq!(
+            TwoFloat::from_ratio_limbs(
+                &[p.hi() as u64],
+                &[q.hi() as u64],
+                true,
+                RoundingMode::TowardPositive
+            ),
+            -down
+        );
+    });
+}
+
+#[test]
+fn from_ratio_nearest_test() {
+    let cases = [
+        (1, 10, 0.1, -5.551115123125783e-18),
+        (2, 3, 0.6666666666666666, 3.700743415417189e-17),
+        (-355, 113, -3.1415929203539825, 2.2007960842126997e-16),
+        (
+            1_000_000_000_000_000_000_000_000_000_000,
+            7,
+            1.4285714285714285e+29,
+            4698847613513.143,
+        ),
+        (
+            1,
+            3i128.pow(40),
+            8.225263339969959e-20,
+            4.695964864283024e-36,
+        ),
+    ];
+    for &(p, q, hi, lo) in &cases {
+        assert_eq!(
+            TwoFloat::from_ratio(p, q),
+            TwoFloat::try_from((hi, lo)).unwrap(),
+            "from_ratio({}, {}) is not correctly rounded",
+            p,
+            q
+        );
+    }
+}
+
+#[test]
+fn from_ratio_limits_test() {
+    let smallest = TwoFloat::from(f64::from_bits(1));
+    let mut denominator = [0u64; 17];
+    denominator[16] = 1 << 51;
+    assert_eq!(
+        TwoFloat::from_ratio_limbs(&[1], &denominator, false, RoundingMode::NearestEven),
+        TwoFloat::from(0.0)
+    );
+    assert_eq!(
+        TwoFloat::from_ratio_limbs(&[3], &denominator, false, RoundingMode::NearestEven),
+        smallest * 2.0
+    );
+    assert_eq!(
+        TwoFloat::from_ratio_limbs(&[1], &denominator, false, RoundingMode::TowardPositive),
+        smallest
+    );
+
+    let huge = [u64::MAX; 17];
+    assert_eq!(
+        TwoFloat::from_ratio_limbs(&huge, &[3], false, RoundingMode::NearestEven),
+        TwoFloat::INFINITY
+    );
+    assert_eq!(
+        TwoFloat::from_ratio_limbs(&huge, &[3], true, RoundingMode::TowardZero),
+        TwoFloat::MIN
+    );
+    assert_eq!(TwoFloat::from_ratio(1, 0), TwoFloat::INFINITY);
+    assert!(TwoFloat::from_ratio(0, 0).hi().is_nan());
+    assert_eq!(TwoFloat::from_ratio(0, -5), TwoFloat::from(0.0));
+}
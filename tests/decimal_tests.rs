@@ -0,0 +1,227 @@
+#![allow(clippy::float_cmp)]
+
+use core::convert::TryFrom;
+
+use rand::Rng;
+use twofloat::{RoundingMode, TwoFloat};
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+fn parse_digits(digits: &str) -> Vec<u8> {
+    digits.bytes().map(|b| b - b'0').collect()
+}
+
+fn digits_value(digits: &[u8]) -> u128 {
+    digits.iter().fold(0, |acc, &d| acc * 10 + d as u128)
+}
+
+// Returns a random value with at most 106 significant bits.
+fn random_106_bit_twofloat() -> TwoFloat {
+    let mut rng = rand::thread_rng();
+    let mantissa = rng.gen_range(1u128 << 105..1u128 << 106);
+    let value = TwoFloat::from(mantissa).ldexp(rng.gen_range(-900..800));
+    if rng.gen() {
+        value
+    } else {
+        -value
+    }
+}
+
+// to_decimal() tests
+
+#[test]
+fn to_decimal_test() {
+    let cases = [
+        (
+            0.1,
+            0.0,
+            ["10000000000000000555111512312578270212", "1000000000"],
+            ["10000000000000000555111512312578270212", "1000000001"],
+            ["10000000000000000555111512312578270211", "1000000000"],
+            [-38, -10],
+        ),
+        (
+            0.3333333333333333,
+            1.8503717077085944e-17,
+            ["33333333333333333333333333333333538766", "3333333333"],
+            ["33333333333333333333333333333333538766", "3333333334"],
+            ["33333333333333333333333333333333538765", "3333333333"],
+            [-38, -10],
+        ),
+        (
+            1e300,
+            -3e283,
+            ["10000000000000000225047602552044195232", "1000000000"],
+            ["10000000000000000225047602552044195232", "1000000001"],
+            ["10000000000000000225047602552044195231", "1000000000"],
+            [263, 291],
+        ),
+        (
+            5e-324,
+            0.0,
+            ["49406564584124654417656879286822137237", "4940656458"],
+            ["49406564584124654417656879286822137237", "4940656459"],
+            ["49406564584124654417656879286822137236", "4940656458"],
+            [-361, -333],
+        ),
+    ];
+
+    for (hi, lo, nearest, up, down, exponents) in cases.iter() {
+        let source = TwoFloat::try_from((*hi, *lo)).unwrap();
+        for (i, &digits) in [38, 10].iter().enumerate() {
+            for (mode, expected) in [
+                (RoundingMode::NearestEven, nearest[i]),
+                (RoundingMode::TowardPositive, up[i]),
+                (RoundingMode::TowardNegative, down[i]),
+            ]
+            .iter()
+            {
+                let (negative, result, exp10) = source.to_decimal(digits, *mode).unwrap();
+                assert!(!negative);
+                assert_eq!(
+                    &result[..digits],
+                    &parse_digits(expected)[..],
+                    "to_decimal({:?}, {}, {:?}) has incorrect digits",
+                    source,
+                    digits,
+                    mode
+                );
+                assert!(result[digits..].iter().all(|&d| d == 0));
+                assert_eq!(exp10, exponents[i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn to_decimal_directed_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let source = random_106_bit_twofloat();
+        let digits = rng.gen_range(1..=TwoFloat::MAX_DECIMAL_DIGITS);
+        let (negative, up, up_exp) = source
+            .to_decimal(digits, RoundingMode::TowardPositive)
+            .unwrap();
+        let (_, down, down_exp) = source
+            .to_decimal(digits, RoundingMode::TowardNegative)
+            .unwrap();
+        let (_, toward_zero, toward_zero_exp) =
+            source.to_decimal(digits, RoundingMode::TowardZero).unwrap();
+
+        assert_eq!(negative, source.is_sign_negative());
+        assert!(up[0] != 0 && down[0] != 0);
+        let (larger, larger_exp, smaller, smaller_exp) = if negative {
+            (down, down_exp, up, up_exp)
+        } else {
+            (up, up_exp, down, down_exp)
+        };
+        assert_eq!((toward_zero, toward_zero_exp), (smaller, smaller_exp));
+
+        let larger_value = digits_value(&larger[..digits]);
+        let smaller_value = digits_value(&smaller[..digits]);
+        let consecutive = if larger_exp == smaller_exp {
+            larger_value == smaller_value || larger_value == smaller_value + 1
+        } else {
+            larger_exp == smaller_exp + 1
+                && larger_value == 10u128.pow(digits as u32 - 1)
+                && smaller_value == 10u128.pow(digits as u32) - 1
+        };
+        assert!(
+            consecutive,
+            "to_decimal({:?}, {}) directed results are not adjacent",
+            source, digits
+        );
+
+        let larger = TwoFloat::from_decimal(false, &larger[..digits], larger_exp).unwrap();
+        let smaller = TwoFloat::from_decimal(false, &smaller[..digits], smaller_exp).unwrap();
+        assert!(
+            smaller <= source.abs() && source.abs() <= larger,
+            "to_decimal({:?}, {}) directed results do not bracket the value",
+            source,
+            digits
+        );
+    });
+}
+
+#[test]
+fn to_decimal_limits_test() {
+    let (negative, digits, exp10) = TwoFloat::from(-0.0)
+        .to_decimal(5, RoundingMode::NearestEven)
+        .unwrap();
+    assert!(negative);
+    assert_eq!(digits, [0; TwoFloat::MAX_DECIMAL_DIGITS]);
+    assert_eq!(exp10, 0);
+
+    let (_, digits, exp10) = TwoFloat::from(9.5)
+        .to_decimal(1, RoundingMode::NearestEven)
+        .unwrap();
+    assert_eq!((digits[0], exp10), (1, 1));
+    let (_, digits, exp10) = TwoFloat::from(8.5)
+        .to_decimal(1, RoundingMode::NearestEven)
+        .unwrap();
+    assert_eq!((digits[0], exp10), (8, 0));
+    let (_, digits, exp10) = TwoFloat::from(8.5)
+        .to_decimal(1, RoundingMode::NearestAway)
+        .unwrap();
+    assert_eq!((digits[0], exp10), (9, 0));
+
+    let (_, digits, exp10) = TwoFloat::MAX
+        .to_decimal(3, RoundingMode::TowardZero)
+        .unwrap();
+    assert_eq!((&digits[..3], exp10), (&[1, 7, 9][..], 306));
+
+    assert!(TwoFloat::from(1.0)
+        .to_decimal(0, RoundingMode::NearestEven)
+        .is_err());
+    assert!(TwoFloat::from(1.0)
+        .to_decimal(TwoFloat::MAX_DECIMAL_DIGITS + 1, RoundingMode::NearestEven)
+        .is_err());
+    assert!(TwoFloat::NAN
+        .to_decimal(10, RoundingMode::NearestEven)
+        .is_err());
+}
+
+// from_decimal() tests
+
+#[test]
+fn decimal_roundtrip_test() {
+    repeated_test(|| {
+        let source = random_106_bit_twofloat();
+        let (negative, digits, exp10) = source.to_decimal(33, RoundingMode::NearestEven).unwrap();
+        let result = TwoFloat::from_decimal(negative, &digits[..33], exp10).unwrap();
+
+        assert_eq!(
+            result, source,
+            "to_decimal({:?}) did not round-trip",
+            source
+        );
+    });
+}
+
+#[test]
+fn from_decimal_test() {
+    let pi_digits = parse_digits(
+        "3141592653589793238462643383279502884197169399375105820974944592307816406286",
+    );
+    let pi = TwoFloat::from_decimal(false, &pi_digits, 1 - pi_digits.len() as i32).unwrap();
+    assert!((pi - twofloat::consts::PI).abs() <= pi.ulp() * 0.5);
+
+    let tenth = TwoFloat::from_decimal(false, &[0, 0, 1], -1).unwrap();
+    assert_eq!(tenth, TwoFloat::from_ratio(1, 100) * 10.0);
+    assert_eq!(
+        TwoFloat::from_decimal(false, &[1], 400).unwrap(),
+        TwoFloat::INFINITY
+    );
+    assert_eq!(
+        TwoFloat::from_decimal(true, &[1], -400).unwrap(),
+        TwoFloat::from(-0.0)
+    );
+    assert_eq!(
+        TwoFloat::from_decimal(false, &[5], -324).unwrap(),
+        TwoFloat::from(f64::from_bits(1))
+    );
+    assert!(TwoFloat::from_decimal(false, &[1; 1001], 0).is_err());
+}
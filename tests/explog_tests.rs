@@ -36,6 +36,71 @@ fn exp_test() {
     });
 }
 
+#[test]
+fn exp_negative_test() {
+    let mut rng = rand::thread_rng();
+    let src_dist = rand::distributions::Uniform::new(0.0, 20.0);
+
+    repeated_test(|| {
+        let a = TwoFloat::from(rng.sample(src_dist));
+
+        // Both arguments are reduced to the same interval
+        let product = a.exp() * (-a).exp();
+
+        assert!(
+            (product - 1.0).abs() < 1e-30,
+            "exp({:?}) is not the reciprocal of exp({:?})",
+            -a,
+            a
+        );
+    });
+}
+
+#[test]
+fn exp_consts_test() {
+    let cases = [
+        (TwoFloat::from(1.0), twofloat::consts::E),
+        (twofloat::consts::LN_2, TwoFloat::from(2.0)),
+        (twofloat::consts::LN_10, TwoFloat::from(10.0)),
+    ];
+
+    for (a, expected) in cases.iter() {
+        let result = a.exp();
+        assert!(
+            ((result - expected) / expected).abs() < 1e-31,
+            "exp({:?}) produced {:?} instead of {:?}",
+            a,
+            result,
+            expected
+        );
+    }
+}
+
+#[test]
+fn exp_m1_small_test() {
+    let mut rng = rand::thread_rng();
+    let src_dist = rand::distributions::Uniform::new(-1e-12, 1e-12);
+
+    repeated_test(|| {
+        let a = match try_get_twofloat_with_hi(rng.sample(src_dist)) {
+            Ok(a) => a,
+            Err(_) => return,
+        };
+
+        // exp(a) - 1 = a + a^2/2 + a^3/6 + O(a^4), with a^4 below the
+        // precision of the result
+        let expected = a + a * a * 0.5 + a * a * a / 6.0;
+        let result = a.exp_m1();
+        assert!(
+            ((result - expected) / expected).abs() < 1e-31,
+            "exp_m1({:?}) produced {:?} instead of {:?}",
+            a,
+            result,
+            expected
+        );
+    });
+}
+
 #[test]
 fn exp_m1_test() {
     let mut rng = rand::thread_rng();
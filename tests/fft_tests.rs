@@ -0,0 +1,164 @@
+#![cfg(feature = "math_funcs")]
+
+#[macro_use]
+pub mod common;
+
+use common::{get_valid_twofloat, repeated_test};
+use rand::Rng;
+use twofloat::{fft, TwoFloat};
+
+type Complex = [TwoFloat; 2];
+
+// Returns a value with magnitude below 1, so that the absolute error of the
+// transforms is bounded
+fn get_value() -> TwoFloat {
+    get_valid_twofloat(|hi, _| hi.abs() < 1.0)
+}
+
+fn max_difference(a: &[Complex], b: &[Complex]) -> TwoFloat {
+    a.iter().zip(b).fold(TwoFloat::from(0.0), |acc, (x, y)| {
+        acc.max((x[0] - y[0]).abs()).max((x[1] - y[1]).abs())
+    })
+}
+
+// Returns exp(-2 pi i k / 24), computed from square roots.
+fn root_of_unity_24(k: usize) -> Complex {
+    let sqrt2 = TwoFloat::from(2.0).sqrt();
+    let sqrt6 = TwoFloat::from(6.0).sqrt();
+    let half_sqrt3 = TwoFloat::from(3.0).sqrt() * 0.5;
+    let half_sqrt2 = sqrt2 * 0.5;
+    let a = (sqrt6 + sqrt2) * 0.25;
+    let b = (sqrt6 - sqrt2) * 0.25;
+    // cos(pi k / 12) for k in 0..=6
+    let cos = [
+        TwoFloat::from(1.0),
+        a,
+        half_sqrt3,
+        half_sqrt2,
+        TwoFloat::from(0.5),
+        b,
+        TwoFloat::from(0.0),
+    ];
+    let k = k % 24;
+    let cos_k = |j: usize| match j {
+        0..=6 => cos[j],
+        7..=12 => -cos[12 - j],
+        13..=18 => -cos[j - 12],
+        _ => cos[24 - j],
+    };
+    [cos_k(k), cos_k((k + 6) % 24)]
+}
+
+#[test]
+fn exact_dft_test() {
+    let mut rng = rand::thread_rng();
+    let lengths = [1, 2, 3, 4, 6, 8, 12, 24];
+    let roots: Vec<Complex> = (0..24).map(root_of_unity_24).collect();
+    repeated_test(|| {
+        let len = lengths[rng.gen_range(0..lengths.len())];
+        let data: Vec<Complex> = (0..len).map(|_| [get_value(), get_value()]).collect();
+        let expected: Vec<Complex> = (0..len)
+            .map(|k| {
+                let zero = TwoFloat::from(0.0);
+                data.iter().enumerate().fold([zero; 2], |acc, (j, &x)| {
+                    let w = roots[j * k * (24 / len) % 24];
+                    [
+                        acc[0] + x[0] * w[0] - x[1] * w[1],
+                        acc[1] + x[0] * w[1] + x[1] * w[0],
+                    ]
+                })
+            })
+            .collect();
+
+        let mut result = data.clone();
+        fft::fft(&mut result);
+        let difference = max_difference(&result, &expected);
+        assert!(
+            difference < 1e-30,
+            "transform of length {} differs by {:?}",
+            len,
+            difference
+        );
+    });
+}
+
+#[test]
+fn round_trip_test() {
+    for &len in &[0, 1, 5, 16, 17, 100, 127, 256, 1000] {
+        let data: Vec<Complex> = (0..len).map(|_| [get_value(), get_value()]).collect();
+        let mut result = data.clone();
+        fft::fft(&mut result);
+        fft::ifft(&mut result);
+        let difference = max_difference(&result, &data);
+        assert!(
+            difference < 1e-30,
+            "round trip of length {} differs by {:?}",
+            len,
+            difference
+        );
+    }
+}
+
+#[test]
+fn parseval_test() {
+    for &len in &[7, 64, 100] {
+        let data: Vec<Complex> = (0..len).map(|_| [get_value(), get_value()]).collect();
+        let mut result = data.clone();
+        fft::fft(&mut result);
+
+        let energy = |values: &[Complex]| {
+            values.iter().fold(TwoFloat::from(0.0), |acc, x| {
+                acc + x[0] * x[0] + x[1] * x[1]
+            })
+        };
+        let expected = energy(&data) * len as f64;
+        assert!((energy(&result) - expected).abs() < expected * 1e-30);
+    }
+}
+
+#[test]
+fn rfft_test() {
+    let zero = TwoFloat::from(0.0);
+    for len in 0..40 {
+        let input: Vec<TwoFloat> = (0..len).map(|_| get_value()).collect();
+        let mut expected: Vec<Complex> = input.iter().map(|&x| [x, zero]).collect();
+        fft::fft(&mut expected);
+        expected.truncate(len / 2 + 1);
+        if len == 0 {
+            expected.push([zero; 2]);
+        }
+
+        let spectrum = fft::rfft(&input);
+        assert_eq!(spectrum.len(), len / 2 + 1);
+        let difference = max_difference(&spectrum, &expected);
+        assert!(
+            difference < 1e-30,
+            "real transform of length {} differs by {:?}",
+            len,
+            difference
+        );
+
+        let output = fft::irfft(&spectrum, len);
+        assert_eq!(output.len(), len);
+        for (x, y) in input.iter().zip(&output) {
+            assert!((x - y).abs() < 1e-30);
+        }
+    }
+}
+
+#[test]
+fn irfft_ignores_imaginary_parts_test() {
+    let mut spectrum = fft::rfft(&[1.0, 2.0, 3.0, 4.0].map(TwoFloat::from));
+    spectrum[0][1] = TwoFloat::from(5.0);
+    spectrum[2][1] = TwoFloat::from(-5.0);
+    let output = fft::irfft(&spectrum, 4);
+    for (i, x) in output.iter().enumerate() {
+        assert!((x - (i + 1) as f64).abs() < 1e-30);
+    }
+}
+
+#[test]
+#[should_panic]
+fn irfft_length_test() {
+    fft::irfft(&[[TwoFloat::from(0.0); 2]; 3], 6);
+}
@@ -0,0 +1,281 @@
+use core::convert::TryFrom;
+
+use rand::Rng;
+use twofloat::{FormatOptions, Notation, RoundingMode, TwoFloat};
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+#[test]
+fn fixed_matches_f64_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let source = get_valid_f64(|x| x.abs() > 1e-10 && x.abs() < 1e20);
+        let precision = rng.gen_range(0..10);
+        let value = TwoFloat::from(source).display_with(FormatOptions::new());
+
+        assert_eq!(
+            format!("{:.*}", precision, value),
+            format!("{:.*}", precision, source),
+            "Fixed formatting of {:?} differs from f64",
+            source
+        );
+    });
+}
+
+#[test]
+fn scientific_matches_f64_test() {
+    let mut rng = rand::thread_rng();
+    let options = FormatOptions::new().notation(Notation::Scientific);
+    repeated_test(|| {
+        let source = get_valid_f64(|x| x != 0.0);
+        let precision = rng.gen_range(0..30);
+        let value = TwoFloat::from(source).display_with(options);
+
+        assert_eq!(
+            format!("{:.*}", precision, value),
+            format!("{:.*e}", precision, source),
+            "Scientific formatting of {:?} differs from f64",
+            source
+        );
+    });
+}
+
+#[test]
+fn default_precision_test() {
+    let third = TwoFloat::from(1.0) / 3.0;
+    let options = FormatOptions::new();
+
+    assert_eq!(
+        format!("{}", TwoFloat::from(0.0).display_with(options)),
+        "0"
+    );
+    assert_eq!(
+        format!("{}", TwoFloat::from(-0.0).display_with(options)),
+        "-0"
+    );
+    assert_eq!(
+        format!("{}", TwoFloat::from(1.5).display_with(options)),
+        "1.5"
+    );
+    assert_eq!(
+        format!("{}", TwoFloat::from(1e20).display_with(options)),
+        "100000000000000000000"
+    );
+    assert_eq!(
+        format!("{}", third.display_with(options)),
+        "0.33333333333333333333333333333333"
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            third.display_with(options.notation(Notation::Scientific))
+        ),
+        "3.3333333333333333333333333333333e-1"
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            TwoFloat::from(1234.0).display_with(options.notation(Notation::Engineering))
+        ),
+        "1.234e3"
+    );
+}
+
+#[test]
+fn fixed_rounding_test() {
+    let options = FormatOptions::new();
+    let cases = [
+        (0.5, 0, "0"),
+        (1.5, 0, "2"),
+        (2.5, 0, "2"),
+        (0.75, 0, "1"),
+        (0.004, 2, "0.00"),
+        (0.005, 2, "0.01"),
+        (0.0004, 2, "0.00"),
+        (9.996, 2, "10.00"),
+        (-0.001, 2, "-0.00"),
+    ];
+    for &(value, precision, expected) in &cases {
+        assert_eq!(
+            format!(
+                "{:.*}",
+                precision,
+                TwoFloat::from(value).display_with(options)
+            ),
+            expected
+        );
+    }
+
+    let value = TwoFloat::from(0.0004);
+    let up = options.rounding(RoundingMode::TowardPositive);
+    assert_eq!(format!("{:.2}", value.display_with(up)), "0.01");
+    assert_eq!(format!("{:.2}", (-value).display_with(up)), "-0.00");
+    assert_eq!(
+        format!(
+            "{:.1}",
+            TwoFloat::from(1.25).display_with(options.rounding(RoundingMode::NearestAway))
+        ),
+        "1.3"
+    );
+}
+
+#[test]
+fn engineering_test() {
+    let options = FormatOptions::new()
+        .notation(Notation::Engineering)
+        .precision(3);
+    let cases = [
+        (1.0, "1.000e0"),
+        (12.0, "12.00e0"),
+        (123.0, "123.0e0"),
+        (1234.0, "1.234e3"),
+        (-0.012345, "-12.35e-3"),
+        (999.96, "1.000e3"),
+        (1.5e-300, "1.500e-300"),
+        (0.0, "0.000e0"),
+    ];
+    for &(value, expected) in &cases {
+        assert_eq!(
+            format!("{}", TwoFloat::from(value).display_with(options)),
+            expected
+        );
+    }
+
+    assert_eq!(
+        format!("{:.0}", TwoFloat::from(456.0).display_with(options)),
+        "500e0"
+    );
+}
+
+#[test]
+fn si_prefix_test() {
+    let options = FormatOptions::new()
+        .notation(Notation::SiPrefix)
+        .precision(2);
+    let cases = [
+        (1.0, "1.00"),
+        (4.7e3, "4.70k"),
+        (2.2e-6, "2.20µ"),
+        (-33e-12, "-33.0p"),
+        (1e30, "1.00Q"),
+        (1e-30, "1.00q"),
+        (1e33, "1.00e33"),
+        (1e-33, "1.00e-33"),
+    ];
+    for &(value, expected) in &cases {
+        assert_eq!(
+            format!("{}", TwoFloat::from(value).display_with(options)),
+            expected
+        );
+    }
+}
+
+#[test]
+fn grouping_test() {
+    let options = FormatOptions::new().grouping(',');
+    let cases = [
+        (0.0, "0"),
+        (123.0, "123"),
+        (1234.0, "1,234"),
+        (-123456.5, "-123,456.5"),
+        (1234567.0, "1,234,567"),
+        (1e20, "100,000,000,000,000,000,000"),
+    ];
+    for &(value, expected) in &cases {
+        assert_eq!(
+            format!("{}", TwoFloat::from(value).display_with(options)),
+            expected
+        );
+    }
+
+    let value = TwoFloat::from(1234.5).display_with(options.grouping('_').precision(2));
+    assert_eq!(format!("{}", value), "1_234.50");
+}
+
+#[test]
+fn padding_test() {
+    let value = TwoFloat::from(-12.5).display_with(FormatOptions::new());
+
+    assert_eq!(format!("{:8}", value), "   -12.5");
+    assert_eq!(format!("{:<8}", value), "-12.5   ");
+    assert_eq!(format!("{:^8}", value), " -12.5  ");
+    assert_eq!(format!("{:*>8.2}", value), "**-12.50");
+    assert_eq!(format!("{:08}", value), "-00012.5");
+    let positive = TwoFloat::from(12.5).display_with(FormatOptions::new());
+    assert_eq!(format!("{:+08}", positive), "+00012.5");
+    assert_eq!(format!("{:3}", value), "-12.5");
+
+    let grouped = TwoFloat::from(1234.0).display_with(FormatOptions::new().grouping(','));
+    assert_eq!(format!("{:>7}", grouped), "  1,234");
+}
+
+#[test]
+fn non_finite_test() {
+    let options = FormatOptions::new();
+
+    assert_eq!(format!("{}", TwoFloat::NAN.display_with(options)), "NaN");
+    assert_eq!(
+        format!("{}", TwoFloat::INFINITY.display_with(options)),
+        "inf"
+    );
+    assert_eq!(
+        format!("{:+}", TwoFloat::INFINITY.display_with(options)),
+        "+inf"
+    );
+    assert_eq!(
+        format!("{:>5}", TwoFloat::NEG_INFINITY.display_with(options)),
+        " -inf"
+    );
+}
+
+#[test]
+fn display_padding_test() {
+    let value = TwoFloat::try_from((1.0, 1e-17)).unwrap();
+
+    assert_eq!(format!("{:>13e}", value), "  1e0 + 1e-17");
+    assert_eq!(format!("{:<13e}", -value), "-1e0 - 1e-17 ");
+    assert_eq!(format!("{:^17.1e}", value), " 1.0e0 + 1.0e-17 ");
+    assert_eq!(format!("{:+014e}", value), "+001e0 + 1e-17");
+    assert_eq!(format!("{:-<14E}", value), "1E0 + 1E-17---");
+    assert_eq!(format!("{:>11.1}", value), "  1.0 + 0.0");
+}
+
+#[test]
+fn from_str_test() {
+    repeated_test(|| {
+        let value = get_valid_twofloat(|x, _| x.abs() > 1e-290 && x.abs() < 1e290);
+        let options = FormatOptions::new()
+            .notation(Notation::Scientific)
+            .precision(37);
+        let text = value.display_with(options).to_string();
+        let parsed: TwoFloat = text.parse().unwrap();
+        assert!(
+            (parsed - value).abs() <= value.ulp() * 2.0,
+            "{:?} parsed from {} differs from {:?}",
+            parsed,
+            text,
+            value
+        );
+    });
+
+    assert_eq!("0.5".parse::<TwoFloat>().unwrap(), TwoFloat::from(0.5));
+    assert_eq!(
+        "-1.25e2".parse::<TwoFloat>().unwrap(),
+        TwoFloat::from(-125.0)
+    );
+    let difference = "+3E-1".parse::<TwoFloat>().unwrap() - TwoFloat::from(3) / 10.0;
+    assert!(difference.abs() < 1e-32);
+    assert_eq!("inf".parse::<TwoFloat>().unwrap(), TwoFloat::INFINITY);
+    assert_eq!(
+        "-Infinity".parse::<TwoFloat>().unwrap(),
+        TwoFloat::NEG_INFINITY
+    );
+    assert!(!"NaN".parse::<TwoFloat>().unwrap().is_valid());
+    assert!("".parse::<TwoFloat>().is_err());
+    assert!("1.2.3".parse::<TwoFloat>().is_err());
+    assert!("1e".parse::<TwoFloat>().is_err());
+    assert!("abc".parse::<TwoFloat>().is_err());
+}
@@ -1,7 +1,8 @@
 #![allow(clippy::float_cmp)]
 
 use core::convert::TryFrom;
-use twofloat::{no_overlap, TwoFloat};
+use rand::Rng;
+use twofloat::{no_overlap, RoundingMode, TwoFloat};
 
 #[macro_use]
 pub mod common;
@@ -612,3 +613,183 @@ fn round_no_fract_test() {
         );
     })
 }
+
+// round_ties_even() tests
+
+#[test]
+fn round_ties_even_no_lo_word_test() {
+    repeated_test(|| {
+        let a = random_float();
+        let source = TwoFloat::from(a);
+        let result = source.round_ties_even();
+
+        assert!(
+            result.is_valid(),
+            "round_ties_even({:?}) produced invalid value",
+            source
+        );
+        assert_eq!(
+            result,
+            a.round_ties_even(),
+            "round_ties_even({:?}) produced incorrect value",
+            source
+        );
+    })
+}
+
+#[test]
+fn round_ties_even_lo_half_test() {
+    repeated_test(|| {
+        let (a, b) = get_valid_pair(|x, y| {
+            let y_half = y.trunc() + 0.5;
+            y_half.fract().abs() == 0.5 && no_overlap(x, y_half)
+        });
+        let source = TwoFloat::try_from((a, b.trunc() + 0.5)).unwrap();
+        let lower = TwoFloat::new_add(a, source.lo().floor());
+        let expected = if lower.fmod(TwoFloat::from(2.0)) == 0.0 {
+            lower
+        } else {
+            lower + 1.0
+        };
+
+        let result = source.round_ties_even();
+
+        assert!(
+            result.is_valid(),
+            "round_ties_even({:?}) produced invalid value",
+            source
+        );
+        assert_eq!(
+            result, expected,
+            "Incorrect value of round_ties_even({:?})",
+            source
+        );
+    });
+}
+
+#[test]
+fn round_ties_even_not_half_test() {
+    repeated_test(|| {
+        let source = get_valid_twofloat(|_, y| y != 0.0);
+        if source.fract().abs() == 0.5 {
+            return;
+        }
+
+        assert_eq!(
+            source.round_ties_even(),
+            source.round(),
+            "round_ties_even({:?}) differs from round",
+            source
+        );
+    });
+}
+
+// rint(), modf() and rounded integer conversion tests
+
+#[test]
+fn rint_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+
+        assert_eq!(
+            source.rint(RoundingMode::NearestEven),
+            source.round_ties_even()
+        );
+        assert_eq!(source.rint(RoundingMode::NearestAway), source.round());
+        assert_eq!(source.rint(RoundingMode::TowardZero), source.trunc());
+        assert_eq!(source.rint(RoundingMode::TowardPositive), source.ceil());
+        assert_eq!(source.rint(RoundingMode::TowardNegative), source.floor());
+    });
+}
+
+#[test]
+fn modf_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+        let (integral, fractional) = source.modf();
+
+        assert_eq!(integral, source.trunc());
+        assert_eq!(fractional, source.fract());
+        assert_eq!(
+            source - integral,
+            fractional,
+            "modf({:?}) parts do not sum to the value",
+            source
+        );
+    });
+}
+
+#[test]
+fn to_i64_rounded_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let base = rng.gen_range(-(1i64 << 62)..1i64 << 62);
+        let quarters = rng.gen_range(0..4);
+        let source = TwoFloat::from(base) + quarters as f64 * 0.25;
+
+        let nearest_even = match quarters {
+            0 | 1 => base,
+            2 => base + (base & 1),
+            _ => base + 1,
+        };
+        let nearest_away = match quarters {
+            0 | 1 => base,
+            2 if base < 0 => base,
+            _ => base + 1,
+        };
+        let toward_zero = if base < 0 && quarters != 0 {
+            base + 1
+        } else {
+            base
+        };
+        let ceil = if quarters == 0 { base } else { base + 1 };
+
+        assert_eq!(
+            source.to_i64_rounded(RoundingMode::NearestEven).unwrap(),
+            nearest_even
+        );
+        assert_eq!(
+            source.to_i64_rounded(RoundingMode::NearestAway).unwrap(),
+            nearest_away
+        );
+        assert_eq!(
+            source.to_i64_rounded(RoundingMode::TowardZero).unwrap(),
+            toward_zero
+        );
+        assert_eq!(
+            source.to_i64_rounded(RoundingMode::TowardPositive).unwrap(),
+            ceil
+        );
+        assert_eq!(
+            source.to_i64_rounded(RoundingMode::TowardNegative).unwrap(),
+            base
+        );
+        assert_eq!(
+            source.to_i128_rounded(RoundingMode::NearestEven).unwrap(),
+            nearest_even as i128
+        );
+    });
+}
+
+#[test]
+fn to_i64_rounded_range_test() {
+    let max = TwoFloat::from(i64::MAX);
+    assert_eq!(
+        (max + 0.25)
+            .to_i64_rounded(RoundingMode::NearestEven)
+            .unwrap(),
+        i64::MAX
+    );
+    assert!((max + 0.75)
+        .to_i64_rounded(RoundingMode::NearestEven)
+        .is_err());
+    assert!((max + 0.25)
+        .to_i64_rounded(RoundingMode::TowardPositive)
+        .is_err());
+    assert_eq!(
+        (max + 0.75)
+            .to_i128_rounded(RoundingMode::NearestEven)
+            .unwrap(),
+        i64::MAX as i128 + 1
+    );
+}
@@ -107,3 +107,196 @@ fn tanh_atanh_test() {
         );
     });
 }
+
+fn relative_error(a: TwoFloat, b: TwoFloat) -> TwoFloat {
+    ((a - b) / b).abs()
+}
+
+#[test]
+fn sinh_small_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new_inclusive(-1e-4, 1e-4);
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        if source == 0.0 {
+            return;
+        }
+
+        let x2 = source * source;
+        let expected = source * (1.0 + x2 / 6.0 * (1.0 + x2 / 20.0 * (1.0 + x2 / 42.0)));
+        let result = source.sinh();
+        assert!(
+            relative_error(result, expected) < 1e-30,
+            "sinh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            result,
+            expected
+        );
+    });
+}
+
+#[test]
+fn cosh_small_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new_inclusive(-1e-4, 1e-4);
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        let x2 = source * source;
+        let expected = 1.0 + x2 / 2.0 * (1.0 + x2 / 12.0 * (1.0 + x2 / 30.0));
+        let result = source.cosh();
+        assert!(
+            relative_error(result, expected) < 1e-30,
+            "cosh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            result,
+            expected
+        );
+    });
+}
+
+#[test]
+fn tanh_small_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new_inclusive(-1e-4, 1e-4);
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        if source == 0.0 {
+            return;
+        }
+
+        let x2 = source * source;
+        let expected =
+            source * (1.0 - x2 / 3.0 + 2.0 * x2 * x2 / 15.0 - 17.0 * x2 * x2 * x2 / 315.0);
+        let result = source.tanh();
+        assert!(
+            relative_error(result, expected) < 1e-30,
+            "tanh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            result,
+            expected
+        );
+    });
+}
+
+#[test]
+fn asinh_small_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new_inclusive(-1e-4, 1e-4);
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        if source == 0.0 {
+            return;
+        }
+
+        let x2 = source * source;
+        let expected =
+            source * (1.0 - x2 / 6.0 + 3.0 * x2 * x2 / 40.0 - 5.0 * x2 * x2 * x2 / 112.0);
+        let result = source.asinh();
+        assert!(
+            relative_error(result, expected) < 1e-30,
+            "asinh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            result,
+            expected
+        );
+    });
+}
+
+#[test]
+fn atanh_small_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new_inclusive(-1e-4, 1e-4);
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        if source == 0.0 {
+            return;
+        }
+
+        let x2 = source * source;
+        let expected = source * (1.0 + x2 / 3.0 + x2 * x2 / 5.0 + x2 * x2 * x2 / 7.0);
+        let result = source.atanh();
+        assert!(
+            relative_error(result, expected) < 1e-30,
+            "atanh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            result,
+            expected
+        );
+    });
+}
+
+#[test]
+fn cosh_sinh_large_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new(700.0, 710.4);
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        let half_exp = source / 2.0;
+        let expected = (half_exp.exp() * 0.5) * half_exp.exp();
+
+        let sinh = source.sinh();
+        assert!(
+            sinh.is_valid(),
+            "sinh({:?}) returned invalid result",
+            source
+        );
+        assert!(
+            relative_error(sinh, expected) < 1e-20,
+            "sinh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            sinh,
+            expected
+        );
+        assert_eq!(-sinh, (-source).sinh(), "sinh({:?}) was not odd", source);
+
+        let cosh = source.cosh();
+        assert!(
+            cosh.is_valid(),
+            "cosh({:?}) returned invalid result",
+            source
+        );
+        assert!(
+            relative_error(cosh, expected) < 1e-20,
+            "cosh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            cosh,
+            expected
+        );
+        assert_eq!(cosh, (-source).cosh(), "cosh({:?}) was not even", source);
+    });
+}
+
+#[test]
+fn tanh_large_test() {
+    assert_eq!(TwoFloat::from(1000.0).tanh(), 1.0);
+    assert_eq!(TwoFloat::from(-1000.0).tanh(), -1.0);
+    assert_eq!(TwoFloat::MAX.tanh(), 1.0);
+}
+
+#[test]
+fn asinh_acosh_large_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new(1e20, 1e300);
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        let expected = source.ln() + twofloat::consts::LN_2;
+
+        let asinh = source.asinh();
+        assert!(
+            relative_error(asinh, expected) < 1e-30,
+            "asinh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            asinh,
+            expected
+        );
+
+        let acosh = source.acosh();
+        assert!(
+            relative_error(acosh, expected) < 1e-30,
+            "acosh({:?}) was inaccurate: got {:?}, expected {:?}",
+            source,
+            acosh,
+            expected
+        );
+    });
+}
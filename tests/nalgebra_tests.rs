@@ -0,0 +1,76 @@
+#![cfg(feature = "nalgebra")]
+
+use nalgebra::{Matrix3, Vector3};
+use twofloat::TwoFloat;
+
+fn hilbert() -> Matrix3<TwoFloat> {
+    Matrix3::from_fn(|i, j| TwoFloat::from(1.0) / ((i + j + 1) as f64))
+}
+
+fn residual(a: Matrix3<TwoFloat>, b: Matrix3<TwoFloat>) -> f64 {
+    (a - b)
+        .iter()
+        .map(|x| f64::from(x.abs()))
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn inverse_test() {
+    let h = hilbert();
+    let inverse = h.try_inverse().unwrap();
+    assert!(residual(h * inverse, Matrix3::identity()) < 1e-28);
+
+    // The inverse of the 3x3 Hilbert matrix has integer entries
+    let expected = Matrix3::new(9.0, -36.0, 30.0, -36.0, 192.0, -180.0, 30.0, -180.0, 180.0)
+        .map(TwoFloat::from);
+    assert!(residual(inverse, expected) < 1e-26);
+}
+
+#[test]
+fn solve_test() {
+    let h = hilbert();
+    let x = Vector3::new(1.0, -2.0, 3.0).map(TwoFloat::from);
+    let b = h * x;
+    let solved = h.lu().solve(&b).unwrap();
+    for (a, b) in solved.iter().zip(x.iter()) {
+        assert!(f64::from((*a - *b).abs()) < 1e-27);
+    }
+}
+
+#[test]
+fn qr_test() {
+    let h = hilbert();
+    let qr = h.qr();
+    let (q, r) = (qr.q(), qr.r());
+    assert!(residual(q * r, h) < 1e-30);
+    assert!(residual(q.transpose() * q, Matrix3::identity()) < 1e-30);
+}
+
+#[test]
+fn svd_test() {
+    let h = hilbert();
+    let svd = h.svd(true, true);
+    let u = svd.u.unwrap();
+    let v_t = svd.v_t.unwrap();
+    let sigma = Matrix3::from_diagonal(&svd.singular_values);
+    assert!(residual(u * sigma * v_t, h) < 1e-29);
+
+    // Condition number of the 3x3 Hilbert matrix
+    let max = svd.singular_values.max();
+    let min = svd.singular_values.min();
+    let cond = f64::from(max / min);
+    assert!((cond - 524.056_777_586_060_4).abs() < 1e-9);
+}
+
+#[test]
+fn symmetric_eigen_test() {
+    let h = hilbert();
+    let eigen = h.symmetric_eigen();
+    let vectors = eigen.eigenvectors;
+    let values = Matrix3::from_diagonal(&eigen.eigenvalues);
+    assert!(residual(vectors * values * vectors.transpose(), h) < 1e-29);
+
+    let trace = eigen.eigenvalues.sum();
+    let expected = TwoFloat::from(1.0) + TwoFloat::from(1.0) / 3.0 + TwoFloat::from(1.0) / 5.0;
+    assert!(f64::from((trace - expected).abs()) < 1e-30);
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "ndarray")]
+
+use ndarray::{Array, Array1, Array2};
+use rand::Rng;
+use twofloat::{array, TwoFloat};
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+#[test]
+fn scalar_operand_test() {
+    repeated_test(|| {
+        let a = get_valid_twofloat(|x, _| x.abs() < 1e150);
+        let b = get_valid_twofloat(|x, _| x.abs() < 1e150);
+        let values = Array1::from(vec![a, b]);
+
+        let product = &values * a;
+        assert_eq!(product[0], a * a);
+        assert_eq!(product[1], b * a);
+
+        let sum = values + b;
+        assert_eq!(sum[0], a + b);
+        assert_eq!(sum[1], b + b);
+    });
+}
+
+#[test]
+fn sum_test() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let values: Array2<f64> = Array::from_shape_fn((8, 8), |_| rng.gen_range(-1e10..1e10));
+        let mut expected = TwoFloat::from(0.0);
+        for &x in values.iter() {
+            expected += x;
+        }
+
+        assert_eq!(array::sum(&values), expected);
+        assert_eq!(array::sum(&values.view()), expected);
+    }
+
+    let cancelling = Array1::from(vec![1e300, 1.0, -1e300, 1e-300]);
+    assert_eq!(array::sum(&cancelling), TwoFloat::from(1.0) + 1e-300);
+}
+
+#[test]
+fn dot_test() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let a: Array1<f64> = Array::from_shape_fn(16, |_| rng.gen_range(-1e10..1e10));
+        let b: Array1<f64> = Array::from_shape_fn(16, |_| rng.gen_range(-1e10..1e10));
+        let mut expected = TwoFloat::from(0.0);
+        for (&x, &y) in a.iter().zip(b.iter()) {
+            expected += TwoFloat::new_mul(x, y);
+        }
+
+        assert_eq!(array::dot(&a, &b), expected);
+        assert_eq!(array::dot(&b.view(), &a), array::dot(&b, &a));
+    }
+
+    // Exact products of values with 27-bit significands
+    let a = Array1::from(vec![134217727.0, 1.0, -134217727.0]);
+    let b = Array1::from(vec![134217729.0, 0.5, 134217729.0]);
+    assert_eq!(array::dot(&a, &b), 0.5);
+}
+
+#[test]
+#[should_panic]
+fn dot_length_mismatch_test() {
+    let a = Array1::from(vec![1.0, 2.0]);
+    let b = Array1::from(vec![1.0]);
+    array::dot(&a, &b);
+}
+
+#[cfg(feature = "math_funcs")]
+type ArrayFn = fn(&Array2<TwoFloat>) -> Array2<TwoFloat>;
+#[cfg(feature = "math_funcs")]
+type ScalarFn = fn(TwoFloat) -> TwoFloat;
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn elementwise_test() {
+    let values: Array2<TwoFloat> =
+        Array::from_shape_fn((4, 4), |_| get_valid_twofloat(|x, _| x > 0.0 && x < 1.0));
+
+    let functions: [(ArrayFn, ScalarFn); 12] = [
+        (array::exp, TwoFloat::exp),
+        (array::exp_m1, TwoFloat::exp_m1),
+        (array::ln, TwoFloat::ln),
+        (array::ln_1p, TwoFloat::ln_1p),
+        (array::sqrt, TwoFloat::sqrt),
+        (array::cbrt, TwoFloat::cbrt),
+        (array::sin, TwoFloat::sin),
+        (array::cos, TwoFloat::cos),
+        (array::tan, TwoFloat::tan),
+        (array::asin, TwoFloat::asin),
+        (array::acos, TwoFloat::acos),
+        (array::atan, TwoFloat::atan),
+    ];
+
+    for (array_fn, scalar_fn) in functions {
+        let result = array_fn(&values);
+        assert_eq!(result.shape(), values.shape());
+        for (&x, &y) in values.iter().zip(result.iter()) {
+            assert_eq!(y, scalar_fn(x));
+        }
+    }
+
+    let view = values.slice(ndarray::s![1.., ..2]);
+    assert_eq!(array::exp(&view), view.mapv(TwoFloat::exp));
+}
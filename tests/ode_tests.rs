@@ -0,0 +1,263 @@
+#[macro_use]
+pub mod common;
+
+use common::{repeated_test, try_get_twofloat_with_hi};
+use rand::Rng;
+use twofloat::{ode, TwoFloat, TwoFloatError};
+
+// y' = y^2 with y(0) = 1, which has the solution y = 1 / (1 - t)
+fn square(_: TwoFloat, y: &[TwoFloat; 1], dydt: &mut [TwoFloat; 1]) {
+    dydt[0] = y[0] * y[0];
+}
+
+#[cfg(feature = "math_funcs")]
+// Acceleration in a Kepler orbit with unit gravitational parameter
+fn kepler(x: &[TwoFloat; 2], a: &mut [TwoFloat; 2]) {
+    let r2 = x[0] * x[0] + x[1] * x[1];
+    let r3 = r2 * r2.sqrt();
+    a[0] = -x[0] / r3;
+    a[1] = -x[1] / r3;
+}
+
+#[cfg(feature = "math_funcs")]
+fn kepler_energy(x: &[TwoFloat; 2], v: &[TwoFloat; 2]) -> TwoFloat {
+    (v[0] * v[0] + v[1] * v[1]) * 0.5 - (x[0] * x[0] + x[1] * x[1]).sqrt().recip()
+}
+
+#[test]
+fn rk4_order_test() {
+    let zero = TwoFloat::from(0.0);
+    let half = TwoFloat::from(0.5);
+    let mut errors = [zero; 2];
+    for (error, &steps) in errors.iter_mut().zip(&[1000, 2000]) {
+        let mut y = [TwoFloat::from(1.0)];
+        let t = ode::rk4(square, zero, &mut y, half / steps as f64, steps);
+        assert!((t - half).abs() < 1e-31);
+        *error = (y[0] - 2.0).abs();
+    }
+
+    // Halving the step divides the error by 16
+    let ratio = errors[0] / errors[1];
+    assert!(ratio > 15.0 && ratio < 17.0, "error ratio {:?}", ratio);
+}
+
+#[test]
+fn rk4_quadratic_test() {
+    // RK4 reduces to Simpson's rule when the derivative depends only on t,
+    // so a single step integrates y' = a + bt without truncation error
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let mut values = [TwoFloat::from(0.0); 5];
+        for value in values.iter_mut() {
+            *value = match try_get_twofloat_with_hi(rng.gen_range(-10.0..10.0)) {
+                Ok(value) => value,
+                Err(_) => return,
+            };
+        }
+        let [a, b, t0, y0, h] = values;
+        let mut y = [y0];
+        let t = ode::rk4(
+            |t, _: &[TwoFloat; 1], dydt: &mut [TwoFloat; 1]| dydt[0] = a + b * t,
+            t0,
+            &mut y,
+            h,
+            1,
+        );
+
+        let expected = y0 + a * h + b * (t0 * 2.0 + h) * h * 0.5;
+        assert_eq!(t, t0 + h);
+        assert!(
+            (y[0] - expected).abs() < 1e-28,
+            "{:?} differs from {:?}",
+            y[0],
+            expected
+        );
+    });
+}
+
+#[test]
+fn compensated_time_test() {
+    let zero = TwoFloat::from(0.0);
+    let h = TwoFloat::from(1.0) / 7.0;
+    let steps = 1000000;
+    let t = ode::rk4(
+        |_, _: &[TwoFloat; 1], dydt: &mut [TwoFloat; 1]| dydt[0] = zero,
+        zero,
+        &mut [zero],
+        h,
+        steps,
+    );
+
+    // Uncompensated summation gives a relative error of about 3e-32
+    let expected = h * steps as f64;
+    assert!(
+        (t - expected).abs() < expected * 1e-33,
+        "{:?} differs from {:?}",
+        t,
+        expected
+    );
+}
+
+#[test]
+fn dormand_prince_test() {
+    let zero = TwoFloat::from(0.0);
+    let tolerance = TwoFloat::from(1e-26);
+    let mut y = [TwoFloat::from(1.0)];
+    let steps = ode::dormand_prince(
+        square,
+        zero,
+        TwoFloat::from(0.5),
+        &mut y,
+        tolerance,
+        TwoFloat::from(1e-3),
+        100000,
+    )
+    .unwrap();
+    assert!(steps > 0);
+    assert!((y[0] - 2.0).abs() < 1e-25, "{:?} differs from 2", y[0]);
+
+    // Integrating backwards returns to the initial value
+    ode::dormand_prince(
+        square,
+        TwoFloat::from(0.5),
+        zero,
+        &mut y,
+        tolerance,
+        TwoFloat::from(1e-3),
+        100000,
+    )
+    .unwrap();
+    assert!((y[0] - 1.0).abs() < 1e-25, "{:?} differs from 1", y[0]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dormand_prince_vec_test() {
+    let zero = TwoFloat::from(0.0);
+    // Rotation, which conserves the radius
+    let mut y = vec![TwoFloat::from(1.0), zero];
+    ode::dormand_prince(
+        |_, y: &Vec<TwoFloat>, dydt: &mut Vec<TwoFloat>| {
+            dydt[0] = -y[1];
+            dydt[1] = y[0];
+        },
+        zero,
+        TwoFloat::from(2.0),
+        &mut y,
+        TwoFloat::from(1e-26),
+        TwoFloat::from(0.1),
+        100000,
+    )
+    .unwrap();
+
+    let radius = y[0] * y[0] + y[1] * y[1];
+    assert!((radius - 1.0).abs() < 1e-24, "radius {:?}", radius);
+}
+
+#[test]
+fn dormand_prince_error_test() {
+    let zero = TwoFloat::from(0.0);
+    let tolerance = TwoFloat::from(1e-28);
+    let step = TwoFloat::from(1e-3);
+    let mut y = [TwoFloat::from(1.0)];
+    assert!(matches!(
+        ode::dormand_prince(square, TwoFloat::NAN, zero, &mut y, tolerance, step, 10),
+        Err(TwoFloatError::ConversionError)
+    ));
+    assert_eq!(
+        ode::dormand_prince(square, zero, zero, &mut y, tolerance, step, 10).unwrap(),
+        0
+    );
+    assert_eq!(y[0], 1.0);
+
+    // The state is left at the last accepted step
+    assert!(matches!(
+        ode::dormand_prince(
+            square,
+            zero,
+            TwoFloat::from(0.5),
+            &mut y,
+            tolerance,
+            step,
+            10
+        ),
+        Err(TwoFloatError::ConvergenceError)
+    ));
+    assert!(y[0] > 1.0 && y[0] < 2.0);
+
+    // The solution is unbounded at t = 1
+    let mut y = [TwoFloat::from(1.0)];
+    assert!(ode::dormand_prince(
+        square,
+        zero,
+        TwoFloat::from(2.0),
+        &mut y,
+        tolerance,
+        step,
+        100000
+    )
+    .is_err());
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn symplectic_energy_test() {
+    let zero = TwoFloat::from(0.0);
+    // Elliptical orbit with eccentricity 0.5 and period 2 pi
+    let initial_x = [TwoFloat::from(0.5), zero];
+    let initial_v = [zero, TwoFloat::from(3.0).sqrt()];
+    let initial_energy = kepler_energy(&initial_x, &initial_v);
+    let h = TwoFloat::from(1.0) / 256.0;
+
+    let (mut x, mut v) = (initial_x, initial_v);
+    ode::leapfrog(kepler, &mut x, &mut v, h, 20000);
+    let leapfrog_error = (kepler_energy(&x, &v) - initial_energy).abs();
+    assert!(leapfrog_error < 1e-3, "leapfrog error {:?}", leapfrog_error);
+
+    let (mut x, mut v) = (initial_x, initial_v);
+    ode::yoshida(kepler, &mut x, &mut v, h, 20000);
+    let yoshida_error = (kepler_energy(&x, &v) - initial_energy).abs();
+    assert!(yoshida_error < 1e-7, "Yoshida error {:?}", yoshida_error);
+    assert!(yoshida_error < leapfrog_error);
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn symplectic_reversibility_test() {
+    let zero = TwoFloat::from(0.0);
+    type Method = fn(
+        fn(&[TwoFloat; 2], &mut [TwoFloat; 2]),
+        &mut [TwoFloat; 2],
+        &mut [TwoFloat; 2],
+        TwoFloat,
+        usize,
+    );
+    let methods: [Method; 2] = [ode::leapfrog, ode::yoshida];
+
+    for method in &methods {
+        let initial_x = [TwoFloat::from(0.5), zero];
+        let initial_v = [zero, TwoFloat::from(3.0).sqrt()];
+        let h = TwoFloat::from(1.0) / 64.0;
+
+        // Integrating forwards and then with reversed velocity returns to
+        // the initial state up to rounding
+        let (mut x, mut v) = (initial_x, initial_v);
+        method(kepler, &mut x, &mut v, h, 1000);
+        v = [-v[0], -v[1]];
+        method(kepler, &mut x, &mut v, h, 1000);
+        for i in 0..2 {
+            assert!((x[i] - initial_x[i]).abs() < 1e-26);
+            assert!((v[i] + initial_v[i]).abs() < 1e-26);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn symplectic_length_test() {
+    let zero = TwoFloat::from(0.0);
+    let mut x = vec![zero; 2];
+    let mut v = vec![zero; 3];
+    ode::leapfrog(|_, _: &mut Vec<TwoFloat>| {}, &mut x, &mut v, zero, 1);
+}
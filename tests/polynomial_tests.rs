@@ -0,0 +1,215 @@
+use rand::Rng;
+use twofloat::{Polynomial, TwoFloat};
+
+#[macro_use]
+pub mod common;
+
+use common::{repeated_test, try_get_twofloat_with_hi};
+
+// Returns a value with a random sign, a high word with magnitude in the given
+// range and a random low word.
+fn try_get_value(low: f64, high: f64) -> Option<TwoFloat> {
+    let mut rng = rand::thread_rng();
+    let hi = rng.gen_range(low..high) * if rng.gen() { 1.0 } else { -1.0 };
+    try_get_twofloat_with_hi(hi).ok()
+}
+
+fn try_get_coefficients(len: usize) -> Option<Vec<TwoFloat>> {
+    (0..len).map(|_| try_get_value(1e-3, 1e3)).collect()
+}
+
+#[test]
+fn empty_test() {
+    let p = Polynomial::<TwoFloat>::new(&[]);
+    let x = TwoFloat::from(2.0);
+    assert_eq!(p.horner(x), 0.0);
+    assert_eq!(p.estrin(x), 0.0);
+    assert_eq!(
+        p.horner_with_derivative(x),
+        (TwoFloat::from(0.0), TwoFloat::from(0.0))
+    );
+    assert_eq!(p.horner_with_error_bound(x), (TwoFloat::from(0.0), 0.0));
+    assert_eq!(Polynomial::<f64>::new(&[]).compensated_horner(2.0), 0.0);
+}
+
+#[test]
+fn horner_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        // Integer coefficients and x = m / 8 keep every intermediate value
+        // within 106 bits, so Horner's rule is exact and can be compared with
+        // the integer sum of c[i] m^i 8^(n - 1 - i)
+        let len = rng.gen_range(1..12);
+        let coefficients: Vec<i128> = (0..len).map(|_| rng.gen_range(-1000..=1000)).collect();
+        let m: i128 = rng.gen_range(-16..=16);
+
+        let numerator: i128 = coefficients
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| c * m.pow(i as u32) * 8i128.pow((len - 1 - i) as u32))
+            .sum();
+        let expected = TwoFloat::from(numerator).ldexp(-3 * (len as i32 - 1));
+
+        let extended: Vec<_> = coefficients.iter().map(|&c| TwoFloat::from(c)).collect();
+        let x = TwoFloat::from(m as f64 / 8.0);
+        assert_eq!(
+            Polynomial::new(&extended).horner(x),
+            expected,
+            "Horner evaluation of {:?} at {:?} is not exact",
+            coefficients,
+            x
+        );
+    });
+
+    // Closed forms: the geometric series and (x + 1)^4 at exact arguments
+    let ones = [TwoFloat::from(1.0); 8];
+    let x = TwoFloat::from(3.0);
+    assert_eq!(Polynomial::new(&ones).horner(x), (3f64.powi(8) - 1.0) / 2.0);
+    let binomial = [1.0, 4.0, 6.0, 4.0, 1.0].map(TwoFloat::from);
+    let x = TwoFloat::from(0.25);
+    assert_eq!(Polynomial::new(&binomial).horner(x), 1.25f64.powi(4));
+}
+
+#[test]
+fn estrin_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let len = rng.gen_range(1..20);
+        let (coefficients, x) = match (try_get_coefficients(len), try_get_value(0.1, 2.0)) {
+            (Some(coefficients), Some(x)) => (coefficients, x),
+            _ => return,
+        };
+        let p = Polynomial::new(&coefficients);
+
+        let (value, bound) = p.horner_with_error_bound(x);
+        let scale = coefficients
+            .iter()
+            .rev()
+            .fold(TwoFloat::from(0.0), |acc, c| acc * x.abs() + c.abs());
+        assert!(
+            (p.estrin(x) - value).abs() <= bound + scale * 1e-29,
+            "Estrin evaluation of {:?} at {:?} differs from Horner",
+            coefficients,
+            x
+        );
+    });
+}
+
+#[test]
+fn derivative_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let len = rng.gen_range(1..12);
+        let (coefficients, x) = match (try_get_coefficients(len), try_get_value(0.1, 2.0)) {
+            (Some(coefficients), Some(x)) => (coefficients, x),
+            _ => return,
+        };
+        let derivative: Vec<_> = coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| c * i as f64)
+            .collect();
+
+        let (value, slope) = Polynomial::new(&coefficients).horner_with_derivative(x);
+        let expected = Polynomial::new(&derivative).horner(x);
+        let scale = derivative
+            .iter()
+            .rev()
+            .fold(TwoFloat::from(0.0), |acc, c| acc * x.abs() + c.abs());
+        assert_eq!(value, Polynomial::new(&coefficients).horner(x));
+        assert!((slope - expected).abs() <= scale * 1e-29);
+    });
+}
+
+#[test]
+fn compensated_horner_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let len = rng.gen_range(1..12);
+        let coefficients: Vec<f64> = (0..len)
+            .map(|_| rng.gen_range(1e-3..1e3) * if rng.gen() { 1.0 } else { -1.0 })
+            .collect();
+        let extended: Vec<_> = coefficients.iter().map(|&c| TwoFloat::from(c)).collect();
+        let x: f64 = rng.gen_range(0.1..2.0) * if rng.gen() { 1.0 } else { -1.0 };
+
+        let (expected, bound) =
+            Polynomial::new(&extended).horner_with_error_bound(TwoFloat::from(x));
+        let scale = extended
+            .iter()
+            .rev()
+            .fold(TwoFloat::from(0.0), |acc, c| acc * x.abs() + c.abs());
+        let result = Polynomial::new(&coefficients).compensated_horner(x);
+        assert!((result - expected).abs() <= bound + scale * 1e-29);
+    });
+
+    // Expansion of (x - 1)^7, which loses all accuracy in plain Horner
+    let p = Polynomial::new(&[-1.0, 7.0, -21.0, 35.0, -35.0, 21.0, -7.0, 1.0]);
+    let x = 1.0 + 2f64.powi(-8);
+    assert_eq!(p.compensated_horner(x), 2f64.powi(-56));
+}
+
+// Exact value `mantissa * 2^exponent`, which avoids the gcd reductions of
+// `BigRational` when evaluating polynomials with widely spaced words.
+#[cfg(feature = "num_rational")]
+#[derive(Clone)]
+struct Dyadic(num_bigint::BigInt, i32);
+
+#[cfg(feature = "num_rational")]
+impl Dyadic {
+    fn from_f64(value: f64) -> Self {
+        let (mantissa, exponent, sign) = num_traits::float::FloatCore::integer_decode(value);
+        Self(num_bigint::BigInt::from(mantissa) * sign, exponent.into())
+    }
+
+    fn from_twofloat(value: TwoFloat) -> Self {
+        Self::from_f64(value.hi()).add(&Self::from_f64(value.lo()))
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let exponent = self.1.min(other.1);
+        let align = |value: &Self| &value.0 << (value.1 - exponent) as usize;
+        Self(align(self) + align(other), exponent)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self(&self.0 * &other.0, self.1 + other.1)
+    }
+
+    fn abs_le(&self, other: &Self) -> bool {
+        let exponent = self.1.min(other.1);
+        let align = |value: &Self| value.0.magnitude() << (value.1 - exponent) as usize;
+        align(self) <= align(other)
+    }
+}
+
+#[cfg(feature = "num_rational")]
+#[test]
+fn error_bound_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let len = rng.gen_range(1..12);
+        let (coefficients, x) = match (try_get_coefficients(len), try_get_value(0.1, 2.0)) {
+            (Some(coefficients), Some(x)) => (coefficients, x),
+            _ => return,
+        };
+
+        let exact_x = Dyadic::from_twofloat(x);
+        let exact = coefficients
+            .iter()
+            .rev()
+            .fold(Dyadic::from_f64(0.0), |acc, &c| {
+                acc.mul(&exact_x).add(&Dyadic::from_twofloat(c))
+            });
+
+        let (value, bound) = Polynomial::new(&coefficients).horner_with_error_bound(x);
+        let error = exact.add(&Dyadic::from_twofloat(-value));
+        assert!(
+            error.abs_le(&Dyadic::from_f64(bound)),
+            "error bound {} for {:?} at {:?} is too small",
+            bound,
+            coefficients,
+            x
+        );
+    });
+}
@@ -227,3 +227,43 @@ fn powf_test() {
         );
     });
 }
+
+// Tests for directed square roots
+
+#[test]
+fn sqrt_directed_test() {
+    repeated_test(|| {
+        let a = get_valid_twofloat(|x, _| x > 0.0);
+        let up = a.sqrt_up();
+        let down = a.sqrt_down();
+
+        // Small values are scaled exactly so that the residuals do not
+        // underflow
+        let scale = if a.hi() < 1.0 { -a.ilogb() / 2 } else { 0 };
+        let scaled_a = a.ldexp(2 * scale);
+        let (scaled_up, scaled_down) = (up.ldexp(scale), down.ldexp(scale));
+
+        assert!(
+            scaled_up.mul_add(scaled_up, -scaled_a) >= 0.0,
+            "sqrt_up({:?}) is less than the square root",
+            a
+        );
+        assert!(
+            scaled_down.mul_add(scaled_down, -scaled_a) <= 0.0,
+            "sqrt_down({:?}) is greater than the square root",
+            a
+        );
+        assert!(up - down <= up.ulp() * 2.0);
+    });
+}
+
+#[test]
+fn sqrt_directed_exact_test() {
+    repeated_test(|| {
+        let a = get_valid_f64(|x| x.abs() > 1e-130 && x.abs() < 1e130);
+        let square = TwoFloat::new_mul(a, a);
+
+        assert_eq!(square.sqrt_up(), a.abs());
+        assert_eq!(square.sqrt_down(), a.abs());
+    });
+}
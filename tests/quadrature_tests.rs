@@ -0,0 +1,163 @@
+#![cfg(feature = "math_funcs")]
+
+#[macro_use]
+pub mod common;
+
+use common::{repeated_test, try_get_twofloat_with_hi};
+use rand::Rng;
+use twofloat::{consts, quadrature, TwoFloat, TwoFloatError};
+
+type Integrand = fn(TwoFloat) -> TwoFloat;
+
+// Integrands that are evaluated to full precision, with their limits and
+// exact integrals
+fn cases() -> [(Integrand, TwoFloat, TwoFloat, TwoFloat); 4] {
+    let zero = TwoFloat::from(0.0);
+    let one = TwoFloat::from(1.0);
+    [
+        (|x| (x * x + 1.0).recip(), zero, one, consts::FRAC_PI_4),
+        (|x| (x + 1.0).recip(), zero, one, consts::LN_2),
+        (
+            |x| (x * x + 1.0).recip(),
+            zero,
+            TwoFloat::INFINITY,
+            consts::FRAC_PI_2,
+        ),
+        (
+            |x| (x * x + 1.0).recip(),
+            TwoFloat::NEG_INFINITY,
+            TwoFloat::INFINITY,
+            consts::PI,
+        ),
+    ]
+}
+
+#[test]
+fn gauss_kronrod_test() {
+    let tolerance = TwoFloat::from(1e-30);
+    for &(f, lower, upper, expected) in &cases() {
+        let (value, error) = quadrature::gauss_kronrod(f, lower, upper, tolerance, 30).unwrap();
+        assert!(
+            (value - expected).abs() < 1e-29,
+            "{:?} differs from {:?}",
+            value,
+            expected
+        );
+        assert!(error <= tolerance);
+
+        let (reversed, _) = quadrature::gauss_kronrod(f, upper, lower, tolerance, 30).unwrap();
+        assert_eq!(reversed, -value);
+    }
+}
+
+#[test]
+fn tanh_sinh_test() {
+    let tolerance = TwoFloat::from(1e-30);
+    for &(f, lower, upper, expected) in &cases() {
+        let (value, error) = quadrature::tanh_sinh(f, lower, upper, tolerance, 12).unwrap();
+        assert!(
+            (value - expected).abs() < 1e-29,
+            "{:?} differs from {:?}",
+            value,
+            expected
+        );
+        assert!(error <= tolerance);
+    }
+
+    // Integrands with singularities at the ends of the interval
+    let zero = TwoFloat::from(0.0);
+    let one = TwoFloat::from(1.0);
+    let (value, _) = quadrature::tanh_sinh(|x| x.sqrt().recip(), zero, one, tolerance, 12).unwrap();
+    assert!((value - 2.0).abs() < 1e-29);
+    let (value, _) =
+        quadrature::tanh_sinh(|x| x.sqrt(), zero, TwoFloat::from(4.0), tolerance, 12).unwrap();
+    assert!((value - TwoFloat::from(16.0) / 3.0).abs() < 1e-29);
+    let (value, _) =
+        quadrature::tanh_sinh(|x| (x.sqrt() * (x + 1.0)).recip(), zero, one, tolerance, 12)
+            .unwrap();
+    assert!((value - consts::FRAC_PI_2).abs() < 1e-29);
+
+    // Accuracy is limited by that of the integrand
+    let (value, _) = quadrature::tanh_sinh(
+        |x| (-x * x).exp(),
+        TwoFloat::NEG_INFINITY,
+        TwoFloat::INFINITY,
+        TwoFloat::from(1e-20),
+        12,
+    )
+    .unwrap();
+    assert!((value - consts::PI.sqrt()).abs() < 1e-20);
+}
+
+#[test]
+fn gauss_legendre_test() {
+    // Exact for polynomials of degree up to 2n - 1
+    for order in 1..12 {
+        let degree = 2 * order - 1;
+        let value = quadrature::gauss_legendre(
+            |x| x.powi(degree as i32) + 1.0,
+            TwoFloat::from(0.0),
+            TwoFloat::from(2.0),
+            order,
+        )
+        .unwrap();
+        let expected = TwoFloat::from(2.0).powi(degree as i32 + 1) / (degree + 1) as f64 + 2.0;
+        assert!((value - expected).abs() < expected * 1e-30);
+    }
+
+    // Cubics with random coefficients and limits need two points
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let mut values = [TwoFloat::from(0.0); 6];
+        for value in values.iter_mut() {
+            *value = match try_get_twofloat_with_hi(rng.gen_range(-10.0..10.0)) {
+                Ok(value) => value,
+                Err(_) => return,
+            };
+        }
+        let [c0, c1, c2, c3, lower, upper] = values;
+        let f = |x: TwoFloat| ((c3 * x + c2) * x + c1) * x + c0;
+        let antiderivative =
+            |x: TwoFloat| (((c3 * 0.25 * x + c2 / 3.0) * x + c1 * 0.5) * x + c0) * x;
+        let expected = antiderivative(upper) - antiderivative(lower);
+        let value = quadrature::gauss_legendre(f, lower, upper, 2).unwrap();
+        assert!(
+            (value - expected).abs() < 1e-25,
+            "{:?} differs from {:?}",
+            value,
+            expected
+        );
+    });
+
+    for &(f, lower, upper, expected) in &cases()[..2] {
+        let value = quadrature::gauss_legendre(f, lower, upper, 30).unwrap();
+        assert!((value - expected).abs() < 1e-30);
+    }
+}
+
+#[test]
+fn error_test() {
+    let zero = TwoFloat::from(0.0);
+    let one = TwoFloat::from(1.0);
+    let tolerance = TwoFloat::from(1e-30);
+    assert!(matches!(
+        quadrature::gauss_kronrod(|x| x, TwoFloat::NAN, one, tolerance, 10),
+        Err(TwoFloatError::ConversionError)
+    ));
+    assert!(matches!(
+        quadrature::gauss_legendre(|x| x, zero, TwoFloat::NAN, 5),
+        Err(TwoFloatError::ConversionError)
+    ));
+    assert!(matches!(
+        quadrature::gauss_kronrod(|x| x.sqrt().recip(), zero, one, tolerance, 3),
+        Err(TwoFloatError::ConvergenceError)
+    ));
+    assert!(matches!(
+        quadrature::tanh_sinh(|x| x.sin(), zero, TwoFloat::from(100.0), tolerance, 2),
+        Err(TwoFloatError::ConvergenceError)
+    ));
+    assert_eq!(
+        quadrature::tanh_sinh(|x| x, one, one, tolerance, 2).unwrap(),
+        (zero, zero)
+    );
+}
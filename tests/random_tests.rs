@@ -0,0 +1,162 @@
+#![cfg(feature = "rand")]
+
+use rand::{distributions::Uniform, rngs::StdRng, Rng, SeedableRng};
+use twofloat::TwoFloat;
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+const SAMPLES: usize = 100000;
+
+#[test]
+fn standard_test() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut sum = TwoFloat::from(0.0);
+    let mut low_bits_used = false;
+    for _ in 0..SAMPLES {
+        let value: TwoFloat = rng.gen();
+
+        assert!(value.is_valid(), "Invalid sample {:?}", value);
+        assert!(
+            value.hi() >= 0.0 && value < 1.0,
+            "Sample {:?} out of range",
+            value
+        );
+        assert_eq!(
+            value.ldexp(106).fract(),
+            0.0,
+            "Sample {:?} has more than 106 bits",
+            value
+        );
+        low_bits_used |= value.lo() != 0.0;
+        sum += value;
+    }
+
+    assert!(low_bits_used, "Samples did not use the low word");
+    let mean = sum / SAMPLES as f64;
+    assert!(
+        (mean - 0.5).abs() < 0.01,
+        "Mean {:?} is not close to 0.5",
+        mean
+    );
+}
+
+#[test]
+fn uniform_test() {
+    let mut rng = rand::thread_rng();
+    repeated_test(|| {
+        let a = get_valid_twofloat(|x, _| x.abs() < 1e300);
+        let b = get_valid_twofloat(|x, _| x.abs() < 1e300);
+        if a == b {
+            return;
+        }
+
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+        let value = rng.sample(Uniform::new(low, high));
+        assert!(
+            value >= low && value < high,
+            "Sample {:?} out of range [{:?}, {:?})",
+            value,
+            low,
+            high
+        );
+
+        let value = rng.gen_range(low..=high);
+        assert!(
+            value >= low && value <= high,
+            "Sample {:?} out of range [{:?}, {:?}]",
+            value,
+            low,
+            high
+        );
+    });
+}
+
+#[test]
+fn uniform_narrow_test() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let low = TwoFloat::from(1.0);
+    let high = low + 2f64.powi(-100);
+    let distribution = Uniform::new(low, high);
+    let mut seen_interior = false;
+    for _ in 0..1000 {
+        let value = rng.sample(distribution);
+
+        assert!(value >= low && value < high);
+        seen_interior |= value != low;
+    }
+
+    assert!(seen_interior, "All samples were equal to the lower bound");
+    assert_eq!(rng.gen_range(low..=low), low);
+}
+
+#[test]
+#[should_panic]
+fn uniform_empty_test() {
+    Uniform::new(TwoFloat::from(1.0), TwoFloat::from(1.0));
+}
+
+#[test]
+#[should_panic]
+fn uniform_invalid_test() {
+    Uniform::new(TwoFloat::from(0.0), TwoFloat::INFINITY);
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn standard_normal_test() {
+    use twofloat::random::StandardNormal;
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut sum = TwoFloat::from(0.0);
+    let mut sum_squares = TwoFloat::from(0.0);
+    let mut within_one = 0;
+    for _ in 0..SAMPLES {
+        let value: TwoFloat = rng.sample(StandardNormal);
+
+        assert!(value.is_valid(), "Invalid sample {:?}", value);
+        sum += value;
+        sum_squares += value * value;
+        if value.abs() < 1.0 {
+            within_one += 1;
+        }
+    }
+
+    let mean = sum / SAMPLES as f64;
+    let variance = sum_squares / SAMPLES as f64 - mean * mean;
+    let fraction = within_one as f64 / SAMPLES as f64;
+    assert!(mean.abs() < 0.02, "Mean {:?} is not close to 0", mean);
+    assert!(
+        (variance - 1.0).abs() < 0.02,
+        "Variance {:?} is not close to 1",
+        variance
+    );
+    assert!(
+        (fraction - 0.6827).abs() < 0.01,
+        "Fraction {} within one standard deviation",
+        fraction
+    );
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn standard_normal_transform_test() {
+    // The polar transform takes the logarithm of a value in (0, 1), which
+    // must be accurate for the low bits of the samples to be meaningful
+    let mut rng = StdRng::seed_from_u64(4);
+    for _ in 0..SAMPLES {
+        let s: TwoFloat = rng.gen();
+        if s == 0.0 {
+            continue;
+        }
+
+        let roundtrip = s.ln().exp();
+        assert!(
+            ((roundtrip - s) / s).abs() < 1e-30,
+            "ln({:?}) is not accurate",
+            s
+        );
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "num_rational")]
+
+use core::convert::TryFrom;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use twofloat::TwoFloat;
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+#[test]
+fn to_big_rational_roundtrip_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+        let ratio = BigRational::try_from(source).unwrap();
+
+        assert!(
+            ratio.denom() > &BigInt::from(0),
+            "BigRational::try_from({:?}) has negative denominator",
+            source
+        );
+        assert_eq!(
+            ratio.reduced(),
+            ratio,
+            "BigRational::try_from({:?}) is not reduced",
+            source
+        );
+
+        let result = TwoFloat::from(&ratio);
+        assert!(
+            (result - source).abs() <= source.ulp() * 0.5,
+            "BigRational::try_from({:?}) did not round-trip",
+            source
+        );
+    });
+}
+
+#[test]
+fn to_big_rational_test() {
+    let a = TwoFloat::new_add(-3.0, 2f64.powi(-80));
+    let expected = BigRational::new(-(BigInt::from(3) << 80u32) + 1, BigInt::from(1) << 80u32);
+    assert_eq!(BigRational::try_from(a).unwrap(), expected);
+    assert_eq!(
+        BigRational::try_from(TwoFloat::MAX).unwrap(),
+        BigRational::from(((BigInt::from(1) << 107u32) - (BigInt::from(1) << 53u32) - 1) << 917u32)
+    );
+    assert!(BigRational::try_from(TwoFloat::INFINITY).is_err());
+}
+
+#[test]
+fn from_big_rational_test() {
+    let ratio = BigRational::new(BigInt::from(-355), BigInt::from(113));
+    assert_eq!(TwoFloat::from(&ratio), TwoFloat::from_ratio(-355, 113));
+
+    let ratio = BigRational::new_raw(BigInt::from(2), BigInt::from(-3));
+    assert_eq!(TwoFloat::from(ratio), TwoFloat::from_ratio(-2, 3));
+
+    let huge = BigRational::from(BigInt::from(10).pow(400));
+    assert_eq!(TwoFloat::from(huge), TwoFloat::INFINITY);
+}
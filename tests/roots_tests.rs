@@ -0,0 +1,248 @@
+#[cfg(feature = "math_funcs")]
+use rand::Rng;
+#[cfg(feature = "math_funcs")]
+use twofloat::Polynomial;
+use twofloat::{roots, TwoFloat, TwoFloatError};
+
+#[macro_use]
+pub mod common;
+
+#[cfg(feature = "math_funcs")]
+use common::{repeated_test, try_get_twofloat_with_hi};
+
+#[test]
+fn bracket_error_test() {
+    let zero = TwoFloat::from(0.0);
+    let f = |x: TwoFloat| x * x + 1.0;
+    let (a, b) = (TwoFloat::from(-1.0), TwoFloat::from(1.0));
+    assert!(matches!(
+        roots::bisection(f, a, b, zero, 200),
+        Err(TwoFloatError::BracketError)
+    ));
+    assert!(matches!(
+        roots::brent(f, a, b, zero, 200),
+        Err(TwoFloatError::BracketError)
+    ));
+    assert!(matches!(
+        roots::newton(|x| (f(x), x * 2.0), zero, a, b, zero, 100),
+        Err(TwoFloatError::BracketError)
+    ));
+    assert!(matches!(
+        roots::brent(f, TwoFloat::NAN, b, zero, 200),
+        Err(TwoFloatError::ConversionError)
+    ));
+}
+
+#[test]
+fn endpoint_root_test() {
+    let zero = TwoFloat::from(0.0);
+    let f = |x: TwoFloat| x - 1.0;
+    let (a, b) = (TwoFloat::from(1.0), TwoFloat::from(2.0));
+    assert_eq!(roots::bisection(f, a, b, zero, 200).unwrap(), 1.0);
+    assert_eq!(roots::brent(f, a, b, zero, 200).unwrap(), 1.0);
+    assert_eq!(roots::brent(f, b, a, zero, 200).unwrap(), 1.0);
+}
+
+#[test]
+fn bracketing_convergence_error_test() {
+    let zero = TwoFloat::from(0.0);
+    let f = |x: TwoFloat| x * x - 2.0;
+    let (a, b) = (TwoFloat::from(0.0), TwoFloat::from(2.0));
+    assert!(matches!(
+        roots::bisection(f, a, b, zero, 10),
+        Err(TwoFloatError::ConvergenceError)
+    ));
+    assert!(matches!(
+        roots::brent(f, a, b, zero, 3),
+        Err(TwoFloatError::ConvergenceError)
+    ));
+    assert!(roots::bisection(f, a, b, TwoFloat::from(1e-2), 10).is_ok());
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn bracketing_test() {
+    let mut rng = rand::thread_rng();
+    let zero = TwoFloat::from(0.0);
+    repeated_test(|| {
+        // Roots of x^2 - c for random c
+        let c = match try_get_twofloat_with_hi(rng.gen_range(0.01..100.0)) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        let expected = c.sqrt();
+        let f = |x: TwoFloat| x * x - c;
+        let upper = TwoFloat::from(11.0);
+
+        let root = roots::bisection(f, zero, upper, zero, 200).unwrap();
+        assert!((root - expected).abs() <= expected * 1e-30);
+
+        let root = roots::brent(f, zero, upper, zero, 200).unwrap();
+        assert!((root - expected).abs() <= expected * 1e-30);
+
+        let root = roots::brent(f, upper, zero, TwoFloat::from(1e-10), 200).unwrap();
+        assert!((root - expected).abs() <= 1e-10);
+    });
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn brent_test() {
+    let zero = TwoFloat::from(0.0);
+    // Wallis's equation x^3 - 2x - 5 = 0
+    let root = roots::brent(
+        |x| x * x * x - x * 2.0 - 5.0,
+        zero,
+        TwoFloat::from(3.0),
+        zero,
+        200,
+    )
+    .unwrap();
+    let expected: TwoFloat = "2.094551481542326591482386540579303".parse().unwrap();
+    assert!((root - expected).abs() < 1e-31);
+
+    let root = roots::brent(|x| x.exp() - 10.0, zero, TwoFloat::from(5.0), zero, 200).unwrap();
+    assert!((root - TwoFloat::from(10.0).ln()).abs() < 1e-31);
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn newton_halley_test() {
+    let mut rng = rand::thread_rng();
+    let zero = TwoFloat::from(0.0);
+    repeated_test(|| {
+        let c = match try_get_twofloat_with_hi(rng.gen_range(0.01..100.0)) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        let expected = c.cbrt();
+        let (lower, upper) = (zero, TwoFloat::from(5.0));
+        let guess = TwoFloat::from(rng.gen_range(0.0..5.0));
+
+        let root = roots::newton(
+            |x| (x * x * x - c, x * x * 3.0),
+            guess,
+            lower,
+            upper,
+            zero,
+            200,
+        )
+        .unwrap();
+        assert!((root - expected).abs() <= expected * 1e-30);
+
+        let root = roots::halley(
+            |x| (x * x * x - c, x * x * 3.0, x * 6.0),
+            guess,
+            lower,
+            upper,
+            zero,
+            200,
+        )
+        .unwrap();
+        assert!((root - expected).abs() <= expected * 1e-30);
+    });
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn newton_safeguard_test() {
+    let zero = TwoFloat::from(0.0);
+    // Newton's method diverges for atan starting outside |x| < 1.39
+    let root = roots::newton(
+        |x| (x.atan(), TwoFloat::from(1.0) / (x * x + 1.0)),
+        TwoFloat::from(3.0),
+        TwoFloat::from(-2.0),
+        TwoFloat::from(4.0),
+        zero,
+        200,
+    )
+    .unwrap();
+    assert!(root.abs() < 1e-30);
+
+    assert!(matches!(
+        roots::newton(
+            |x| (x.atan(), TwoFloat::from(1.0) / (x * x + 1.0)),
+            TwoFloat::from(3.0),
+            TwoFloat::from(-2.0),
+            TwoFloat::from(4.0),
+            zero,
+            2,
+        ),
+        Err(TwoFloatError::ConvergenceError)
+    ));
+}
+
+#[cfg(feature = "math_funcs")]
+// Returns the coefficients of the monic polynomial with the given real
+// roots.
+fn from_roots(values: &[TwoFloat]) -> Vec<TwoFloat> {
+    let mut coefficients = vec![TwoFloat::from(1.0)];
+    for &root in values {
+        coefficients.insert(0, TwoFloat::from(0.0));
+        for i in 0..coefficients.len() - 1 {
+            let next = coefficients[i + 1];
+            coefficients[i] -= root * next;
+        }
+    }
+    coefficients
+}
+
+#[cfg(feature = "math_funcs")]
+type Solver =
+    fn(Polynomial<'_, TwoFloat>, &mut [(TwoFloat, TwoFloat)], usize) -> Result<(), TwoFloatError>;
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn polynomial_roots_test() {
+    let solvers: [Solver; 2] = [roots::aberth, roots::durand_kerner];
+    let mut rng = rand::thread_rng();
+    let zero = TwoFloat::from(0.0);
+    for solver in solvers {
+        repeated_test(|| {
+            let degree = rng.gen_range(1..8);
+            // Well separated roots, so that they are well conditioned
+            let mut expected: Vec<_> = (0..degree)
+                .map(|i| TwoFloat::from(i as f64 * 1.5 - 5.0 + rng.gen_range(-0.25..0.25)))
+                .collect();
+            let coefficients = from_roots(&expected);
+
+            let mut found = vec![(zero, zero); degree];
+            solver(Polynomial::new(&coefficients), &mut found, 500).unwrap();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            for (&(re, im), &root) in found.iter().zip(&expected) {
+                assert!((re - root).abs() < 1e-24, "{:?} != {:?}", re, root);
+                assert!(im.abs() < 1e-24);
+            }
+        });
+
+        // Wilkinson's polynomial of degree 10
+        let expected: Vec<_> = (1..=10).map(|i| TwoFloat::from(i as f64)).collect();
+        let coefficients = from_roots(&expected);
+        let mut found = vec![(zero, zero); 10];
+        solver(Polynomial::new(&coefficients), &mut found, 500).unwrap();
+        found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        for (&(re, im), &root) in found.iter().zip(&expected) {
+            assert!((re - root).abs() < 1e-20);
+            assert!(im.abs() < 1e-20);
+        }
+
+        // Roots of unity with trailing zero coefficients
+        let coefficients = [-1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0].map(TwoFloat::from);
+        let mut found = [(zero, zero); 5];
+        solver(Polynomial::new(&coefficients), &mut found, 500).unwrap();
+        for &(re, im) in &found {
+            assert!(((re * re + im * im).sqrt() - 1.0).abs() < 1e-30);
+        }
+    }
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+#[should_panic]
+fn polynomial_degree_mismatch_test() {
+    let zero = TwoFloat::from(0.0);
+    let coefficients = [1.0, 2.0, 1.0].map(TwoFloat::from);
+    let mut found = [(zero, zero); 3];
+    let _ = roots::aberth(Polynomial::new(&coefficients), &mut found, 100);
+}
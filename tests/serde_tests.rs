@@ -4,8 +4,23 @@
 pub mod common;
 
 use common::*;
-use serde_test::{assert_de_tokens_error, assert_tokens, Token};
-use twofloat::{no_overlap, TwoFloat};
+use serde::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Token,
+};
+use twofloat::{consts::PI, no_overlap, TwoFloat};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct AsString(#[serde(with = "twofloat::serde::as_string")] TwoFloat);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct AsTuple(#[serde(with = "twofloat::serde::as_tuple")] TwoFloat);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct AsF64Lossy(#[serde(with = "twofloat::serde::as_f64_lossy")] TwoFloat);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Flexible(#[serde(with = "twofloat::serde::flexible")] TwoFloat);
 
 #[test]
 fn serialize_test() {
@@ -48,3 +63,238 @@ fn deserialize_invalid_test() {
         );
     });
 }
+
+// as_string tests
+
+// Returns the value rounded to 106 significant bits.
+fn round_106_bits(value: TwoFloat) -> TwoFloat {
+    let (mantissa, exponent, sign) = value.integer_decode();
+    TwoFloat::from(mantissa).ldexp(exponent as i32) * sign as f64
+}
+
+#[test]
+fn as_string_roundtrip_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+        let json = serde_json::to_string(&AsString(source)).unwrap();
+        let result: AsString = serde_json::from_str(&json).unwrap();
+
+        assert!(json.len() <= 48, "String {} is too long", json);
+        if round_106_bits(source) == source {
+            assert_eq!(result.0, source, "String {} did not roundtrip", json);
+        } else {
+            assert!(
+                (result.0 - source).abs() <= source.ulp(),
+                "String {} is not close to {:?}",
+                json,
+                source
+            );
+        }
+    });
+}
+
+#[test]
+fn as_string_test() {
+    let cases = [
+        (TwoFloat::from(0.0), "0"),
+        (TwoFloat::from(-0.0), "-0"),
+        (TwoFloat::from(1.5), "1.5"),
+        (TwoFloat::from(-1234.0), "-1234"),
+        (TwoFloat::from(0.1), "0.100000000000000005551115123125783"),
+        (TwoFloat::from(2.0).powi(-20), "9.5367431640625e-7"),
+        (TwoFloat::from(1e21), "1e21"),
+        (
+            TwoFloat::from(1e300),
+            "1.00000000000000005250476025520442e300",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        assert_tokens(
+            &AsString(*value),
+            &[
+                Token::NewtypeStruct { name: "AsString" },
+                Token::Str(expected),
+            ],
+        );
+    }
+
+    // PI has 107 significant bits, so the string holds its 106-bit rounding
+    assert_ser_tokens(
+        &AsString(PI),
+        &[
+            Token::NewtypeStruct { name: "AsString" },
+            Token::Str("3.14159265358979323846264338327951"),
+        ],
+    );
+
+    let parse_cases: [(&str, bool, &[u8], i32); 6] = [
+        ("0.1", false, &[1], -1),
+        ("+.5", false, &[5], -1),
+        ("1.e3", false, &[1], 3),
+        ("-2.5E-3", true, &[2, 5], -4),
+        ("000123.4500", false, &[1, 2, 3, 4, 5], -2),
+        ("1e-400", false, &[], 0),
+    ];
+    for (text, negative, digits, exp10) in parse_cases.iter() {
+        let result: AsString = serde_json::from_str(&format!("\"{}\"", text)).unwrap();
+        assert_eq!(
+            result.0,
+            TwoFloat::from_decimal(*negative, digits, *exp10).unwrap()
+        );
+    }
+
+    for text in [
+        "", "-", ".", "e5", "1e", "1.2.3", "1e+", "0x10", " 1", "nan", "inf",
+    ]
+    .iter()
+    {
+        assert_de_tokens_error::<AsString>(
+            &[Token::NewtypeStruct { name: "AsString" }, Token::Str(text)],
+            &format!(
+                "invalid value: string \"{}\", expected a decimal number",
+                text
+            ),
+        );
+    }
+    assert_de_tokens_error::<AsString>(
+        &[
+            Token::NewtypeStruct { name: "AsString" },
+            Token::Str("1e400"),
+        ],
+        "invalid TwoFloat conversion",
+    );
+}
+
+// as_tuple tests
+
+#[test]
+fn as_tuple_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+        assert_tokens(
+            &AsTuple(source),
+            &[
+                Token::NewtypeStruct { name: "AsTuple" },
+                Token::Tuple { len: 2 },
+                Token::F64(source.hi()),
+                Token::F64(source.lo()),
+                Token::TupleEnd,
+            ],
+        );
+    });
+}
+
+#[test]
+fn as_tuple_invalid_test() {
+    repeated_test(|| {
+        let (hi, lo) = get_valid_pair(|x, y| !no_overlap(x, y));
+        assert_de_tokens_error::<AsTuple>(
+            &[
+                Token::NewtypeStruct { name: "AsTuple" },
+                Token::Tuple { len: 2 },
+                Token::F64(hi),
+                Token::F64(lo),
+                Token::TupleEnd,
+            ],
+            "invalid TwoFloat conversion",
+        );
+    });
+}
+
+// as_f64_lossy tests
+
+#[test]
+fn as_f64_lossy_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+        assert_tokens(
+            &AsF64Lossy(TwoFloat::from(source.hi())),
+            &[
+                Token::NewtypeStruct { name: "AsF64Lossy" },
+                Token::F64(source.hi()),
+            ],
+        );
+        assert_ser_tokens(
+            &AsF64Lossy(source),
+            &[
+                Token::NewtypeStruct { name: "AsF64Lossy" },
+                Token::F64(source.hi()),
+            ],
+        );
+    });
+
+    assert_de_tokens_error::<AsF64Lossy>(
+        &[
+            Token::NewtypeStruct { name: "AsF64Lossy" },
+            Token::F64(f64::NAN),
+        ],
+        "invalid TwoFloat conversion",
+    );
+}
+
+// flexible tests
+
+#[test]
+fn flexible_test() {
+    repeated_test(|| {
+        let source = get_twofloat();
+        assert_tokens(
+            &Flexible(source),
+            &[
+                Token::NewtypeStruct { name: "Flexible" },
+                Token::Struct {
+                    name: "TwoFloat",
+                    len: 2,
+                },
+                Token::Str("hi"),
+                Token::F64(source.hi()),
+                Token::Str("lo"),
+                Token::F64(source.lo()),
+                Token::StructEnd,
+            ],
+        );
+        assert_de_tokens(
+            &Flexible(source),
+            &[
+                Token::NewtypeStruct { name: "Flexible" },
+                Token::Seq { len: Some(2) },
+                Token::F64(source.hi()),
+                Token::F64(source.lo()),
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens(
+            &Flexible(TwoFloat::from(source.hi())),
+            &[
+                Token::NewtypeStruct { name: "Flexible" },
+                Token::F64(source.hi()),
+            ],
+        );
+    });
+
+    let cases = [
+        ("2.5", TwoFloat::from(2.5)),
+        ("-3", TwoFloat::from(-3.0)),
+        ("18446744073709551615", TwoFloat::from(u64::MAX)),
+        ("\"0.1\"", TwoFloat::from_decimal(false, &[1], -1).unwrap()),
+        ("{\"lo\": 0.0, \"hi\": 4.0}", TwoFloat::from(4.0)),
+        (
+            "[1.0, 5.421010862427522e-20]",
+            TwoFloat::new_add(1.0, 2f64.powi(-64)),
+        ),
+        (
+            "[1.0, -8.673617379884035e-19]",
+            TwoFloat::new_add(1.0, -2f64.powi(-60)),
+        ),
+    ];
+    for (json, expected) in cases.iter() {
+        assert_eq!(serde_json::from_str::<Flexible>(json).unwrap().0, *expected);
+    }
+
+    for json in ["[1.0, 1.0]", "{\"hi\": 1.0, \"lo\": 1.0}", "\"1e999\""].iter() {
+        let error = serde_json::from_str::<Flexible>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid TwoFloat conversion"));
+    }
+    assert!(serde_json::from_str::<Flexible>("true").is_err());
+    assert!(serde_json::from_str::<Flexible>("\"abc\"").is_err());
+}
@@ -0,0 +1,143 @@
+use twofloat::{TwoFloat, TwoFloatX4, TwoFloatX8};
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+fn assert_same_bits(actual: TwoFloat, expected: TwoFloat) {
+    if expected.hi().is_nan() {
+        assert!(actual.hi().is_nan(), "{:?} is not NaN", actual);
+    } else {
+        assert_eq!(
+            (actual.hi().to_bits(), actual.lo().to_bits()),
+            (expected.hi().to_bits(), expected.lo().to_bits()),
+            "{:?} differs from {:?}",
+            actual,
+            expected
+        );
+    }
+}
+
+fn get_lanes<const N: usize>(mut gen: impl FnMut() -> TwoFloat) -> [TwoFloat; N] {
+    let mut values = [TwoFloat::from(0.0); N];
+    values.iter_mut().for_each(|x| *x = gen());
+    values
+}
+
+#[test]
+fn load_store_test() {
+    repeated_test(|| {
+        let values: [TwoFloat; 10] = get_lanes(get_twofloat);
+
+        let a = TwoFloatX4::load(&values[1..]);
+        let b = TwoFloatX8::load(&values[2..]);
+        for i in 0..4 {
+            assert_same_bits(a.extract(i), values[i + 1]);
+        }
+        for i in 0..8 {
+            assert_same_bits(b.extract(i), values[i + 2]);
+        }
+
+        let mut output = [TwoFloat::from(0.0); 9];
+        b.store(&mut output[1..]);
+        assert_eq!(output[0], 0.0);
+        for i in 0..8 {
+            assert_same_bits(output[i + 1], values[i + 2]);
+        }
+
+        let mut c = TwoFloatX4::splat(values[0]);
+        c.replace(3, values[1]);
+        assert_same_bits(c.extract(0), values[0]);
+        assert_same_bits(c.extract(3), values[1]);
+        assert_eq!(c.hi()[3].to_bits(), values[1].hi().to_bits());
+        assert_eq!(c.lo()[3].to_bits(), values[1].lo().to_bits());
+    });
+}
+
+#[test]
+#[should_panic]
+fn load_short_slice_test() {
+    TwoFloatX4::load(&[TwoFloat::from(1.0); 3]);
+}
+
+#[test]
+fn arithmetic_x4_test() {
+    repeated_test(|| {
+        let a: [TwoFloat; 4] = get_lanes(get_twofloat);
+        let b: [TwoFloat; 4] = get_lanes(get_twofloat);
+        let (x, y) = (TwoFloatX4::from(a), TwoFloatX4::from(b));
+
+        let mut assigned = x;
+        assigned += y;
+        assigned *= y;
+        for i in 0..4 {
+            assert_same_bits((x + y).extract(i), a[i] + b[i]);
+            assert_same_bits((x - y).extract(i), a[i] - b[i]);
+            assert_same_bits((x * y).extract(i), a[i] * b[i]);
+            assert_same_bits((x / y).extract(i), a[i] / b[i]);
+            assert_same_bits((-x).extract(i), -a[i]);
+            assert_same_bits(x.abs().extract(i), a[i].abs());
+            assert_same_bits(assigned.extract(i), (a[i] + b[i]) * b[i]);
+        }
+    });
+}
+
+#[test]
+fn arithmetic_x8_test() {
+    repeated_test(|| {
+        let a: [TwoFloat; 8] = get_lanes(get_twofloat);
+        let b: [TwoFloat; 8] = get_lanes(get_twofloat);
+        let (x, y) = (TwoFloatX8::from(a), TwoFloatX8::from(b));
+
+        let mut assigned = x;
+        assigned -= y;
+        assigned /= y;
+        for i in 0..8 {
+            assert_same_bits((x + y).extract(i), a[i] + b[i]);
+            assert_same_bits((x - y).extract(i), a[i] - b[i]);
+            assert_same_bits((x * y).extract(i), a[i] * b[i]);
+            assert_same_bits((x / y).extract(i), a[i] / b[i]);
+            assert_same_bits(assigned.extract(i), (a[i] - b[i]) / b[i]);
+        }
+
+        let sum = a.iter().fold(TwoFloat::from(0.0), |acc, &v| acc + v);
+        assert_same_bits(x.sum(), sum);
+    });
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn sqrt_test() {
+    repeated_test(|| {
+        let a: [TwoFloat; 8] = get_lanes(get_twofloat);
+        let result = TwoFloatX8::from(a).sqrt();
+        for (i, &value) in a.iter().enumerate() {
+            assert_same_bits(result.extract(i), value.sqrt());
+        }
+    });
+
+    let special = [0.0, -0.0, -1.0, f64::INFINITY].map(TwoFloat::from);
+    let result = TwoFloatX4::from(special).sqrt();
+    for (i, &value) in special.iter().enumerate() {
+        assert_same_bits(result.extract(i), value.sqrt());
+    }
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn exp_test() {
+    repeated_test(|| {
+        let a: [TwoFloat; 4] = get_lanes(|| get_valid_twofloat(|x, _| x.abs() < 800.0));
+        let result = TwoFloatX4::from(a).exp();
+        for (i, &value) in a.iter().enumerate() {
+            assert_same_bits(result.extract(i), value.exp());
+        }
+    });
+
+    let special = [0.0, -800.0, 800.0, f64::NAN].map(TwoFloat::from);
+    let result = TwoFloatX4::from(special).exp();
+    for (i, &value) in special.iter().enumerate() {
+        assert_same_bits(result.extract(i), value.exp());
+    }
+}
@@ -0,0 +1,141 @@
+use rand::Rng;
+use twofloat::{slice, TwoFloat};
+
+#[macro_use]
+pub mod common;
+
+use common::*;
+
+fn assert_same_bits(actual: &[TwoFloat], expected: &[TwoFloat]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        if e.hi().is_nan() {
+            assert!(a.hi().is_nan(), "{:?} is not NaN", a);
+        } else {
+            assert_eq!(
+                (a.hi().to_bits(), a.lo().to_bits()),
+                (e.hi().to_bits(), e.lo().to_bits()),
+                "{:?} differs from {:?}",
+                a,
+                e
+            );
+        }
+    }
+}
+
+fn get_slice(len: usize, gen: impl Fn() -> TwoFloat) -> Vec<TwoFloat> {
+    (0..len).map(|_| gen()).collect()
+}
+
+fn get_len() -> usize {
+    rand::thread_rng().gen_range(0..20)
+}
+
+type SliceOp = fn(&[TwoFloat], &[TwoFloat], &mut [TwoFloat]);
+type AssignOp = fn(&mut [TwoFloat], &[TwoFloat]);
+type ScalarOp = fn(TwoFloat, TwoFloat) -> TwoFloat;
+
+#[test]
+fn binary_ops_test() {
+    let ops: [(SliceOp, AssignOp, ScalarOp); 4] = [
+        (slice::add, slice::add_assign, |x, y| x + y),
+        (slice::sub, slice::sub_assign, |x, y| x - y),
+        (slice::mul, slice::mul_assign, |x, y| x * y),
+        (slice::div, slice::div_assign, |x, y| x / y),
+    ];
+
+    for _ in 0..10000 {
+        let len = get_len();
+        let a = get_slice(len, get_twofloat);
+        let b = get_slice(len, get_twofloat);
+
+        for &(op, assign_op, scalar_op) in &ops {
+            let expected: Vec<_> = a.iter().zip(&b).map(|(&x, &y)| scalar_op(x, y)).collect();
+
+            let mut out = vec![TwoFloat::from(0.0); len];
+            op(&a, &b, &mut out);
+            assert_same_bits(&out, &expected);
+
+            let mut assigned = a.clone();
+            assign_op(&mut assigned, &b);
+            assert_same_bits(&assigned, &expected);
+        }
+    }
+}
+
+#[test]
+fn scale_axpy_test() {
+    for _ in 0..10000 {
+        let len = get_len();
+        let factor = get_twofloat();
+        let x = get_slice(len, get_twofloat);
+        let y = get_slice(len, get_twofloat);
+
+        let expected: Vec<_> = x.iter().map(|&v| v * factor).collect();
+        let mut out = vec![TwoFloat::from(0.0); len];
+        slice::scale(&x, factor, &mut out);
+        assert_same_bits(&out, &expected);
+        let mut assigned = x.clone();
+        slice::scale_assign(&mut assigned, factor);
+        assert_same_bits(&assigned, &expected);
+
+        let expected: Vec<_> = x.iter().zip(&y).map(|(&a, &b)| factor * a + b).collect();
+        let mut result = y.clone();
+        slice::axpy(factor, &x, &mut result);
+        assert_same_bits(&result, &expected);
+    }
+}
+
+#[test]
+fn mul_add_test() {
+    for _ in 0..10000 {
+        let len = get_len();
+        let a = get_slice(len, get_twofloat);
+        let b = get_slice(len, get_twofloat);
+        let c = get_slice(len, get_twofloat);
+
+        let expected: Vec<_> = (0..len).map(|i| a[i].mul_add(b[i], c[i])).collect();
+        let mut out = vec![TwoFloat::from(0.0); len];
+        slice::mul_add(&a, &b, &c, &mut out);
+        assert_same_bits(&out, &expected);
+    }
+}
+
+#[cfg(feature = "math_funcs")]
+#[test]
+fn unary_ops_test() {
+    type UnaryOp = fn(&[TwoFloat], &mut [TwoFloat]);
+    type UnaryAssignOp = fn(&mut [TwoFloat]);
+    type UnaryScalarOp = fn(TwoFloat) -> TwoFloat;
+    let ops: [(UnaryOp, UnaryAssignOp, UnaryScalarOp); 3] = [
+        (slice::sqrt, slice::sqrt_assign, TwoFloat::sqrt),
+        (slice::exp, slice::exp_assign, TwoFloat::exp),
+        (slice::ln, slice::ln_assign, TwoFloat::ln),
+    ];
+
+    for _ in 0..2000 {
+        let len = get_len();
+        let a = get_slice(len, || get_valid_twofloat(|x, _| x.abs() < 800.0));
+
+        for &(op, assign_op, scalar_op) in &ops {
+            let expected: Vec<_> = a.iter().map(|&x| scalar_op(x)).collect();
+
+            let mut out = vec![TwoFloat::from(0.0); len];
+            op(&a, &mut out);
+            assert_same_bits(&out, &expected);
+
+            let mut assigned = a.clone();
+            assign_op(&mut assigned);
+            assert_same_bits(&assigned, &expected);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn length_mismatch_test() {
+    let a = [TwoFloat::from(1.0); 5];
+    let b = [TwoFloat::from(1.0); 4];
+    let mut out = [TwoFloat::from(0.0); 5];
+    slice::add(&a, &b, &mut out);
+}
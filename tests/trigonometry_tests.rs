@@ -50,7 +50,7 @@ fn sin_cos_test() {
         let result = cos * cos + sin * sin;
         let difference = (1.0 - result).abs();
         assert!(
-            difference < 1e-10,
+            difference < 1e-30,
             "cos^2 + sin^2 for {:?} returned value different from 1",
             source
         );
@@ -148,3 +148,279 @@ fn sin_cos_atan2_test() {
         );
     });
 }
+
+#[test]
+fn sin_cos_pi_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new_inclusive(-20.0, 20.0);
+
+    repeated_test(|| {
+        let source = loop {
+            let a = rng.sample(dist);
+            let b = random_float();
+            if let Ok(result) = TwoFloat::try_from((a, b)) {
+                break result;
+            }
+        };
+
+        let (sin, cos) = source.sin_cos_pi();
+        assert_eq!(
+            sin,
+            source.sin_pi(),
+            "sin_cos_pi({0:?}).0 not equal to sin_pi({0:?})",
+            source
+        );
+        assert_eq!(
+            cos,
+            source.cos_pi(),
+            "sin_cos_pi({0:?}).1 not equal to cos_pi({0:?})",
+            source
+        );
+
+        let angle = source * twofloat::consts::PI;
+        assert!(
+            (sin - angle.sin()).abs() < 1e-29,
+            "sin_pi({0:?}) differs from sin(π * {0:?})",
+            source
+        );
+        assert!(
+            (cos - angle.cos()).abs() < 1e-29,
+            "cos_pi({0:?}) differs from cos(π * {0:?})",
+            source
+        );
+
+        let tan = source.tan_pi();
+        assert!(
+            (tan - sin / cos).abs() < 1e-29 * (1.0 + tan.abs()),
+            "tan_pi({0:?}) differs from sin_pi({0:?}) / cos_pi({0:?})",
+            source
+        );
+    });
+}
+
+#[test]
+fn sin_cos_accuracy_test() {
+    let sqrt2 = TwoFloat::from(2.0).sqrt();
+    let sqrt3 = TwoFloat::from(3.0).sqrt();
+    let sqrt6 = TwoFloat::from(6.0).sqrt();
+    let cases = [
+        (15.0, (sqrt6 - sqrt2) * 0.25, (sqrt6 + sqrt2) * 0.25),
+        (22.5, (2.0 - sqrt2).sqrt() * 0.5, (2.0 + sqrt2).sqrt() * 0.5),
+        (30.0, TwoFloat::from(0.5), sqrt3 * 0.5),
+        (45.0, sqrt2 * 0.5, sqrt2 * 0.5),
+        (75.0, (sqrt6 + sqrt2) * 0.25, (sqrt6 - sqrt2) * 0.25),
+    ];
+
+    for &(degrees, sin, cos) in &cases {
+        let source = TwoFloat::from(degrees);
+        let radians = source * twofloat::consts::PI / 180.0;
+        let results = [
+            (source.sind(), source.cosd()),
+            (source / 180.0).sin_cos_pi(),
+            radians.sin_cos(),
+        ];
+        for &(result_sin, result_cos) in &results {
+            assert!(
+                (result_sin - sin).abs() < 1e-31,
+                "sine of {} degrees differs by {:?}",
+                degrees,
+                result_sin - sin
+            );
+            assert!(
+                (result_cos - cos).abs() < 1e-31,
+                "cosine of {} degrees differs by {:?}",
+                degrees,
+                result_cos - cos
+            );
+        }
+
+        let tan = sin / cos;
+        assert!(
+            (source.tand() - tan).abs() < tan * 1e-31,
+            "tangent of {} degrees differs by {:?}",
+            degrees,
+            source.tand() - tan
+        );
+    }
+}
+
+#[test]
+fn sin_cos_pi_exact_test() {
+    for n in -20i32..=20 {
+        let source = TwoFloat::from(n);
+        let sign = if n % 2 == 0 { 1.0 } else { -1.0 };
+        assert_eq!(source.sin_pi(), 0.0, "sin_pi({}) was not zero", n);
+        assert_eq!(
+            source.sin_pi().is_sign_negative(),
+            n < 0,
+            "sin_pi({}) had the wrong sign",
+            n
+        );
+        assert_eq!(source.cos_pi(), sign, "cos_pi({}) was not ±1", n);
+        assert_eq!(source.tan_pi(), 0.0, "tan_pi({}) was not zero", n);
+
+        let half = source + 0.5;
+        assert_eq!(half.sin_pi(), sign, "sin_pi({:?}) was not ±1", half);
+        assert_eq!(half.cos_pi(), 0.0, "cos_pi({:?}) was not zero", half);
+        assert!(
+            !half.tan_pi().is_valid(),
+            "tan_pi({:?}) was not infinite",
+            half
+        );
+
+        let quarter = source + 0.25;
+        assert_eq!(quarter.tan_pi(), 1.0, "tan_pi({:?}) was not one", quarter);
+    }
+}
+
+#[test]
+fn sind_cosd_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new_inclusive(-1000.0, 1000.0);
+
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        let angle = source.to_radians();
+        assert!(
+            (source.sind() - angle.sin()).abs() < 1e-10,
+            "sind({0:?}) differs from sin({0:?} in radians)",
+            source
+        );
+        assert!(
+            (source.cosd() - angle.cos()).abs() < 1e-10,
+            "cosd({0:?}) differs from cos({0:?} in radians)",
+            source
+        );
+
+        let tan = source.tand();
+        assert!(
+            (tan - angle.tan()).abs() < 1e-10 * (1.0 + tan.abs()),
+            "tand({0:?}) differs from tan({0:?} in radians)",
+            source
+        );
+    });
+}
+
+#[test]
+fn sind_cosd_exact_test() {
+    for n in -12i32..=12 {
+        let source = TwoFloat::from(30 * n);
+        let expected_sin = match n.rem_euclid(12) {
+            0 | 6 => Some(0.0),
+            1 | 5 => Some(0.5),
+            3 => Some(1.0),
+            7 | 11 => Some(-0.5),
+            9 => Some(-1.0),
+            _ => None,
+        };
+        let expected_cos = match n.rem_euclid(12) {
+            0 => Some(1.0),
+            2 | 10 => Some(0.5),
+            3 | 9 => Some(0.0),
+            4 | 8 => Some(-0.5),
+            6 => Some(-1.0),
+            _ => None,
+        };
+
+        if let Some(expected) = expected_sin {
+            assert_eq!(source.sind(), expected, "sind({:?}) was not exact", source);
+        }
+        if let Some(expected) = expected_cos {
+            assert_eq!(source.cosd(), expected, "cosd({:?}) was not exact", source);
+        }
+    }
+
+    for n in -8i32..=8 {
+        let source = TwoFloat::from(45 * n);
+        let expected = match n.rem_euclid(4) {
+            0 => 0.0,
+            1 => 1.0,
+            3 => -1.0,
+            _ => continue,
+        };
+        assert_eq!(source.tand(), expected, "tand({:?}) was not exact", source);
+    }
+}
+
+#[test]
+fn atan2_pi_special_test() {
+    let zero = TwoFloat::from(0.0);
+    let one = TwoFloat::from(1.0);
+    let cases = [
+        (zero, one),
+        (-zero, one),
+        (zero, -one),
+        (-zero, -one),
+        (zero, zero),
+        (-zero, -zero),
+        (one, zero),
+        (-one, -zero),
+        (TwoFloat::INFINITY, one),
+        (TwoFloat::NEG_INFINITY, -one),
+        (one, TwoFloat::INFINITY),
+        (-one, TwoFloat::INFINITY),
+        (one, TwoFloat::NEG_INFINITY),
+        (-one, TwoFloat::NEG_INFINITY),
+        (TwoFloat::INFINITY, TwoFloat::INFINITY),
+        (TwoFloat::INFINITY, TwoFloat::NEG_INFINITY),
+        (TwoFloat::NEG_INFINITY, TwoFloat::INFINITY),
+        (TwoFloat::NEG_INFINITY, TwoFloat::NEG_INFINITY),
+        (zero, TwoFloat::NAN),
+        (TwoFloat::NAN, one),
+    ];
+
+    for &(y, x) in &cases {
+        let expected = y.hi().atan2(x.hi()) / core::f64::consts::PI;
+        let result = y.atan2_pi(x);
+        if expected.is_nan() {
+            assert!(
+                result.hi().is_nan(),
+                "atan2_pi({:?}, {:?}) was not NaN",
+                y,
+                x
+            );
+        } else {
+            assert_eq!(
+                (result.hi().to_bits(), result.lo()),
+                (expected.to_bits(), 0.0),
+                "atan2_pi({:?}, {:?}) differs from f64::atan2",
+                y,
+                x
+            );
+        }
+    }
+}
+
+#[test]
+fn inverse_pi_test() {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::new_inclusive(-1.0, 1.0);
+
+    repeated_test(|| {
+        let source = TwoFloat::from(rng.sample(dist));
+        let pi = twofloat::consts::PI;
+        assert!(
+            (source.asin_pi() * pi - source.asin()).abs() < 1e-10,
+            "asin_pi({0:?}) differs from asin({0:?}) / π",
+            source
+        );
+        assert!(
+            (source.acos_pi() * pi - source.acos()).abs() < 1e-10,
+            "acos_pi({0:?}) differs from acos({0:?}) / π",
+            source
+        );
+        assert!(
+            (source.atan_pi() * pi - source.atan()).abs() < 1e-10,
+            "atan_pi({0:?}) differs from atan({0:?}) / π",
+            source
+        );
+
+        let other = TwoFloat::from(rng.sample(dist));
+        assert!(
+            (source.atan2_pi(other) * pi - source.atan2(other)).abs() < 1e-10,
+            "atan2_pi({0:?}, {1:?}) differs from atan2({0:?}, {1:?}) / π",
+            source,
+            other
+        );
+    });
+}